@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::time::Duration;
+use std::time::Instant;
+
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+
+use api::remote::parse_available_versions;
+use api::remote::parse_candidates;
+use api::remote::RemoteVersion;
+
+const CANDIDATES: &str = include_str!("../tests/fixtures/candidates.txt");
+const JAVA_VERSIONS: &str = include_str!("../tests/fixtures/versions-java.txt");
+
+// the parsers run on every refresh, so a run over one response must stay well below a frame
+const CANDIDATES_BUDGET: Duration = Duration::from_millis(5);
+const JAVA_VERSIONS_BUDGET: Duration = Duration::from_millis(2);
+const MERGE_BUDGET: Duration = Duration::from_millis(1);
+const BUDGET_RUNS: u32 = 100;
+
+// the recorded list is short, the real one has hundreds of rows over a dozen vendors
+fn java_versions() -> String {
+    let (head, rest) = JAVA_VERSIONS.split_at(JAVA_VERSIONS.find(" Temurin").unwrap());
+    let mut rows = String::new();
+    for vendor in 0..16 {
+        for version in 0..30 {
+            let name = if version == 0 {
+                format!("Vendor{}", vendor)
+            } else {
+                String::new()
+            };
+            let number = format!("{}.0.{}", 30 - version, vendor);
+            rows.push_str(&format!(
+                " {: <13} |     | {: <12} | v{: <6} |            | {}-v{}\n",
+                name, number, vendor, number, vendor
+            ));
+        }
+    }
+    let separator = rest.find("=====").unwrap();
+    format!("{}{}{}", head, rows, &rest[separator..])
+}
+
+// what the versions tab does for every row: combine the remote version with the local state
+fn merge(versions: &[RemoteVersion], local_versions: &HashMap<String, bool>) -> Vec<String> {
+    versions
+        .iter()
+        .map(|version| version.mk_string(local_versions))
+        .collect()
+}
+
+fn local_versions(versions: &[RemoteVersion]) -> HashMap<String, bool> {
+    versions
+        .iter()
+        .step_by(7)
+        .enumerate()
+        .map(|(index, version)| (version.id().clone(), index == 0))
+        .collect()
+}
+
+// only reports, since the time depends on the machine and whatever else it's doing
+fn check_budget(name: &str, budget: Duration, mut f: impl FnMut()) {
+    let start = Instant::now();
+    for _ in 0..BUDGET_RUNS {
+        f();
+    }
+    let average = start.elapsed() / BUDGET_RUNS;
+    if average > budget {
+        eprintln!(
+            "warning: {} took {:?} on average, the budget is {:?}",
+            name, average, budget
+        );
+    }
+}
+
+fn benchmarks(c: &mut Criterion) {
+    let java = java_versions();
+    let versions = parse_available_versions(&java);
+    let local = local_versions(&versions);
+
+    check_budget("parse_candidates", CANDIDATES_BUDGET, || {
+        black_box(parse_candidates(CANDIDATES.to_string()));
+    });
+    check_budget(
+        "parse_available_versions (java)",
+        JAVA_VERSIONS_BUDGET,
+        || {
+            black_box(parse_available_versions(&java));
+        },
+    );
+    check_budget("merge", MERGE_BUDGET, || {
+        black_box(merge(&versions, &local));
+    });
+
+    c.bench_function("parse_candidates", |b| {
+        b.iter(|| parse_candidates(black_box(CANDIDATES.to_string())))
+    });
+    c.bench_function("parse_available_versions (java)", |b| {
+        b.iter(|| parse_available_versions(black_box(&java)))
+    });
+    c.bench_function("merge", |b| {
+        b.iter(|| merge(black_box(&versions), black_box(&local)))
+    });
+}
+
+criterion_group!(benches, benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,191 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::category;
+use crate::remote::RemoteVersion;
+
+// the Java versions with long-term support
+const JAVA_LTS: [&str; 5] = ["8", "11", "17", "21", "25"];
+
+lazy_static! {
+    static ref PRE_RELEASE: Regex =
+        Regex::new(r"(?i)(^|[.\-_+])(alpha|beta|rc|cr|ea|snapshot|preview|m\d+)\d*($|[.\-_+])")
+            .unwrap();
+}
+
+// turns a shorthand into the identifier of one of the versions:
+// "latest", "default" or "stable", a prefix like "3.9", and for Java "lts", "21-tem" or "lts-zulu";
+// Java shorthands without a vendor stick to the vendor of the default version;
+// only identifiers resolve to versions their vendor deprecated
+pub fn resolve(
+    binary_name: &str,
+    alias: &str,
+    versions: &[RemoteVersion],
+    default_version: &str,
+) -> Option<String> {
+    let alias = alias.trim();
+    let default_version = default_version
+        .trim_start_matches('(')
+        .trim_end_matches(')');
+    if let Some(version) = versions.iter().find(|version| *version.id() == alias) {
+        return Some(version.id().clone());
+    }
+    let (alias, dist) = match alias.rsplit_once('-') {
+        Some((alias, dist))
+            if binary_name == "java" && versions.iter().any(|version| version.dist() == dist) =>
+        {
+            (alias, dist)
+        }
+        _ => (alias, default_dist(binary_name, versions, default_version)),
+    };
+    let candidates = versions
+        .iter()
+        .filter(|version| version.dist() == dist)
+        .filter(|version| !is_pre_release(version.version()))
+        .filter(|version| !is_deprecated(binary_name, version));
+
+    match alias.to_lowercase().as_str() {
+        "default" | "stable" => match versions
+            .iter()
+            .find(|version| *version.id() == default_version)
+        {
+            Some(version) if is_deprecated(binary_name, version) => newest(candidates),
+            version => version.map(|version| version.id().clone()),
+        },
+        "latest" => newest(candidates),
+        "lts" if binary_name == "java" => newest(candidates.filter(|version| {
+            JAVA_LTS
+                .iter()
+                .any(|major| matches_prefix(version.version(), major))
+        })),
+        prefix => newest(candidates.filter(|version| matches_prefix(version.version(), prefix))),
+    }
+}
+
+// the newest release of the same major version from the same vendor, if it isn't the given one;
+// for Java these updates are mostly security fixes
+pub fn newer_update(binary_name: &str, id: &str, versions: &[RemoteVersion]) -> Option<String> {
+    let current = versions.iter().find(|version| *version.id() == id)?;
+    let major = current.version().split(['.', '-', '+', '_']).next()?;
+    newest(
+        versions
+            .iter()
+            .filter(|version| version.dist() == current.dist())
+            .filter(|version| !is_pre_release(version.version()))
+            .filter(|version| !is_deprecated(binary_name, version))
+            .filter(|version| matches_prefix(version.version(), major)),
+    )
+    .filter(|newest| newest != id)
+}
+
+// the vendor suffix of the default version, e.g. tem; other candidates have none
+fn default_dist<'a>(
+    binary_name: &str,
+    versions: &'a [RemoteVersion],
+    default_version: &str,
+) -> &'a str {
+    if binary_name != "java" {
+        return "";
+    }
+    versions
+        .iter()
+        .find(|version| *version.id() == default_version)
+        .map(|version| version.dist())
+        .unwrap_or("tem")
+}
+
+fn is_deprecated(binary_name: &str, version: &RemoteVersion) -> bool {
+    category::deprecation(binary_name, version.id(), version.dist()).is_some()
+}
+
+fn is_pre_release(version: &str) -> bool {
+    PRE_RELEASE.is_match(version)
+}
+
+// 3.9 matches 3.9 and 3.9.6, but not 3.90
+fn matches_prefix(version: &str, prefix: &str) -> bool {
+    version == prefix
+        || version
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with(['.', '-', '+', '_']))
+}
+
+fn newest<'a>(versions: impl Iterator<Item = &'a RemoteVersion>) -> Option<String> {
+    versions
+        .max_by(|v1, v2| alphanumeric_sort::compare_str(v1.version(), v2.version()))
+        .map(|version| version.id().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::remote::parse_available_versions;
+
+    const JAVA: &str = include_str!("../tests/fixtures/versions-java.txt");
+    const GRADLE: &str = include_str!("../tests/fixtures/versions-gradle.txt");
+
+    fn java(alias: &str) -> Option<String> {
+        resolve(
+            "java",
+            alias,
+            &parse_available_versions(JAVA),
+            "(17.0.6-tem)",
+        )
+    }
+
+    fn gradle(alias: &str) -> Option<String> {
+        resolve(
+            "gradle",
+            alias,
+            &parse_available_versions(GRADLE),
+            "(8.0.2)",
+        )
+    }
+
+    #[test]
+    fn resolves_identifiers_to_themselves() {
+        assert_eq!(java("11.0.18-tem").as_deref(), Some("11.0.18-tem"));
+        assert_eq!(gradle("7.5.1").as_deref(), Some("7.5.1"));
+    }
+
+    #[test]
+    fn resolves_keywords() {
+        assert_eq!(java("latest").as_deref(), Some("17.0.6-tem"));
+        assert_eq!(java("lts").as_deref(), Some("17.0.6-tem"));
+        assert_eq!(gradle("default").as_deref(), Some("8.0.2"));
+        assert_eq!(gradle("latest").as_deref(), Some("8.0.2"));
+        assert_eq!(gradle("lts"), None);
+    }
+
+    #[test]
+    fn resolves_prefixes() {
+        assert_eq!(java("11").as_deref(), Some("11.0.18-tem"));
+        assert_eq!(java("11-tem").as_deref(), Some("11.0.18-tem"));
+        assert_eq!(java("11-zulu"), None);
+        assert_eq!(gradle("7").as_deref(), Some("7.6"));
+        assert_eq!(gradle("7.5").as_deref(), Some("7.5.1"));
+        assert_eq!(gradle("7.55"), None);
+    }
+
+    #[test]
+    fn finds_newer_updates() {
+        let java = parse_available_versions(JAVA);
+        let gradle = parse_available_versions(GRADLE);
+        assert_eq!(newer_update("java", "17.0.6-tem", &java), None);
+        assert_eq!(
+            newer_update("gradle", "7.5.1", &gradle).as_deref(),
+            Some("7.6")
+        );
+        assert_eq!(newer_update("gradle", "8.0.2", &gradle), None);
+        assert_eq!(newer_update("gradle", "0.0.1", &gradle), None);
+    }
+
+    #[test]
+    fn skips_pre_releases() {
+        assert!(is_pre_release("8.1-rc-1"));
+        assert!(is_pre_release("22.ea.3"));
+        assert!(is_pre_release("4.0.0-M1"));
+        assert!(!is_pre_release("17.0.6"));
+        assert!(!is_pre_release("22.3.r17"));
+    }
+}
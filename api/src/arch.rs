@@ -0,0 +1,269 @@
+use std::env::consts;
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::local::home_path;
+
+// enough for the ELF and Mach-O headers and the architectures of a fat binary
+const HEADER_SIZE: usize = 512;
+// java class files start with the same magic as fat binaries, but never with this few architectures
+const MAX_FAT_ARCHS: u32 = 20;
+const ROSETTA_RUNTIME: &str = "/Library/Apple/usr/libexec/oah/libRosettaRuntime";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Arch {
+    X86,
+    X86_64,
+    Arm,
+    Aarch64,
+    Ppc64,
+    S390x,
+    Riscv64,
+    Unknown,
+}
+
+impl Arch {
+    pub fn host() -> Arch {
+        match consts::ARCH {
+            "x86" => Arch::X86,
+            "x86_64" => Arch::X86_64,
+            "arm" => Arch::Arm,
+            "aarch64" => Arch::Aarch64,
+            "powerpc64" => Arch::Ppc64,
+            "s390x" => Arch::S390x,
+            "riscv64" => Arch::Riscv64,
+            _ => Arch::Unknown,
+        }
+    }
+
+    fn from_elf(machine: u16) -> Arch {
+        match machine {
+            3 => Arch::X86,
+            21 => Arch::Ppc64,
+            22 => Arch::S390x,
+            40 => Arch::Arm,
+            62 => Arch::X86_64,
+            183 => Arch::Aarch64,
+            243 => Arch::Riscv64,
+            _ => Arch::Unknown,
+        }
+    }
+
+    fn from_mach_o(cpu_type: u32) -> Arch {
+        match cpu_type {
+            0x0000_0007 => Arch::X86,
+            0x0100_0007 => Arch::X86_64,
+            0x0000_000c => Arch::Arm,
+            0x0100_000c => Arch::Aarch64,
+            0x0100_0012 => Arch::Ppc64,
+            _ => Arch::Unknown,
+        }
+    }
+
+    // 64 bit hosts run the 32 bit binaries of their family as well
+    fn runs_on(&self, host: Arch) -> bool {
+        *self == host || (*self == Arch::X86 && host == Arch::X86_64)
+    }
+}
+
+impl fmt::Display for Arch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Arch::X86 => "x86",
+            Arch::X86_64 => "x86_64",
+            Arch::Arm => "arm",
+            Arch::Aarch64 => "aarch64",
+            Arch::Ppc64 => "ppc64",
+            Arch::S390x => "s390x",
+            Arch::Riscv64 => "riscv64",
+            Arch::Unknown => "an unknown architecture",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchMismatch {
+    pub binary: PathBuf,
+    pub found: Vec<Arch>,
+    pub host: Arch,
+}
+
+impl ArchMismatch {
+    pub fn found(&self) -> String {
+        self.found
+            .iter()
+            .map(|arch| arch.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    pub fn suggestion(&self) -> String {
+        if cfg!(target_os = "macos")
+            && self.host == Arch::Aarch64
+            && self.found.contains(&Arch::X86_64)
+        {
+            format!(
+                "Install Rosetta 2 with 'softwareupdate --install-rosetta', \
+                 or reinstall the version to get the {} build.",
+                self.host
+            )
+        } else {
+            format!(
+                "Reinstall the version to get the {} build, or pick a version that is built for {}.",
+                self.host, self.host
+            )
+        }
+    }
+}
+
+// looks at the first native binary of the version; scripts and jars don't tell anything
+pub fn check_version(binary_name: &str, version: &str) -> io::Result<Option<ArchMismatch>> {
+    let bin = home_path(binary_name, version)?.join("bin");
+    if !bin.is_dir() {
+        return Ok(None);
+    }
+    let mut paths: Vec<PathBuf> = fs::read_dir(&bin)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+    let host = Arch::host();
+    for path in paths {
+        if let Some(found) = binary_archs(&path)?.filter(|found| !found.is_empty()) {
+            if host == Arch::Unknown
+                || found.iter().any(|arch| arch.runs_on(host))
+                || runs_translated(host, &found)
+            {
+                return Ok(None);
+            }
+            return Ok(Some(ArchMismatch {
+                binary: path,
+                found,
+                host,
+            }));
+        }
+    }
+    Ok(None)
+}
+
+// Apple silicon runs x86_64 binaries once Rosetta is installed
+fn runs_translated(host: Arch, found: &[Arch]) -> bool {
+    cfg!(target_os = "macos")
+        && host == Arch::Aarch64
+        && found.contains(&Arch::X86_64)
+        && Path::new(ROSETTA_RUNTIME).exists()
+}
+
+// the architectures of an ELF or Mach-O binary, none for anything else
+fn binary_archs(path: &Path) -> io::Result<Option<Vec<Arch>>> {
+    let mut header = Vec::with_capacity(HEADER_SIZE);
+    File::open(path)?
+        .take(HEADER_SIZE as u64)
+        .read_to_end(&mut header)?;
+    Ok(header_archs(&header))
+}
+
+fn header_archs(header: &[u8]) -> Option<Vec<Arch>> {
+    if header.len() < 8 {
+        return None;
+    }
+    match header[..4] {
+        [0x7f, b'E', b'L', b'F'] if header.len() >= 20 => {
+            let machine = [header[18], header[19]];
+            let machine = match header[5] {
+                2 => u16::from_be_bytes(machine),
+                _ => u16::from_le_bytes(machine),
+            };
+            Some(vec![Arch::from_elf(machine)])
+        }
+        [0xce, 0xfa, 0xed, 0xfe] | [0xcf, 0xfa, 0xed, 0xfe] => {
+            Some(vec![Arch::from_mach_o(read_u32_le(header, 4))])
+        }
+        [0xfe, 0xed, 0xfa, 0xce] | [0xfe, 0xed, 0xfa, 0xcf] => {
+            Some(vec![Arch::from_mach_o(read_u32_be(header, 4))])
+        }
+        [0xca, 0xfe, 0xba, 0xbe] | [0xca, 0xfe, 0xba, 0xbf] => {
+            let count = read_u32_be(header, 4);
+            let entry_size = if header[3] == 0xbf { 32 } else { 20 };
+            if count == 0 || count > MAX_FAT_ARCHS {
+                None
+            } else {
+                Some(
+                    (0..count as usize)
+                        .map(|index| 8 + index * entry_size)
+                        .take_while(|offset| offset + 4 <= header.len())
+                        .map(|offset| Arch::from_mach_o(read_u32_be(header, offset)))
+                        .collect(),
+                )
+            }
+        }
+        _ => None,
+    }
+}
+
+fn read_u32_le(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap_or_default())
+}
+
+fn read_u32_be(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes(bytes[offset..offset + 4].try_into().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elf(machine: u16) -> Vec<u8> {
+        let mut header = vec![0x7f, b'E', b'L', b'F', 2, 1];
+        header.resize(18, 0);
+        header.extend_from_slice(&machine.to_le_bytes());
+        header
+    }
+
+    #[test]
+    fn reads_elf_machines() {
+        assert_eq!(header_archs(&elf(62)), Some(vec![Arch::X86_64]));
+        assert_eq!(header_archs(&elf(183)), Some(vec![Arch::Aarch64]));
+    }
+
+    #[test]
+    fn reads_mach_o_cpu_types() {
+        let x86_64 = [0xcf, 0xfa, 0xed, 0xfe, 0x07, 0x00, 0x00, 0x01];
+        let aarch64 = [0xcf, 0xfa, 0xed, 0xfe, 0x0c, 0x00, 0x00, 0x01];
+        assert_eq!(header_archs(&x86_64), Some(vec![Arch::X86_64]));
+        assert_eq!(header_archs(&aarch64), Some(vec![Arch::Aarch64]));
+    }
+
+    #[test]
+    fn reads_every_arch_of_a_universal_binary() {
+        let mut header = vec![0xca, 0xfe, 0xba, 0xbe, 0, 0, 0, 2];
+        for cpu_type in [0x0100_0007u32, 0x0100_000c] {
+            header.extend_from_slice(&cpu_type.to_be_bytes());
+            header.extend_from_slice(&[0; 16]);
+        }
+        assert_eq!(
+            header_archs(&header),
+            Some(vec![Arch::X86_64, Arch::Aarch64])
+        );
+        // a class file has the same magic, followed by its version
+        assert_eq!(header_archs(&[0xca, 0xfe, 0xba, 0xbe, 0, 0, 0, 55]), None);
+    }
+
+    #[test]
+    fn ignores_truncated_headers() {
+        assert_eq!(header_archs(&[0x7f, b'E', b'L', b'F']), None);
+        assert_eq!(header_archs(&elf(62)[..12]), None);
+        assert_eq!(header_archs(&[0xcf, 0xfa, 0xed, 0xfe, 0x07]), None);
+        // the architectures cut off by the end of the header are left out
+        let mut header = vec![0xca, 0xfe, 0xba, 0xbe, 0, 0, 0, 2];
+        header.extend_from_slice(&0x0100_0007u32.to_be_bytes());
+        assert_eq!(header_archs(&header), Some(vec![Arch::X86_64]));
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use crate::dirs::SdkmanDirs;
+use crate::fsutil;
+use crate::install;
+use crate::remote::SdkmanApiError;
+
+// a download still being written isn't touched
+const SETTLED_AFTER: Duration = Duration::from_secs(600);
+
+// archives with the same content, e.g. a build the vendor published under two versions
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateArchives {
+    pub kept: PathBuf,
+    pub linked: Vec<PathBuf>,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeduplicationReport {
+    pub duplicates: Vec<DuplicateArchives>,
+    pub saved: u64,
+}
+
+// replaces identical archives in the download directory by hard links to one of them,
+// so SDKMAN still finds every archive under its own name
+pub fn deduplicate() -> Result<DeduplicationReport, SdkmanApiError> {
+    let dir = SdkmanDirs::resolve()?.download_dir().to_path_buf();
+    deduplicate_in(&dir, &install::checksum)
+}
+
+fn deduplicate_in(
+    dir: &Path,
+    checksum: &dyn Fn(&Path) -> Result<String, SdkmanApiError>,
+) -> Result<DeduplicationReport, SdkmanApiError> {
+    // only archives of the same size can be the same, which saves most of the checksums
+    let mut by_size: BTreeMap<u64, Vec<PathBuf>> = BTreeMap::new();
+    for (archive, size) in archives(dir)? {
+        by_size.entry(size).or_default().push(archive);
+    }
+    let mut report = DeduplicationReport::default();
+    for (size, archives) in by_size.into_iter().filter(|(_, a)| a.len() > 1) {
+        let mut by_checksum: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+        for archive in archives {
+            by_checksum
+                .entry(checksum(&archive)?)
+                .or_default()
+                .push(archive);
+        }
+        for mut same in by_checksum.into_values().filter(|same| same.len() > 1) {
+            same.sort();
+            let kept = same.remove(0);
+            let mut linked = Vec::new();
+            for archive in same {
+                // linked by an earlier run already
+                if is_same_file(&kept, &archive)? {
+                    continue;
+                }
+                fsutil::replace_with_hard_link(&kept, &archive)?;
+                linked.push(archive);
+            }
+            if !linked.is_empty() {
+                report.saved += size * linked.len() as u64;
+                report
+                    .duplicates
+                    .push(DuplicateArchives { kept, linked, size });
+            }
+        }
+    }
+    Ok(report)
+}
+
+// the regular files of the directory with their sizes, leaving out hidden and fresh ones
+fn archives(dir: &Path) -> io::Result<Vec<(PathBuf, u64)>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let settled = SystemTime::now() - SETTLED_AFTER;
+    let mut archives = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let metadata = fs::symlink_metadata(entry.path())?;
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        let fresh = metadata
+            .modified()
+            .map_or(true, |modified| modified > settled);
+        if metadata.is_file() && !hidden && !fresh && metadata.len() > 0 {
+            archives.push((entry.path(), metadata.len()));
+        }
+    }
+    Ok(archives)
+}
+
+#[cfg(unix)]
+fn is_same_file(a: &Path, b: &Path) -> io::Result<bool> {
+    use std::os::unix::fs::MetadataExt;
+    let (a, b) = (fs::metadata(a)?, fs::metadata(b)?);
+    Ok(a.dev() == b.dev() && a.ino() == b.ino())
+}
+
+#[cfg(not(unix))]
+fn is_same_file(_a: &Path, _b: &Path) -> io::Result<bool> {
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs::File;
+    use std::process;
+
+    use super::*;
+
+    fn write_settled(path: &Path, content: &str) {
+        fs::write(path, content).unwrap();
+        let settled = SystemTime::now() - 2 * SETTLED_AFTER;
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(settled)
+            .unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn links_identical_archives() {
+        let dir = env::temp_dir().join(format!("sdkman-ui-archives-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        write_settled(&dir.join("java-21.0.2-tem.bin"), "build 13");
+        write_settled(&dir.join("java-21.0.2.fx-tem.bin"), "build 13");
+        write_settled(&dir.join("java-21.0.1-tem.bin"), "build 12");
+        fs::write(dir.join("java-22-tem.bin"), "build 13").unwrap();
+        let checksum =
+            |path: &Path| -> Result<String, SdkmanApiError> { Ok(fs::read_to_string(path)?) };
+
+        let report = deduplicate_in(&dir, &checksum).unwrap();
+        assert_eq!(
+            report.duplicates,
+            vec![DuplicateArchives {
+                kept: dir.join("java-21.0.2-tem.bin"),
+                linked: vec![dir.join("java-21.0.2.fx-tem.bin")],
+                size: 8,
+            }]
+        );
+        assert_eq!(report.saved, 8);
+        assert_eq!(
+            fs::read_to_string(dir.join("java-21.0.2.fx-tem.bin")).unwrap(),
+            "build 13"
+        );
+        // the download that may still be running is left alone
+        assert!(!is_same_file(
+            &dir.join("java-21.0.2-tem.bin"),
+            &dir.join("java-22-tem.bin")
+        )
+        .unwrap());
+        // a second run finds nothing left to save
+        let report = deduplicate_in(&dir, &checksum).unwrap();
+        assert_eq!(report.saved, 0);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
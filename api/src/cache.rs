@@ -0,0 +1,121 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use lazy_static::lazy_static;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Namespace {
+    // the list of candidates, which rarely changes
+    Catalog,
+    // the versions of a single candidate, which change whenever something gets released
+    Versions,
+    // the text of the candidates' homepages, which is only skimmed
+    Homepages,
+}
+
+impl Namespace {
+    fn dir_name(&self) -> &'static str {
+        match self {
+            Namespace::Catalog => "catalog",
+            Namespace::Versions => "versions",
+            Namespace::Homepages => "homepages",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CacheSettings {
+    pub dir: PathBuf,
+    pub catalog_ttl: Duration,
+    pub versions_ttl: Duration,
+}
+
+impl CacheSettings {
+    fn ttl(&self, namespace: Namespace) -> Duration {
+        match namespace {
+            Namespace::Catalog | Namespace::Homepages => self.catalog_ttl,
+            Namespace::Versions => self.versions_ttl,
+        }
+    }
+    fn path(&self, namespace: Namespace, key: &str) -> PathBuf {
+        self.dir.join(namespace.dir_name()).join(key)
+    }
+}
+
+lazy_static! {
+    static ref SETTINGS: RwLock<Option<CacheSettings>> = RwLock::new(None);
+}
+
+// nothing is cached until the cache has been configured
+pub fn configure(settings: CacheSettings) {
+    if let Ok(mut current) = SETTINGS.write() {
+        *current = Some(settings);
+    }
+}
+
+fn settings() -> Option<CacheSettings> {
+    SETTINGS.read().ok().and_then(|settings| settings.clone())
+}
+
+pub fn dir() -> Option<PathBuf> {
+    settings().map(|settings| settings.dir)
+}
+
+pub(crate) fn get(namespace: Namespace, key: &str) -> Option<String> {
+    let settings = settings()?;
+    let path = settings.path(namespace, key);
+    let age = fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())?;
+    if age > settings.ttl(namespace) {
+        None
+    } else {
+        fs::read_to_string(path).ok()
+    }
+}
+
+pub(crate) fn put(namespace: Namespace, key: &str, value: &str) {
+    if let Some(settings) = settings() {
+        let path = settings.path(namespace, key);
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, value));
+        // a failing cache must never fail the request itself
+        if let Err(e) = written {
+            tracing::warn!("Failed to cache {}: {}", path.display(), e);
+        }
+    }
+}
+
+pub fn invalidate(namespace: Namespace) -> std::io::Result<()> {
+    match settings() {
+        Some(settings) => {
+            let dir = settings.dir.join(namespace.dir_name());
+            if dir.exists() {
+                fs::remove_dir_all(dir)
+            } else {
+                Ok(())
+            }
+        }
+        None => Ok(()),
+    }
+}
+
+pub fn invalidate_entry(namespace: Namespace, key: &str) -> std::io::Result<()> {
+    match settings() {
+        Some(settings) => {
+            let path = settings.path(namespace, key);
+            if path.exists() {
+                fs::remove_file(path)
+            } else {
+                Ok(())
+            }
+        }
+        None => Ok(()),
+    }
+}
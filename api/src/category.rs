@@ -0,0 +1,221 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::RwLock;
+
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[cfg(feature = "native")]
+use crate::cache;
+#[cfg(feature = "native")]
+use crate::cache::Namespace;
+#[cfg(feature = "native")]
+use crate::remote::SdkmanApiError;
+#[cfg(feature = "native")]
+use crate::util;
+
+// the defaults the app ships with, until a metadata file says otherwise
+const BUNDLED: &str = include_str!("categories.toml");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    Jdk,
+    Language,
+    BuildTool,
+    Framework,
+    Platform,
+    Tool,
+}
+
+// the grouping of the candidates and the ones to suggest, so they can change without a release
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Metadata {
+    pub suggested: Vec<String>,
+    pub categories: BTreeMap<String, Category>,
+    pub deprecated: Vec<Deprecation>,
+}
+
+// a version a vendor has pulled or deprecated, or all versions of a vendor if only dist is given
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Deprecation {
+    pub candidate: String,
+    pub version: Option<String>,
+    // the vendor suffix of Java identifiers, e.g. open
+    pub dist: Option<String>,
+    pub reason: String,
+}
+
+impl Deprecation {
+    fn matches(&self, binary_name: &str, id: &str, dist: &str) -> bool {
+        self.candidate == binary_name
+            && match (&self.version, &self.dist) {
+                (Some(version), _) => version == id,
+                (None, Some(deprecated)) => deprecated == dist,
+                (None, None) => false,
+            }
+    }
+}
+
+impl Metadata {
+    pub fn parse(text: &str) -> Result<Metadata, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    fn bundled() -> Metadata {
+        Metadata::parse(BUNDLED).unwrap_or_default()
+    }
+
+    // a fetched file only needs to list what differs from the bundled one
+    fn merged_into(self, mut base: Metadata) -> Metadata {
+        if !self.suggested.is_empty() {
+            base.suggested = self.suggested;
+        }
+        base.categories.extend(self.categories);
+        base.deprecated.extend(self.deprecated);
+        base
+    }
+
+    fn deprecation(&self, binary_name: &str, id: &str, dist: &str) -> Option<String> {
+        self.deprecated
+            .iter()
+            .find(|deprecation| deprecation.matches(binary_name, id, dist))
+            .map(|deprecation| deprecation.reason.clone())
+    }
+}
+
+lazy_static! {
+    static ref METADATA: RwLock<Metadata> = RwLock::new(Metadata::bundled());
+}
+
+// applies a fetched metadata file on top of the bundled defaults
+pub fn set_metadata(metadata: Metadata) {
+    if let Ok(mut current) = METADATA.write() {
+        *current = metadata.merged_into(Metadata::bundled());
+    }
+}
+
+pub fn suggested() -> Vec<String> {
+    METADATA
+        .read()
+        .map(|metadata| metadata.suggested.clone())
+        .unwrap_or_default()
+}
+
+// why a version shouldn't be installed anymore, if its vendor deprecated it
+pub fn deprecation(binary_name: &str, id: &str, dist: &str) -> Option<String> {
+    METADATA
+        .read()
+        .ok()
+        .and_then(|metadata| metadata.deprecation(binary_name, id, dist))
+}
+
+impl Category {
+    pub fn of(binary_name: &str) -> Category {
+        METADATA
+            .read()
+            .ok()
+            .and_then(|metadata| metadata.categories.get(binary_name).copied())
+            .unwrap_or(Category::Tool)
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Category::Jdk => "JDK",
+            Category::Language => "Language",
+            Category::BuildTool => "Build tool",
+            Category::Framework => "Framework",
+            Category::Platform => "Server & platform",
+            Category::Tool => "Tool",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// the metadata file at the given URL, cached like the catalog
+#[cfg(feature = "native")]
+pub fn fetch_metadata(url: &str) -> Result<Metadata, SdkmanApiError> {
+    let key = cache_key(url);
+    if let Some(metadata) =
+        cache::get(Namespace::Catalog, &key).and_then(|text| Metadata::parse(&text).ok())
+    {
+        return Ok(metadata);
+    }
+    let text = util::fetch_text(url)?;
+    // only a file that parses is worth keeping
+    let metadata = Metadata::parse(&text)
+        .map_err(|_| SdkmanApiError::BadRequest("the metadata file is malformed"))?;
+    cache::put(Namespace::Catalog, &key, &text);
+    Ok(metadata)
+}
+
+// another URL is another file
+#[cfg(feature = "native")]
+fn cache_key(url: &str) -> String {
+    format!("categories-{}", util::cache_key(url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_bundled_metadata() {
+        let metadata = Metadata::bundled();
+        assert_eq!(metadata.categories.get("java"), Some(&Category::Jdk));
+        assert_eq!(metadata.categories.get("sbt"), Some(&Category::BuildTool));
+        assert!(metadata.suggested.contains(&"java".to_string()));
+    }
+
+    #[test]
+    fn fetched_metadata_overrides_the_bundled_one() {
+        let fetched = Metadata::parse(
+            r#"
+            [categories]
+            jbang = "build_tool"
+            sbt = "tool"
+            "#,
+        )
+        .unwrap();
+        let merged = fetched.merged_into(Metadata::bundled());
+        assert_eq!(merged.categories.get("jbang"), Some(&Category::BuildTool));
+        assert_eq!(merged.categories.get("sbt"), Some(&Category::Tool));
+        assert_eq!(merged.categories.get("java"), Some(&Category::Jdk));
+        assert_eq!(merged.suggested, Metadata::bundled().suggested);
+    }
+
+    #[test]
+    fn finds_deprecated_versions_and_vendors() {
+        let metadata = Metadata::parse(
+            r#"
+            [[deprecated]]
+            candidate = "java"
+            version = "17.0.6-tem"
+            reason = "pulled"
+
+            [[deprecated]]
+            candidate = "java"
+            dist = "open"
+            reason = "no updates"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            metadata.deprecation("java", "17.0.6-tem", "tem").as_deref(),
+            Some("pulled")
+        );
+        assert_eq!(
+            metadata
+                .deprecation("java", "20.0.1-open", "open")
+                .as_deref(),
+            Some("no updates")
+        );
+        assert_eq!(metadata.deprecation("java", "17.0.7-tem", "tem"), None);
+        assert_eq!(metadata.deprecation("gradle", "17.0.6-tem", ""), None);
+    }
+}
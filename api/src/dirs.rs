@@ -0,0 +1,262 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::io::Error;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use lazy_static::lazy_static;
+
+use crate::util;
+
+const DEFAULT_DIR: &str = ".sdkman";
+const XDG_DIR: &str = "sdkman";
+const INIT_SCRIPT: &str = "bin/sdkman-init.sh";
+// where shared installations are usually put by an administrator
+const SYSTEM_ROOTS: [&str; 3] = ["/usr/local/sdkman", "/opt/sdkman", "/usr/share/sdkman"];
+
+lazy_static! {
+    static ref SELECTED: RwLock<Option<PathBuf>> = RwLock::new(None);
+}
+
+// the installation the app manages, instead of the one the shell points to
+pub fn select(root: Option<PathBuf>) {
+    if let Ok(mut current) = SELECTED.write() {
+        *current = root;
+    }
+}
+
+fn selected() -> Option<PathBuf> {
+    SELECTED.read().ok().and_then(|root| root.clone())
+}
+
+// every installation found on this machine, the one the shell points to first
+pub fn installations() -> Vec<SdkmanDirs> {
+    let mut roots: Vec<PathBuf> = env::var_os("SDKMAN_DIR")
+        .map(PathBuf::from)
+        .into_iter()
+        .collect();
+    roots.extend(default_roots());
+    roots.extend(SYSTEM_ROOTS.iter().map(PathBuf::from));
+    roots.extend(selected());
+
+    let mut installations: Vec<SdkmanDirs> = Vec::new();
+    for root in roots.into_iter().filter(|root| is_installation(root)) {
+        // symlinked locations are the same installation
+        let canonical = root.canonicalize().unwrap_or_else(|_| root.clone());
+        let known = installations.iter().any(|dirs| {
+            dirs.root
+                .canonicalize()
+                .unwrap_or_else(|_| dirs.root.clone())
+                == canonical
+        });
+        if !known {
+            installations.push(SdkmanDirs::from_root(root));
+        }
+    }
+    installations
+}
+
+// where the parts of an SDKMAN installation live; the shell exports most of them,
+// but the app is often started from a launcher that never ran sdkman-init.sh
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdkmanDirs {
+    pub root: PathBuf,
+    pub candidates: PathBuf,
+    pub tmp: PathBuf,
+    pub var: PathBuf,
+    // the native helpers of newer SDKMAN versions
+    pub libexec: PathBuf,
+    // older SDKMAN versions kept the downloaded archives here instead of in tmp
+    pub archives: PathBuf,
+}
+
+impl SdkmanDirs {
+    // the selected installation, $SDKMAN_DIR if exported,
+    // otherwise the first installation found in the usual places
+    pub fn resolve() -> io::Result<SdkmanDirs> {
+        SdkmanDirs::locate(
+            selected(),
+            env::var_os("SDKMAN_DIR").map(PathBuf::from),
+            env::var_os("SDKMAN_CANDIDATES_DIR").map(PathBuf::from),
+            default_roots(),
+        )
+    }
+
+    fn locate(
+        selected: Option<PathBuf>,
+        exported: Option<PathBuf>,
+        exported_candidates: Option<PathBuf>,
+        default_roots: Vec<PathBuf>,
+    ) -> io::Result<SdkmanDirs> {
+        let root = match selected.or_else(|| exported.clone()) {
+            Some(root) => root,
+            None => default_roots
+                .into_iter()
+                .find(|root| is_installation(root))
+                .ok_or_else(|| Error::new(ErrorKind::NotFound, "no SDKMAN installation found"))?,
+        };
+        if !root.is_dir() {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                format!("{} doesn't exist", root.display()),
+            ));
+        }
+        // the exported candidates directory only belongs to the exported installation
+        let candidates = exported_candidates.filter(|_| exported.as_ref() == Some(&root));
+        let mut dirs = SdkmanDirs::from_root(root);
+        if let Some(candidates) = candidates {
+            dirs.candidates = candidates;
+        }
+        Ok(dirs)
+    }
+
+    pub fn from_root(root: PathBuf) -> SdkmanDirs {
+        SdkmanDirs {
+            candidates: root.join("candidates"),
+            tmp: root.join("tmp"),
+            var: root.join("var"),
+            libexec: root.join("libexec"),
+            archives: root.join("archives"),
+            root,
+        }
+    }
+
+    // a directory name for whatever is kept per installation, e.g. the cache
+    pub fn key(&self) -> String {
+        util::cache_key(&self.root.to_string_lossy())
+            .trim_matches('_')
+            .to_string()
+    }
+
+    // the native layout ships its commands as binaries in libexec
+    pub fn is_native(&self) -> bool {
+        self.libexec.is_dir()
+    }
+
+    // where downloads go before they're unpacked
+    pub fn download_dir(&self) -> &Path {
+        if !self.is_native() && self.archives.is_dir() {
+            &self.archives
+        } else {
+            &self.tmp
+        }
+    }
+
+    // the platform sdkman-init.sh exports, as SDKMAN stored it on its last selfupdate
+    pub fn platform(&self) -> Option<String> {
+        fs::read_to_string(self.var.join("platform"))
+            .ok()
+            .map(|platform| platform.trim().to_string())
+            .filter(|platform| !platform.is_empty())
+    }
+
+    pub fn version(&self) -> Option<String> {
+        fs::read_to_string(self.var.join("version"))
+            .ok()
+            .map(|version| version.trim().to_string())
+            .filter(|version| !version.is_empty())
+    }
+}
+
+fn default_roots() -> Vec<PathBuf> {
+    default_roots_in(
+        env::var_os("HOME").map(PathBuf::from),
+        env::var_os("XDG_DATA_HOME").map(PathBuf::from),
+    )
+}
+
+fn default_roots_in(home: Option<PathBuf>, data_home: Option<PathBuf>) -> Vec<PathBuf> {
+    let data_home = data_home.or_else(|| home.as_ref().map(|home| home.join(".local/share")));
+    home.map(|home| home.join(DEFAULT_DIR))
+        .into_iter()
+        .chain(data_home.map(|data_home| data_home.join(XDG_DIR)))
+        .collect()
+}
+
+fn is_installation(root: &Path) -> bool {
+    root.join(INIT_SCRIPT).is_file() || root.join("candidates").is_dir()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("sdkman-ui-dirs-{}-{}", name, process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn prefers_the_exported_installation() {
+        let dir = temp_dir("exported");
+        let exported = dir.join("exported");
+        let default = dir.join("home/.sdkman");
+        fs::create_dir_all(&exported).unwrap();
+        fs::create_dir_all(default.join("candidates")).unwrap();
+
+        let dirs = SdkmanDirs::locate(
+            None,
+            Some(exported.clone()),
+            Some(dir.join("elsewhere")),
+            vec![default],
+        );
+        fs::remove_dir_all(&dir).unwrap();
+
+        let dirs = dirs.unwrap();
+        assert_eq!(dirs.root, exported);
+        assert_eq!(dirs.candidates, dir.join("elsewhere"));
+        assert_eq!(dirs.tmp, exported.join("tmp"));
+    }
+
+    #[test]
+    fn falls_back_to_the_first_default_installation() {
+        let dir = temp_dir("default");
+        let home = dir.join("home");
+        fs::create_dir_all(dir.join("data/sdkman/candidates")).unwrap();
+
+        let roots = default_roots_in(Some(home.clone()), Some(dir.join("data")));
+        let dirs = SdkmanDirs::locate(None, None, None, roots.clone());
+        let missing = SdkmanDirs::locate(None, None, None, roots[..1].to_vec());
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            roots,
+            vec![home.join(DEFAULT_DIR), dir.join("data").join(XDG_DIR)]
+        );
+        assert_eq!(dirs.unwrap().candidates, dir.join("data/sdkman/candidates"));
+        assert_eq!(missing.unwrap_err().kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn keeps_the_exported_candidates_to_the_exported_installation() {
+        let dir = temp_dir("selected");
+        let exported = dir.join("exported");
+        let selected = dir.join("selected");
+        fs::create_dir_all(&exported).unwrap();
+        fs::create_dir_all(&selected).unwrap();
+
+        let dirs = SdkmanDirs::locate(
+            Some(selected.clone()),
+            Some(exported),
+            Some(dir.join("elsewhere")),
+            Vec::new(),
+        );
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(dirs.unwrap(), SdkmanDirs::from_root(selected));
+    }
+
+    #[test]
+    fn defaults_to_the_local_share_directory() {
+        let home = PathBuf::from("/home/duke");
+        assert_eq!(
+            default_roots_in(Some(home.clone()), None),
+            vec![home.join(DEFAULT_DIR), home.join(".local/share/sdkman")]
+        );
+    }
+}
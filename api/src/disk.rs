@@ -0,0 +1,46 @@
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+// the bytes left for the current user on the volume the path lives on
+pub fn free_space(path: &Path) -> io::Result<u64> {
+    if cfg!(windows) {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "measuring the free space is only supported on unix",
+        ));
+    }
+    // -P keeps the line of a long device name from wrapping
+    let output = Command::new("df").arg("-Pk").arg(path).output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    parse_df(&String::from_utf8_lossy(&output.stdout)).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "unexpected output of df".to_string(),
+        )
+    })
+}
+
+// the available column of the POSIX format, in kilobytes
+fn parse_df(output: &str) -> Option<u64> {
+    let line = output.lines().nth(1)?;
+    let available: u64 = line.split_whitespace().nth(3)?.parse().ok()?;
+    Some(available * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_available_space() {
+        let output = "Filesystem     1024-blocks      Used Available Capacity Mounted on\n\
+                      /dev/nvme0n1p2   488245288 401923512  61456012      87% /\n";
+        assert_eq!(parse_df(output), Some(61456012 * 1024));
+        assert_eq!(parse_df("Filesystem 1024-blocks Used Available\n"), None);
+    }
+}
@@ -0,0 +1,269 @@
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use crate::lock::EditLock;
+
+// how many timestamped backups of an edited file are kept
+const KEPT_BACKUPS: usize = 5;
+
+// replaces a file of the installation in one step, keeping a timestamped copy of the previous
+// content; another sdkman-ui editing the same file waits for this one
+pub fn edit(path: &Path, content: &[u8]) -> io::Result<Option<PathBuf>> {
+    let _lock = EditLock::acquire(path)?;
+    let backup = backup(path)?;
+    write_atomic(path, content)?;
+    Ok(backup)
+}
+
+// writes next to the file and renames it over the file, so readers like sdkman-init.sh
+// see either the old or the new content, never a half-written one
+pub fn write_atomic(path: &Path, content: &[u8]) -> io::Result<()> {
+    let dir = parent(path)?;
+    fs::create_dir_all(dir)?;
+    let temp = temp_path(path);
+    let written = File::create(&temp).and_then(|mut file| {
+        file.write_all(content)?;
+        file.sync_all()
+    });
+    match written.and_then(|_| fs::rename(&temp, path)) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            let _ = fs::remove_file(&temp);
+            Err(e)
+        }
+    }
+}
+
+// a copy like config.1760520000123.bak, none if there's no file yet; older copies beyond the
+// last few are removed
+pub fn backup(path: &Path) -> io::Result<Option<PathBuf>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let mut millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default();
+    // two edits within the same millisecond keep a backup each
+    let mut backup = path.with_file_name(format!("{}.{}.bak", file_name(path), millis));
+    while backup.exists() {
+        millis += 1;
+        backup = path.with_file_name(format!("{}.{}.bak", file_name(path), millis));
+    }
+    fs::copy(path, &backup)?;
+    prune_backups(path)?;
+    Ok(Some(backup))
+}
+
+// the backups of a file, oldest first by the millisecond in their names
+pub fn backups(path: &Path) -> io::Result<Vec<PathBuf>> {
+    let prefix = format!("{}.", file_name(path));
+    let mut backups: Vec<(u64, PathBuf)> = fs::read_dir(parent(path)?)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter_map(|backup| {
+            let name = backup.file_name()?.to_string_lossy().to_string();
+            let millis = name
+                .strip_prefix(&prefix)?
+                .strip_suffix(".bak")?
+                .parse()
+                .ok()?;
+            Some((millis, backup))
+        })
+        .collect();
+    backups.sort();
+    Ok(backups.into_iter().map(|(_, backup)| backup).collect())
+}
+
+fn prune_backups(path: &Path) -> io::Result<()> {
+    let backups = backups(path)?;
+    let surplus = backups.len().saturating_sub(KEPT_BACKUPS);
+    for backup in &backups[..surplus] {
+        fs::remove_file(backup)?;
+    }
+    Ok(())
+}
+
+// moves a directory of the installation, e.g. a version out of the way and back; within a file
+// system that's a rename in one step, across file systems a copy that only replaces the source
+// once it's complete
+pub fn rename_dir(from: &Path, to: &Path) -> io::Result<()> {
+    match fs::rename(from, to) {
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+            let temp = temp_path(to);
+            let copied = copy_dir(from, &temp).and_then(|_| fs::rename(&temp, to));
+            if let Err(e) = copied {
+                let _ = fs::remove_dir_all(&temp);
+                return Err(e);
+            }
+            fs::remove_dir_all(from)
+        }
+        result => result,
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else if file_type.is_symlink() {
+            symlink(&fs::read_link(entry.path())?, &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+// points a link somewhere else in one step, so `current` never goes missing in between
+pub fn replace_symlink(target: &Path, link: &Path) -> io::Result<()> {
+    let temp = temp_path(link);
+    let _ = fs::remove_file(&temp);
+    symlink(target, &temp)?;
+    match fs::rename(&temp, link) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            let _ = fs::remove_file(&temp);
+            Err(e)
+        }
+    }
+}
+
+// makes a file another name of the target, e.g. for identical archives, in one step as well
+pub fn replace_with_hard_link(target: &Path, path: &Path) -> io::Result<()> {
+    let temp = temp_path(path);
+    let _ = fs::remove_file(&temp);
+    fs::hard_link(target, &temp)?;
+    match fs::rename(&temp, path) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            let _ = fs::remove_file(&temp);
+            Err(e)
+        }
+    }
+}
+
+#[cfg(unix)]
+pub fn symlink(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(not(unix))]
+pub fn symlink(_target: &Path, _link: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "current versions are symbolic links, which are only supported on unix",
+    ))
+}
+
+// hidden and unique per process, in the same directory so the rename stays on one file system
+fn temp_path(path: &Path) -> PathBuf {
+    path.with_file_name(format!(".{}.{}.tmp", file_name(path), process::id()))
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn parent(path: &Path) -> io::Result<&Path> {
+    path.parent().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} has no parent directory", path.display()),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("sdkman-ui-fsutil-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn writes_atomically_and_keeps_a_backup() {
+        let dir = scratch_dir("edit");
+        let config = dir.join("config");
+        write_atomic(&config, b"sdkman_auto_env=false\n").unwrap();
+        let backup = backup(&config).unwrap().unwrap();
+        write_atomic(&config, b"sdkman_auto_env=true\n").unwrap();
+        assert_eq!(
+            fs::read_to_string(&config).unwrap(),
+            "sdkman_auto_env=true\n"
+        );
+        assert_eq!(
+            fs::read_to_string(&backup).unwrap(),
+            "sdkman_auto_env=false\n"
+        );
+        assert_eq!(backups(&config).unwrap(), vec![backup]);
+        // no temporary files are left behind
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn keeps_a_backup_of_every_edit() {
+        let dir = scratch_dir("backups");
+        let config = dir.join("config");
+        write_atomic(&config, b"first\n").unwrap();
+        let first = backup(&config).unwrap().unwrap();
+        write_atomic(&config, b"second\n").unwrap();
+        let second = backup(&config).unwrap().unwrap();
+        assert_ne!(first, second);
+        assert_eq!(fs::read_to_string(&first).unwrap(), "first\n");
+        assert_eq!(fs::read_to_string(&second).unwrap(), "second\n");
+        assert_eq!(backups(&config).unwrap(), vec![first, second]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copies_a_directory_with_its_links() {
+        let dir = scratch_dir("copy");
+        fs::create_dir_all(dir.join("21/bin")).unwrap();
+        fs::write(dir.join("21/bin/java"), "#!/bin/sh\n").unwrap();
+        symlink(Path::new("bin/java"), &dir.join("21/java")).unwrap();
+        copy_dir(&dir.join("21"), &dir.join("copy")).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join("copy/bin/java")).unwrap(),
+            "#!/bin/sh\n"
+        );
+        assert_eq!(
+            fs::read_link(dir.join("copy/java")).unwrap(),
+            Path::new("bin/java")
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn replaces_a_symlink() {
+        let dir = scratch_dir("symlink");
+        fs::create_dir_all(dir.join("17")).unwrap();
+        fs::create_dir_all(dir.join("21")).unwrap();
+        let link = dir.join("current");
+        replace_symlink(&dir.join("17"), &link).unwrap();
+        replace_symlink(&dir.join("21"), &link).unwrap();
+        assert_eq!(fs::read_link(&link).unwrap(), dir.join("21"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
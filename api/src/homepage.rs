@@ -0,0 +1,130 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+#[cfg(feature = "native")]
+use crate::cache;
+#[cfg(feature = "native")]
+use crate::cache::Namespace;
+#[cfg(feature = "native")]
+use crate::remote::SdkmanApiError;
+#[cfg(feature = "native")]
+use crate::util;
+
+// enough to skim what a project is about
+const MAX_CHARS: usize = 4000;
+
+lazy_static! {
+    // the regex crate has no backreferences, so every tag is spelled out
+    static ref INVISIBLE: Regex = Regex::new(
+        r"(?is)<script\b.*?</script\s*>|<style\b.*?</style\s*>|<noscript\b.*?</noscript\s*>|<svg\b.*?</svg\s*>|<template\b.*?</template\s*>|<head\b.*?</head\s*>|<nav\b.*?</nav\s*>|<footer\b.*?</footer\s*>"
+    )
+    .unwrap();
+    static ref COMMENT: Regex = Regex::new(r"(?s)<!--.*?-->").unwrap();
+    static ref TITLE: Regex = Regex::new(r"(?is)<title[^>]*>(.*?)</title\s*>").unwrap();
+    static ref DESCRIPTION: Regex = Regex::new(
+        r#"(?is)<meta\s+[^>]*name\s*=\s*["']description["'][^>]*content\s*=\s*["']([^"']*)["']"#
+    )
+    .unwrap();
+    static ref HEADING: Regex = Regex::new(r"(?i)<h[1-6][^>]*>").unwrap();
+    static ref BREAK: Regex =
+        Regex::new(r"(?i)</?(p|div|br|li|ul|ol|h[1-6]|tr|table|section|article|pre)\b[^>]*>")
+            .unwrap();
+    static ref TAG: Regex = Regex::new(r"(?s)<[^>]*>").unwrap();
+    static ref SPACES: Regex = Regex::new(r"[ \t\r\f]+").unwrap();
+    static ref BLANK_LINES: Regex = Regex::new(r"\n\s*\n\s*(\n\s*)+").unwrap();
+}
+
+// the text of a homepage, headings marked like in markdown
+pub fn html_to_text(html: &str) -> String {
+    let title = TITLE
+        .captures(html)
+        .map(|captures| decode_entities(captures[1].trim()));
+    let description = DESCRIPTION
+        .captures(html)
+        .map(|captures| decode_entities(captures[1].trim()));
+
+    let body = COMMENT.replace_all(html, "");
+    let body = INVISIBLE.replace_all(&body, "");
+    let body = HEADING.replace_all(&body, "\n\n## ");
+    let body = BREAK.replace_all(&body, "\n");
+    let body = TAG.replace_all(&body, "");
+    let body = decode_entities(&body);
+    let body = SPACES.replace_all(&body, " ");
+    let body: String = body
+        .lines()
+        .map(str::trim)
+        .collect::<Vec<&str>>()
+        .join("\n");
+    let body = BLANK_LINES.replace_all(&body, "\n\n");
+
+    let mut text = String::new();
+    for part in [title, description]
+        .into_iter()
+        .flatten()
+        .filter(|part| !part.is_empty())
+    {
+        text.push_str(&part);
+        text.push_str("\n\n");
+    }
+    text.push_str(body.trim());
+    truncate(text)
+}
+
+fn truncate(text: String) -> String {
+    match text.char_indices().nth(MAX_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text,
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&mdash;", "—")
+        .replace("&ndash;", "–")
+        .replace("&amp;", "&")
+}
+
+// the homepage as text, cached like the catalog
+#[cfg(feature = "native")]
+pub fn fetch_summary(url: &str) -> Result<String, SdkmanApiError> {
+    let key = util::cache_key(url);
+    if let Some(cached) = cache::get(Namespace::Homepages, &key) {
+        return Ok(cached);
+    }
+    let html = util::fetch_text(url)?;
+    let text = html_to_text(&html);
+    cache::put(Namespace::Homepages, &key, &text);
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_text_and_drops_the_markup() {
+        let html = r#"<html><head><title>Tool &amp; Co</title>
+            <meta name="description" content="Builds things.">
+            <script>var x = "<p>no</p>";</script></head>
+            <body><nav>Menu</nav><h1>Getting started</h1><p>Install it with
+            <code>sdk install tool</code>.</p><!-- hidden --><ul><li>fast</li><li>small</li></ul>
+            <footer>© 2023</footer></body></html>"#;
+        assert_eq!(
+            html_to_text(html),
+            "Tool & Co\n\nBuilds things.\n\n## Getting started\n\nInstall it with\nsdk install tool.\n\nfast\n\nsmall"
+        );
+    }
+
+    #[test]
+    fn truncates_long_pages() {
+        let html = format!("<p>{}</p>", "ä".repeat(MAX_CHARS + 10));
+        let text = html_to_text(&html);
+        assert_eq!(text.chars().count(), MAX_CHARS + 1);
+        assert!(text.ends_with('…'));
+    }
+}
@@ -0,0 +1,466 @@
+use std::env;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::ExitStatus;
+use std::process::Stdio;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+pub mod quirks;
+
+use crate::dirs::SdkmanDirs;
+use crate::download;
+use crate::fsutil;
+use crate::local::home_path;
+use crate::local::version_path;
+use crate::lock::CandidateLock;
+use crate::remote::download_url;
+use crate::remote::SdkmanApiError;
+use crate::security;
+use crate::store::store;
+
+const STASH_DIR: &str = "stash";
+const VERIFY_TIMEOUT: Duration = Duration::from_secs(30);
+const RUN_TIMEOUT: Duration = Duration::from_secs(60);
+// the environment a verification run gets to see
+const VERIFY_ENV: [&str; 5] = ["PATH", "HOME", "JAVA_HOME", "LANG", "TMPDIR"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Progress {
+    // waiting for a free download slot
+    Waiting,
+    // bytes downloaded so far and in total, if known
+    Downloading(u64, Option<u64>),
+    Extracting,
+}
+
+pub fn install_version(
+    binary_name: &str,
+    version: &str,
+    on_progress: &mut dyn FnMut(Progress),
+) -> Result<(), SdkmanApiError> {
+    ensure_not_installed(binary_name, version)?;
+    let archive = download_archive(binary_name, version, on_progress)?;
+    unpack(binary_name, version, &archive, on_progress)?;
+    fs::remove_file(archive)?;
+    Ok(())
+}
+
+// installs an archive the user downloaded, under a version identifier of their choice
+pub fn install_archive(
+    binary_name: &str,
+    version: &str,
+    archive: &Path,
+    on_progress: &mut dyn FnMut(Progress),
+) -> Result<(), SdkmanApiError> {
+    ensure_not_installed(binary_name, version)?;
+    unpack(binary_name, version, archive, on_progress)
+}
+
+fn ensure_not_installed(binary_name: &str, version: &str) -> Result<(), SdkmanApiError> {
+    if store().is_installed(binary_name, version) {
+        return Err(SdkmanApiError::BadRequest(
+            "the version is already installed",
+        ));
+    }
+    Ok(())
+}
+
+fn unpack(
+    binary_name: &str,
+    version: &str,
+    archive: &Path,
+    on_progress: &mut dyn FnMut(Progress),
+) -> Result<(), SdkmanApiError> {
+    on_progress(Progress::Extracting);
+    let staging = tmp_dir()?.join(format!("{}-{}", binary_name, version));
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+    fs::create_dir_all(&staging)?;
+    extract(archive, &staging)?;
+    let mut content = staging.clone();
+    for quirk in quirks::for_candidate(binary_name) {
+        content = quirk.apply(content)?;
+    }
+    store().add_version(binary_name, version, &content)?;
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+    Ok(())
+}
+
+// the SHA-256 of a file, from whichever of the usual tools is around
+pub fn checksum(path: &Path) -> Result<String, SdkmanApiError> {
+    let output = match Command::new("sha256sum").arg(path).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Command::new("shasum")
+            .arg("-a")
+            .arg("256")
+            .arg(path)
+            .output()?,
+        Err(e) => return Err(e.into()),
+    };
+    if !output.status.success() {
+        return Err(SdkmanApiError::BadRequest("computing the checksum failed"));
+    }
+    String::from_utf8(output.stdout)?
+        .split_whitespace()
+        .next()
+        .map(|sum| sum.to_string())
+        .ok_or(SdkmanApiError::BadRequest("computing the checksum failed"))
+}
+
+// the launcher of a candidate and the argument that makes it print its version
+pub fn launcher(binary_name: &str) -> (&str, &str) {
+    match binary_name {
+        "java" => ("java", "-version"),
+        "ant" => ("ant", "-version"),
+        "kotlin" => ("kotlin", "-version"),
+        "scala" => ("scala", "-version"),
+        "maven" => ("mvn", "--version"),
+        "micronaut" => ("mn", "--version"),
+        "springboot" => ("spring", "--version"),
+        other => (other, "--version"),
+    }
+}
+
+// runs the launcher of an installed version and returns what it printed; a broken or
+// wrong-architecture download fails here instead of in the user's shell
+pub fn verify_version(binary_name: &str, version: &str) -> Result<String, SdkmanApiError> {
+    let (name, arg) = launcher(binary_name);
+    let launcher = home_path(binary_name, version)?.join("bin").join(name);
+    if !launcher.is_file() {
+        return Ok(format!(
+            "No launcher at {}, skipped the check",
+            launcher.display()
+        ));
+    }
+    let mut command = Command::new(&launcher);
+    command.arg(arg).env_clear();
+    for key in VERIFY_ENV {
+        if let Ok(value) = env::var(key) {
+            command.env(key, value);
+        }
+    }
+    let log_name = format!("verify-{}-{}.log", binary_name, version);
+    let output = run_captured(command, &log_name, VERIFY_TIMEOUT)?;
+    match output.status {
+        Some(status) if status.success() => Ok(output.printed.trim().to_string()),
+        Some(status) => Err(SdkmanApiError::VerificationFailed(format!(
+            "{} {} exited with {}: {}",
+            name,
+            arg,
+            status,
+            output.printed.trim()
+        ))),
+        None => Err(SdkmanApiError::VerificationFailed(format!(
+            "{} {} did not finish within {} seconds",
+            name,
+            arg,
+            VERIFY_TIMEOUT.as_secs()
+        ))),
+    }
+}
+
+// what a run printed and how it exited, no status if it was killed after the timeout
+pub struct RunOutput {
+    pub status: Option<ExitStatus>,
+    pub printed: String,
+}
+
+// runs an executable of an installed version with that version first on the PATH
+pub fn run_version(
+    binary_name: &str,
+    version: &str,
+    executable: &str,
+    args: &[String],
+) -> Result<RunOutput, SdkmanApiError> {
+    let home = home_path(binary_name, version)?;
+    let bin = home.join("bin");
+    let program = bin.join(executable);
+    if executable.contains(std::path::is_separator) || !program.is_file() {
+        return Err(SdkmanApiError::BadRequest(
+            "the executable is not part of the version",
+        ));
+    }
+    let mut paths = vec![bin];
+    if let Some(path) = env::var_os("PATH") {
+        paths.extend(env::split_paths(&path));
+    }
+    let path = env::join_paths(paths)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+    let mut command = Command::new(&program);
+    command
+        .args(args)
+        .env("PATH", path)
+        .env(format!("{}_HOME", binary_name.to_uppercase()), &home);
+    let log_name = format!("run-{}-{}.log", binary_name, version);
+    run_captured(command, &log_name, RUN_TIMEOUT)
+}
+
+fn run_captured(
+    mut command: Command,
+    log_name: &str,
+    timeout: Duration,
+) -> Result<RunOutput, SdkmanApiError> {
+    let dir = tmp_dir()?;
+    fs::create_dir_all(&dir)?;
+    let output_path = dir.join(log_name);
+    // a file instead of pipes, so a chatty process can't block while we wait for it
+    let output = File::create(&output_path)?;
+    command
+        .current_dir(&dir)
+        .stdin(Stdio::null())
+        .stdout(output.try_clone()?)
+        .stderr(output);
+    let mut child = command.spawn()?;
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if started.elapsed() > timeout {
+            child.kill()?;
+            child.wait()?;
+            break None;
+        }
+        thread::sleep(Duration::from_millis(100));
+    };
+    let printed = fs::read_to_string(&output_path).unwrap_or_default();
+    fs::remove_file(&output_path)?;
+    Ok(RunOutput { status, printed })
+}
+
+pub fn uninstall_version(binary_name: &str, version: &str) -> Result<(), SdkmanApiError> {
+    let store = store();
+    if !store.is_installed(binary_name, version) {
+        return Err(SdkmanApiError::BadRequest("the version is not installed"));
+    }
+    // a dangling current link would break the candidate in every shell
+    if store.current_version(binary_name)?.as_deref() == Some(version) {
+        store.clear_current_version(binary_name)?;
+    }
+    store.remove_version(binary_name, version)?;
+    store.remove_candidate(binary_name)?;
+    Ok(())
+}
+
+pub fn set_current_version(binary_name: &str, version: &str) -> Result<(), SdkmanApiError> {
+    let store = store();
+    if !store.is_installed(binary_name, version) {
+        return Err(SdkmanApiError::BadRequest("the version is not installed"));
+    }
+    store.set_current_version(binary_name, version)?;
+    Ok(())
+}
+
+pub fn current_version(binary_name: &str) -> Result<Option<String>, SdkmanApiError> {
+    Ok(store().current_version(binary_name)?)
+}
+
+pub fn clear_current_version(binary_name: &str) -> Result<(), SdkmanApiError> {
+    store().clear_current_version(binary_name)?;
+    Ok(())
+}
+
+// moves the version out of the way instead of deleting it, so it can still be restored
+pub fn stash_version(binary_name: &str, version: &str) -> Result<PathBuf, SdkmanApiError> {
+    let target = version_path(binary_name, version)?;
+    if !target.is_dir() {
+        return Err(SdkmanApiError::BadRequest("the version is not installed"));
+    }
+    if current_version(binary_name)?.as_deref() == Some(version) {
+        clear_current_version(binary_name)?;
+    }
+    let stash = stash_path(binary_name, version)?;
+    let _lock = CandidateLock::acquire(binary_name)?;
+    if stash.exists() {
+        fs::remove_dir_all(&stash)?;
+    }
+    if let Some(parent) = stash.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fsutil::rename_dir(&target, &stash)?;
+    Ok(stash)
+}
+
+// gives an installed version the name it should have had, keeping it current if it was
+pub fn rename_version(binary_name: &str, version: &str, to: &str) -> Result<(), SdkmanApiError> {
+    let source = version_path(binary_name, version)?;
+    let target = version_path(binary_name, to)?;
+    if !source.is_dir() {
+        return Err(SdkmanApiError::BadRequest("the version is not installed"));
+    }
+    // on a case-insensitive file system a name differing only by case is the same directory
+    if target.exists() && !version.eq_ignore_ascii_case(to) {
+        return Err(SdkmanApiError::BadRequest(
+            "a version of that name is installed already",
+        ));
+    }
+    let current = current_version(binary_name)?.as_deref() == Some(version);
+    {
+        let _lock = CandidateLock::acquire(binary_name)?;
+        fsutil::rename_dir(&source, &target)?;
+    }
+    if current {
+        set_current_version(binary_name, to)?;
+    }
+    Ok(())
+}
+
+// the candidate directory and a current link pointing nowhere, once the last version is gone
+pub fn remove_empty_candidate(binary_name: &str) -> Result<bool, SdkmanApiError> {
+    Ok(store().remove_candidate(binary_name)?)
+}
+
+pub fn stash_path(binary_name: &str, version: &str) -> Result<PathBuf, SdkmanApiError> {
+    Ok(tmp_dir()?
+        .join(STASH_DIR)
+        .join(format!("{}-{}", binary_name, version)))
+}
+
+pub fn restore_version(
+    binary_name: &str,
+    version: &str,
+    stash: &Path,
+) -> Result<(), SdkmanApiError> {
+    let _lock = CandidateLock::acquire(binary_name)?;
+    let target = version_path(binary_name, version)?;
+    // the candidate directory is gone if its other versions have been rolled back already
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fsutil::rename_dir(stash, &target)?;
+    Ok(())
+}
+
+pub fn drop_stash(stash: &Path) -> Result<(), SdkmanApiError> {
+    fs::remove_dir_all(stash)?;
+    Ok(())
+}
+
+fn tmp_dir() -> Result<PathBuf, SdkmanApiError> {
+    Ok(SdkmanDirs::resolve()?.tmp)
+}
+
+fn download_archive(
+    binary_name: &str,
+    version: &str,
+    on_progress: &mut dyn FnMut(Progress),
+) -> Result<PathBuf, SdkmanApiError> {
+    let dir = SdkmanDirs::resolve()?.download_dir().to_path_buf();
+    fs::create_dir_all(&dir)?;
+    let archive = dir.join(format!("{}-{}.bin", binary_name, version));
+    let source = download::download_with_mirrors(
+        binary_name,
+        &download_url(binary_name, version)?,
+        &archive,
+        on_progress,
+    )?;
+    // a tampered archive isn't kept around to be unpacked later
+    if let Err(e) = security::verify(binary_name, &archive, &source) {
+        fs::remove_file(&archive)?;
+        return Err(e);
+    }
+    Ok(archive)
+}
+
+// the broker serves zip and tar.gz archives, told apart by their magic bytes
+fn extract(archive: &Path, dest: &Path) -> Result<(), SdkmanApiError> {
+    let mut magic = [0u8; 2];
+    File::open(archive)?.read_exact(&mut magic)?;
+    let mut command = match magic {
+        [0x50, 0x4b] => {
+            let mut command = Command::new("unzip");
+            command.arg("-qo").arg(archive).arg("-d").arg(dest);
+            command
+        }
+        [0x1f, 0x8b] => {
+            let mut command = Command::new("tar");
+            command.arg("-xzf").arg(archive).arg("-C").arg(dest);
+            command
+        }
+        _ => return Err(SdkmanApiError::BadRequest("unsupported archive format")),
+    };
+    let status = command.status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(SdkmanApiError::ExtractionFailed(status.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local::retrieve_local_candidate;
+    use crate::local::retrieve_local_candidates;
+    use crate::store::memory_store;
+
+    #[test]
+    fn sets_the_current_version() {
+        memory_store();
+        set_current_version("gradle", "8.0.2").unwrap();
+        assert_eq!(current_version("gradle").unwrap().as_deref(), Some("8.0.2"));
+        set_current_version("gradle", "7.6").unwrap();
+        assert_eq!(current_version("gradle").unwrap().as_deref(), Some("7.6"));
+        let gradle = retrieve_local_candidates()
+            .unwrap()
+            .into_iter()
+            .find(|local| local.binary_name() == "gradle")
+            .unwrap();
+        assert_eq!(gradle.current_version().map(String::as_str), Some("7.6"));
+    }
+
+    #[test]
+    fn refuses_to_use_missing_versions() {
+        memory_store();
+        assert!(set_current_version("maven", "4.0.0").is_err());
+        assert_eq!(current_version("maven").unwrap(), None);
+    }
+
+    #[test]
+    fn deleting_the_current_version_clears_it() {
+        memory_store();
+        set_current_version("kotlin", "1.8.10").unwrap();
+        uninstall_version("kotlin", "1.8.10").unwrap();
+        assert_eq!(current_version("kotlin").unwrap(), None);
+        assert!(uninstall_version("kotlin", "1.8.10").is_err());
+        assert!(ensure_not_installed("kotlin", "1.8.10").is_ok());
+    }
+
+    #[test]
+    fn deleting_the_last_version_removes_the_candidate() {
+        memory_store();
+        set_current_version("sbt", "1.9.0").unwrap();
+        uninstall_version("sbt", "1.9.0").unwrap();
+        assert!(!remove_empty_candidate("sbt").unwrap());
+        let has_sbt = || {
+            retrieve_local_candidates()
+                .unwrap()
+                .iter()
+                .any(|candidate| candidate.binary_name() == "sbt")
+        };
+        assert!(has_sbt());
+        uninstall_version("sbt", "1.8.2").unwrap();
+        assert!(!has_sbt());
+    }
+
+    #[test]
+    fn rescans_a_single_candidate() {
+        memory_store();
+        set_current_version("scala", "3.3.1").unwrap();
+        let scala = retrieve_local_candidate("scala").unwrap().unwrap();
+        assert_eq!(scala.current_version().map(String::as_str), Some("3.3.1"));
+        uninstall_version("scala", "3.3.1").unwrap();
+        assert!(retrieve_local_candidate("scala").unwrap().is_none());
+        assert!(retrieve_local_candidate("unknown").unwrap().is_none());
+    }
+}
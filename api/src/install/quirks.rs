@@ -0,0 +1,142 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use crate::remote::SdkmanApiError;
+
+static CLEAR_QUARANTINE: AtomicBool = AtomicBool::new(true);
+
+// an opt-out, for machines where Gatekeeper is meant to check every tool
+pub fn set_clear_quarantine(clear: bool) {
+    CLEAR_QUARANTINE.store(clear, Ordering::SeqCst);
+}
+
+// a step some candidates need between extracting the archive and moving it into place;
+// gets the directory holding the extracted content and returns where the content is now
+pub trait Quirk {
+    fn name(&self) -> &'static str;
+    fn apply(&self, dir: PathBuf) -> Result<PathBuf, SdkmanApiError>;
+}
+
+// the quirks of a candidate, applied in order; new quirks only need to be registered here
+pub fn for_candidate(binary_name: &str) -> Vec<Box<dyn Quirk>> {
+    let mut quirks: Vec<Box<dyn Quirk>> = vec![Box::new(FlattenSingleDir)];
+    match binary_name {
+        "java" => quirks.push(Box::new(MacosContentsHome)),
+        // zip archives built on other platforms may lose the executable bits of the launchers
+        "gradle" | "grails" | "groovy" | "kotlin" | "maven" | "micronaut" | "springboot" => {
+            quirks.push(Box::new(MakeExecutable("bin")))
+        }
+        _ => {}
+    }
+    if cfg!(target_os = "macos") && CLEAR_QUARANTINE.load(Ordering::SeqCst) {
+        quirks.push(Box::new(ClearQuarantine));
+    }
+    quirks
+}
+
+// most archives wrap everything in a single top level directory
+struct FlattenSingleDir;
+
+impl Quirk for FlattenSingleDir {
+    fn name(&self) -> &'static str {
+        "flatten single directory"
+    }
+
+    fn apply(&self, dir: PathBuf) -> Result<PathBuf, SdkmanApiError> {
+        let entries: Vec<PathBuf> = fs::read_dir(&dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<_>>()?;
+        match entries.as_slice() {
+            [entry] if entry.is_dir() => Ok(entry.clone()),
+            _ => Ok(dir),
+        }
+    }
+}
+
+// macOS JDK bundles keep the actual home in Contents/Home; sdkman installs only that part,
+// so JAVA_HOME can point at the version directory just like on every other platform
+struct MacosContentsHome;
+
+impl Quirk for MacosContentsHome {
+    fn name(&self) -> &'static str {
+        "macOS Contents/Home"
+    }
+
+    fn apply(&self, dir: PathBuf) -> Result<PathBuf, SdkmanApiError> {
+        let home = dir.join("Contents").join("Home");
+        if home.is_dir() {
+            Ok(home)
+        } else {
+            Ok(dir)
+        }
+    }
+}
+
+// makes every file in the subdirectory executable
+struct MakeExecutable(&'static str);
+
+impl Quirk for MakeExecutable {
+    fn name(&self) -> &'static str {
+        "make executable"
+    }
+
+    fn apply(&self, dir: PathBuf) -> Result<PathBuf, SdkmanApiError> {
+        let bin = dir.join(self.0);
+        if bin.is_dir() {
+            for entry in fs::read_dir(&bin)? {
+                let path = entry?.path();
+                if path.is_file() {
+                    set_executable(&path)?;
+                }
+            }
+        }
+        Ok(dir)
+    }
+}
+
+// downloaded archives carry the quarantine attribute into every extracted file, so Gatekeeper
+// would ask about each launcher on its first run
+struct ClearQuarantine;
+
+impl Quirk for ClearQuarantine {
+    fn name(&self) -> &'static str {
+        "clear quarantine"
+    }
+
+    fn apply(&self, dir: PathBuf) -> Result<PathBuf, SdkmanApiError> {
+        let status = Command::new("xattr")
+            .arg("-dr")
+            .arg("com.apple.quarantine")
+            .arg(&dir)
+            .status();
+        // the tool still works, only with a prompt, so this doesn't fail the installation
+        match status {
+            Ok(status) if status.success() => {}
+            Ok(status) => tracing::warn!(
+                "Clearing the quarantine of {} failed: {}",
+                dir.display(),
+                status
+            ),
+            Err(e) => tracing::warn!("Clearing the quarantine of {} failed: {}", dir.display(), e),
+        }
+        Ok(dir)
+    }
+}
+
+#[cfg(unix)]
+fn set_executable(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut permissions = fs::metadata(path)?.permissions();
+    permissions.set_mode(permissions.mode() | 0o755);
+    fs::set_permissions(path, permissions)
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
@@ -1,3 +1,31 @@
+pub mod alias;
+pub mod arch;
+#[cfg(feature = "native")]
+pub mod archives;
+pub mod cache;
+pub mod category;
+pub mod dirs;
+#[cfg(feature = "native")]
+pub mod disk;
+#[cfg(feature = "native")]
+pub mod download;
+pub mod fsutil;
+pub mod homepage;
+#[cfg(feature = "native")]
+pub mod install;
+pub mod links;
 pub mod local;
+pub mod lock;
+pub mod manifest;
+pub mod mock;
+pub mod names;
+pub mod release;
 pub mod remote;
+#[cfg(feature = "native")]
+pub mod sdkman;
+pub mod sdkman_config;
+#[cfg(feature = "native")]
+pub mod security;
+pub mod store;
+pub mod trace;
 mod util;
@@ -0,0 +1,232 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkKind {
+    Documentation,
+    Source,
+    Changelog,
+}
+
+impl LinkKind {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            LinkKind::Documentation => "📚",
+            // the GitHub logo of the Nerd Font the app ships with
+            LinkKind::Source => "\u{f09b}",
+            LinkKind::Changelog => "📝",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LinkKind::Documentation => "Documentation",
+            LinkKind::Source => "Source code",
+            LinkKind::Changelog => "Changelog",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuickLink {
+    pub kind: LinkKind,
+    pub url: &'static str,
+}
+
+// where the most used candidates keep their docs, code and release notes;
+// candidates that aren't listed only have their homepage
+pub fn of(binary_name: &str) -> &'static [QuickLink] {
+    use LinkKind::*;
+    match binary_name {
+        "java" => &[
+            QuickLink {
+                kind: Documentation,
+                url: "https://docs.oracle.com/en/java/javase/",
+            },
+            QuickLink {
+                kind: Source,
+                url: "https://github.com/openjdk/jdk",
+            },
+            QuickLink {
+                kind: Changelog,
+                url: "https://jdk.java.net/",
+            },
+        ],
+        "gradle" => &[
+            QuickLink {
+                kind: Documentation,
+                url: "https://docs.gradle.org/current/userguide/userguide.html",
+            },
+            QuickLink {
+                kind: Source,
+                url: "https://github.com/gradle/gradle",
+            },
+            QuickLink {
+                kind: Changelog,
+                url: "https://gradle.org/releases/",
+            },
+        ],
+        "maven" => &[
+            QuickLink {
+                kind: Documentation,
+                url: "https://maven.apache.org/guides/",
+            },
+            QuickLink {
+                kind: Source,
+                url: "https://github.com/apache/maven",
+            },
+            QuickLink {
+                kind: Changelog,
+                url: "https://maven.apache.org/docs/history.html",
+            },
+        ],
+        "kotlin" => &[
+            QuickLink {
+                kind: Documentation,
+                url: "https://kotlinlang.org/docs/home.html",
+            },
+            QuickLink {
+                kind: Source,
+                url: "https://github.com/JetBrains/kotlin",
+            },
+            QuickLink {
+                kind: Changelog,
+                url: "https://github.com/JetBrains/kotlin/releases",
+            },
+        ],
+        "scala" => &[
+            QuickLink {
+                kind: Documentation,
+                url: "https://docs.scala-lang.org/",
+            },
+            QuickLink {
+                kind: Source,
+                url: "https://github.com/scala/scala3",
+            },
+            QuickLink {
+                kind: Changelog,
+                url: "https://github.com/scala/scala3/releases",
+            },
+        ],
+        "groovy" => &[
+            QuickLink {
+                kind: Documentation,
+                url: "https://groovy-lang.org/documentation.html",
+            },
+            QuickLink {
+                kind: Source,
+                url: "https://github.com/apache/groovy",
+            },
+            QuickLink {
+                kind: Changelog,
+                url: "https://groovy-lang.org/changelogs.html",
+            },
+        ],
+        "sbt" => &[
+            QuickLink {
+                kind: Documentation,
+                url: "https://www.scala-sbt.org/1.x/docs/",
+            },
+            QuickLink {
+                kind: Source,
+                url: "https://github.com/sbt/sbt",
+            },
+            QuickLink {
+                kind: Changelog,
+                url: "https://github.com/sbt/sbt/releases",
+            },
+        ],
+        "springboot" => &[
+            QuickLink {
+                kind: Documentation,
+                url: "https://docs.spring.io/spring-boot/",
+            },
+            QuickLink {
+                kind: Source,
+                url: "https://github.com/spring-projects/spring-boot",
+            },
+            QuickLink {
+                kind: Changelog,
+                url: "https://github.com/spring-projects/spring-boot/releases",
+            },
+        ],
+        "micronaut" => &[
+            QuickLink {
+                kind: Documentation,
+                url: "https://docs.micronaut.io/latest/guide/",
+            },
+            QuickLink {
+                kind: Source,
+                url: "https://github.com/micronaut-projects/micronaut-core",
+            },
+            QuickLink {
+                kind: Changelog,
+                url: "https://github.com/micronaut-projects/micronaut-core/releases",
+            },
+        ],
+        "quarkus" => &[
+            QuickLink {
+                kind: Documentation,
+                url: "https://quarkus.io/guides/",
+            },
+            QuickLink {
+                kind: Source,
+                url: "https://github.com/quarkusio/quarkus",
+            },
+            QuickLink {
+                kind: Changelog,
+                url: "https://github.com/quarkusio/quarkus/releases",
+            },
+        ],
+        "visualvm" => &[
+            QuickLink {
+                kind: Documentation,
+                url: "https://visualvm.github.io/documentation.html",
+            },
+            QuickLink {
+                kind: Source,
+                url: "https://github.com/oracle/visualvm",
+            },
+            QuickLink {
+                kind: Changelog,
+                url: "https://visualvm.github.io/relnotes.html",
+            },
+        ],
+        "jbang" => &[
+            QuickLink {
+                kind: Documentation,
+                url: "https://www.jbang.dev/documentation/guide/latest/",
+            },
+            QuickLink {
+                kind: Source,
+                url: "https://github.com/jbangdev/jbang",
+            },
+            QuickLink {
+                kind: Changelog,
+                url: "https://github.com/jbangdev/jbang/releases",
+            },
+        ],
+        "mvnd" => &[
+            QuickLink {
+                kind: Source,
+                url: "https://github.com/apache/maven-mvnd",
+            },
+            QuickLink {
+                kind: Changelog,
+                url: "https://github.com/apache/maven-mvnd/releases",
+            },
+        ],
+        "tomcat" => &[
+            QuickLink {
+                kind: Documentation,
+                url: "https://tomcat.apache.org/tomcat-10.1-doc/",
+            },
+            QuickLink {
+                kind: Source,
+                url: "https://github.com/apache/tomcat",
+            },
+            QuickLink {
+                kind: Changelog,
+                url: "https://tomcat.apache.org/tomcat-10.1-doc/changelog.html",
+            },
+        ],
+        _ => &[],
+    }
+}
@@ -1,8 +1,25 @@
 use std::collections::HashMap;
-use std::env;
 use std::fs;
 use std::io::Error;
 use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::sync::RwLock;
+use std::thread;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use lazy_static::lazy_static;
+
+use crate::dirs::SdkmanDirs;
+use crate::store::store;
+
+const DEFAULT_SCAN_TIMEOUT: Duration = Duration::from_secs(30);
+
+lazy_static! {
+    static ref SCAN_TIMEOUT: RwLock<Duration> = RwLock::new(DEFAULT_SCAN_TIMEOUT);
+}
 
 #[derive(Debug, Clone)]
 pub struct LocalCandidate {
@@ -23,52 +40,262 @@ impl LocalCandidate {
     pub fn versions(&self) -> &HashMap<String, bool> {
         &self.versions
     }
+    pub fn current_version(&self) -> Option<&String> {
+        self.versions
+            .iter()
+            .find(|(_, current)| **current)
+            .map(|(version, _)| version)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalCandidateUsage {
+    binary_name: String,
+    disk_usage: u64,
+    last_used: Option<SystemTime>,
+    versions: Vec<LocalVersionUsage>,
+}
+
+impl LocalCandidateUsage {
+    pub fn binary_name(&self) -> &String {
+        &self.binary_name
+    }
+    pub fn disk_usage(&self) -> u64 {
+        self.disk_usage
+    }
+    pub fn last_used(&self) -> Option<SystemTime> {
+        self.last_used
+    }
+    pub fn versions(&self) -> &[LocalVersionUsage] {
+        &self.versions
+    }
+    // access times are only a guess, e.g. with noatime mounts, so better records can be added
+    pub fn merge_last_used(&mut self, recorded: impl Fn(&str) -> Option<SystemTime>) {
+        for version in self.versions.iter_mut() {
+            version.last_used = version.last_used.max(recorded(&version.version));
+        }
+        self.last_used = self
+            .versions
+            .iter()
+            .map(|version| version.last_used)
+            .max()
+            .flatten();
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalVersionUsage {
+    version: String,
+    disk_usage: u64,
+    last_used: Option<SystemTime>,
+    current: bool,
+}
+
+impl LocalVersionUsage {
+    pub fn version(&self) -> &String {
+        &self.version
+    }
+    pub fn disk_usage(&self) -> u64 {
+        self.disk_usage
+    }
+    pub fn last_used(&self) -> Option<SystemTime> {
+        self.last_used
+    }
+    pub fn current(&self) -> bool {
+        self.current
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalFile {
+    name: String,
+    is_dir: bool,
+    size: u64,
+}
+
+impl LocalFile {
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+    pub fn is_dir(&self) -> bool {
+        self.is_dir
+    }
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+pub(crate) fn candidates_dir() -> std::io::Result<PathBuf> {
+    SdkmanDirs::resolve().map(|dirs| dirs.candidates)
+}
+
+// a scan that hangs, e.g. on an unresponsive network mount, gives up after this long
+pub fn set_scan_timeout(timeout: Duration) {
+    if let Ok(mut current) = SCAN_TIMEOUT.write() {
+        *current = timeout;
+    }
 }
 
 pub fn retrieve_local_candidates() -> std::io::Result<Vec<LocalCandidate>> {
-    match env::var("SDKMAN_CANDIDATES_DIR") {
-        Err(e) => Err(Error::new(ErrorKind::NotFound, e)),
-        Ok(candidates_dir) => {
-            let mut local_candidates: Vec<LocalCandidate> = Vec::new();
-
-            for candidate_entry in fs::read_dir(candidates_dir)? {
-                let candidate_path = candidate_entry?.path();
-                if candidate_path.is_file() {
-                    continue;
-                }
-                let binary_name = candidate_path
-                    .file_name()
-                    .unwrap_or_default()
-                    .to_string_lossy()
-                    .to_string();
-
-                let mut local_versions: HashMap<String, bool> = HashMap::new();
-
-                for version_dir in fs::read_dir(candidate_path)? {
-                    let version_path = version_dir?.path();
-
-                    if version_path.is_file() {
-                        continue;
-                    }
-
-                    let version_id = version_path
-                        .canonicalize()? // using canonicalize() follows a symlink and creates a canonized path
-                        .file_name()
-                        .unwrap_or_default()
-                        .to_string_lossy()
-                        .to_string();
-
-                    // since we followed the symlink,
-                    // one of the versions would be processed twice,
-                    // and that version is the currently used one
-                    let current = local_versions.contains_key(&version_id);
-                    local_versions.insert(version_id, current);
-                }
-
-                local_candidates.push(LocalCandidate::new(binary_name, local_versions));
-            }
-
-            Ok(local_candidates)
+    with_scan_timeout(|| store().scan())
+}
+
+pub fn retrieve_candidate_names() -> std::io::Result<Vec<String>> {
+    with_scan_timeout(|| store().candidate_names())
+}
+
+// rescans a single candidate, e.g. after a job changed it; None once it's gone
+pub fn retrieve_local_candidate(binary_name: &str) -> std::io::Result<Option<LocalCandidate>> {
+    let binary_name = binary_name.to_string();
+    with_scan_timeout(move || store().scan_candidate(&binary_name))
+}
+
+fn with_scan_timeout<T: Send + 'static>(
+    scan: impl FnOnce() -> std::io::Result<T> + Send + 'static,
+) -> std::io::Result<T> {
+    let timeout = SCAN_TIMEOUT
+        .read()
+        .map(|timeout| *timeout)
+        .unwrap_or(DEFAULT_SCAN_TIMEOUT);
+    let (sender, receiver) = mpsc::channel();
+    // a hanging scan can't be interrupted, so its thread is left behind
+    thread::spawn(move || {
+        let _ = sender.send(scan());
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(scanned) => scanned,
+        Err(_) => Err(Error::new(
+            ErrorKind::TimedOut,
+            format!(
+                "scanning the candidates took longer than {} seconds",
+                timeout.as_secs()
+            ),
+        )),
+    }
+}
+
+pub fn retrieve_candidate_usage(binary_name: &str) -> std::io::Result<LocalCandidateUsage> {
+    let candidate_path = candidates_dir()?.join(binary_name);
+    let current = store().current_version(binary_name)?;
+    let mut versions = Vec::new();
+
+    for version_dir in fs::read_dir(candidate_path)? {
+        let version_path = version_dir?.path();
+        // the current version is a symlink to one of the versions, so skip it to avoid counting twice
+        if fs::symlink_metadata(&version_path)?
+            .file_type()
+            .is_symlink()
+            || version_path.is_file()
+        {
+            continue;
         }
+        let version = version_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        versions.push(LocalVersionUsage {
+            current: current.as_ref() == Some(&version),
+            version,
+            disk_usage: dir_size(&version_path)?,
+            last_used: last_accessed(&version_path.join("bin")),
+        });
+    }
+
+    Ok(LocalCandidateUsage {
+        binary_name: binary_name.to_string(),
+        disk_usage: versions.iter().map(|version| version.disk_usage).sum(),
+        last_used: versions
+            .iter()
+            .map(|version| version.last_used)
+            .max()
+            .flatten(),
+        versions,
+    })
+}
+
+pub(crate) fn dir_size(path: &Path) -> std::io::Result<u64> {
+    let mut size = 0;
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = fs::symlink_metadata(entry.path())?;
+        if metadata.is_dir() {
+            size += dir_size(&entry.path())?;
+        } else {
+            size += metadata.len();
+        }
+    }
+    Ok(size)
+}
+
+// the most recent access time of the binaries is the best guess of when a version was used last
+fn last_accessed(bin_path: &Path) -> Option<SystemTime> {
+    fs::read_dir(bin_path)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.metadata().ok())
+        .filter_map(|metadata| metadata.accessed().ok())
+        .max()
+}
+
+pub fn candidate_path(binary_name: &str) -> std::io::Result<PathBuf> {
+    candidates_dir().map(|dir| dir.join(binary_name))
+}
+
+pub fn version_path(binary_name: &str, version: &str) -> std::io::Result<PathBuf> {
+    candidate_path(binary_name).map(|dir| dir.join(version))
+}
+
+// versions installed by other tools may still use the macOS bundle layout
+pub fn home_path(binary_name: &str, version: &str) -> std::io::Result<PathBuf> {
+    let path = version_path(binary_name, version)?;
+    let bundle_home = path.join("Contents").join("Home");
+    if bundle_home.is_dir() {
+        Ok(bundle_home)
+    } else {
+        Ok(path)
+    }
+}
+
+// the absolute path `sdk home` prints for an installed version, "current" for the current one
+pub fn sdk_home(binary_name: &str, version: &str) -> std::io::Result<PathBuf> {
+    let version = match version {
+        "current" => store().current_version(binary_name)?.ok_or_else(|| {
+            Error::new(
+                ErrorKind::NotFound,
+                format!("no version of {} is current", binary_name),
+            )
+        })?,
+        version => version.to_string(),
+    };
+    if !store().is_installed(binary_name, &version) {
+        return Err(Error::new(
+            ErrorKind::NotFound,
+            format!("{} {} is not installed", binary_name, version),
+        ));
+    }
+    std::path::absolute(home_path(binary_name, &version)?)
+}
+
+pub fn version_disk_usage(binary_name: &str, version: &str) -> std::io::Result<u64> {
+    store().version_size(binary_name, version)
+}
+
+pub fn list_version_files(binary_name: &str, version: &str) -> std::io::Result<Vec<LocalFile>> {
+    let mut files: Vec<LocalFile> = Vec::new();
+    for entry in fs::read_dir(version_path(binary_name, version)?)? {
+        let entry = entry?;
+        let metadata = fs::symlink_metadata(entry.path())?;
+        files.push(LocalFile {
+            name: entry.file_name().to_string_lossy().to_string(),
+            is_dir: metadata.is_dir(),
+            size: if metadata.is_dir() {
+                dir_size(&entry.path())?
+            } else {
+                metadata.len()
+            },
+        });
     }
+    files.sort_by(|f1, f2| f2.is_dir.cmp(&f1.is_dir).then(f1.name.cmp(&f2.name)));
+    Ok(files)
 }
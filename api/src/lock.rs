@@ -0,0 +1,102 @@
+use std::fs;
+use std::fs::File;
+use std::fs::TryLockError;
+use std::io;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::dirs::SdkmanDirs;
+use crate::util;
+
+const LOCK_DIR: &str = "locks";
+const LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+const RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+// an advisory lock on a candidate, held while its directory changes, so another sdkman-ui of
+// this or another user can't switch `current` or move a version in at the same time;
+// the lock goes away with the file handle
+#[derive(Debug)]
+pub struct CandidateLock {
+    _file: File,
+}
+
+impl CandidateLock {
+    // waits a while for whoever holds the lock, a switch or a move only takes a moment
+    pub fn acquire(binary_name: &str) -> io::Result<CandidateLock> {
+        Ok(CandidateLock {
+            _file: lock(binary_name, binary_name, LOCK_TIMEOUT)?,
+        })
+    }
+}
+
+// the same for a file of the installation that is edited, e.g. etc/config
+#[derive(Debug)]
+pub struct EditLock {
+    _file: File,
+}
+
+impl EditLock {
+    pub fn acquire(path: &Path) -> io::Result<EditLock> {
+        let name = util::cache_key(&path.to_string_lossy());
+        Ok(EditLock {
+            _file: lock(
+                &format!("edit{}", name),
+                &path.display().to_string(),
+                LOCK_TIMEOUT,
+            )?,
+        })
+    }
+
+    // fails with WouldBlock right away if someone else holds the lock
+    pub fn try_acquire(path: &Path) -> io::Result<EditLock> {
+        let name = util::cache_key(&path.to_string_lossy());
+        Ok(EditLock {
+            _file: lock(
+                &format!("edit{}", name),
+                &path.display().to_string(),
+                Duration::ZERO,
+            )?,
+        })
+    }
+}
+
+fn lock(name: &str, what: &str, timeout: Duration) -> io::Result<File> {
+    let file = open_lock_file(name)?;
+    let started = Instant::now();
+    loop {
+        match file.try_lock() {
+            Ok(()) => return Ok(file),
+            Err(TryLockError::WouldBlock) if started.elapsed() < timeout => {
+                thread::sleep(RETRY_INTERVAL)
+            }
+            Err(TryLockError::WouldBlock) => {
+                return Err(io::Error::new(
+                    ErrorKind::WouldBlock,
+                    format!("{} is being changed by another process", what),
+                ))
+            }
+            Err(TryLockError::Error(e)) => return Err(e),
+        }
+    }
+}
+
+// the lock files of a shared installation are created by whoever came first,
+// and a read-only handle is all the others need to lock them
+fn open_lock_file(name: &str) -> io::Result<File> {
+    let dir = SdkmanDirs::resolve()?.tmp.join(LOCK_DIR);
+    let path = dir.join(format!("{}.lock", name));
+    match File::open(&path) {
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            fs::create_dir_all(&dir)?;
+            fs::OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(&path)
+        }
+        opened => opened,
+    }
+}
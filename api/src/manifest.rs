@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use toml::Value;
+
+// bumped when a manifest changes in a way older versions can't read
+pub const SCHEMA_VERSION: i64 = 1;
+
+// the files the app writes for other machines and reads back
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Settings,
+    Snapshot,
+    Sdkmanrc,
+}
+
+impl Kind {
+    pub fn all() -> [Kind; 3] {
+        [Kind::Settings, Kind::Snapshot, Kind::Sdkmanrc]
+    }
+
+    // the value of the schema key
+    pub fn id(&self) -> &'static str {
+        match self {
+            Kind::Settings => "sdkman-ui/settings",
+            Kind::Snapshot => "sdkman-ui/snapshot",
+            Kind::Sdkmanrc => "sdkman-ui/sdkmanrc",
+        }
+    }
+
+    // the JSON Schema describing the data of the files, for editors and other tools
+    pub fn json_schema(&self) -> &'static str {
+        match self {
+            Kind::Settings => include_str!("schemas/settings.schema.json"),
+            Kind::Snapshot => include_str!("schemas/snapshot.schema.json"),
+            Kind::Sdkmanrc => include_str!("schemas/sdkmanrc.schema.json"),
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Kind::Settings => "settings",
+            Kind::Snapshot => "snapshot",
+            Kind::Sdkmanrc => "sdkmanrc",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum ManifestError {
+    #[error("{0}")]
+    Syntax(String),
+    #[error("The schema key is missing, this doesn't look like an exported {0} file")]
+    MissingSchema(Kind),
+    #[error("This is a {found} file, not a {expected} file")]
+    WrongKind { expected: Kind, found: String },
+    #[error(
+        "Schema version {0} is newer than this app understands, update sdkman-ui to import it"
+    )]
+    NewerVersion(i64),
+    #[error("Schema version must be a positive number")]
+    InvalidVersion,
+    #[error("{key}: {message}")]
+    Invalid { key: String, message: String },
+    #[error("Line {line}: {message}")]
+    Line { line: usize, message: String },
+}
+
+// the lines exported TOML files start with
+pub fn header(kind: Kind) -> String {
+    format!(
+        "schema = \"{}\"\nschema_version = {}\n\n",
+        kind.id(),
+        SCHEMA_VERSION
+    )
+}
+
+// checks the header and, as far as serde doesn't, the structure of an exported TOML file
+pub fn parse_toml(kind: Kind, content: &str) -> Result<Value, Vec<ManifestError>> {
+    let value: Value = content
+        .parse()
+        .map_err(|e: toml::de::Error| vec![ManifestError::Syntax(e.to_string())])?;
+    check_header(kind, &value).map_err(|e| vec![e])?;
+    let errors = match kind {
+        Kind::Snapshot => check_versions(value.get("versions"), "versions"),
+        Kind::Settings | Kind::Sdkmanrc => Vec::new(),
+    };
+    if errors.is_empty() {
+        Ok(value)
+    } else {
+        Err(errors)
+    }
+}
+
+fn check_header(kind: Kind, value: &Value) -> Result<(), ManifestError> {
+    match value.get("schema").map(Value::as_str) {
+        None => return Err(ManifestError::MissingSchema(kind)),
+        Some(Some(id)) if id == kind.id() => {}
+        Some(found) => {
+            return Err(ManifestError::WrongKind {
+                expected: kind,
+                found: found.unwrap_or("unknown").to_string(),
+            })
+        }
+    }
+    match value.get("schema_version").map(Value::as_integer) {
+        Some(Some(version)) if version > SCHEMA_VERSION => {
+            Err(ManifestError::NewerVersion(version))
+        }
+        Some(Some(version)) if version > 0 => Ok(()),
+        _ => Err(ManifestError::InvalidVersion),
+    }
+}
+
+fn check_versions(versions: Option<&Value>, key: &str) -> Vec<ManifestError> {
+    let table = match versions {
+        Some(Value::Table(table)) => table,
+        Some(_) => return vec![invalid(key, "must be a table of candidates and versions")],
+        None => return vec![invalid(key, "is missing")],
+    };
+    table
+        .iter()
+        .filter_map(|(binary_name, version)| {
+            let key = format!("{}.{}", key, binary_name);
+            if !is_candidate_name(binary_name) {
+                Some(invalid(&key, "isn't a candidate name"))
+            } else {
+                match version.as_str() {
+                    Some(version) if is_version(version) => None,
+                    Some(_) => Some(invalid(&key, "isn't a version")),
+                    None => Some(invalid(&key, "must be a string")),
+                }
+            }
+        })
+        .collect()
+}
+
+fn invalid(key: &str, message: &str) -> ManifestError {
+    ManifestError::Invalid {
+        key: key.to_string(),
+        message: message.to_string(),
+    }
+}
+
+// the "candidate=version" lines of a .sdkmanrc; lines that aren't are reported instead of skipped
+pub fn validate_sdkmanrc(content: &str) -> (Vec<(String, String)>, Vec<ManifestError>) {
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let mut error = |message: String| {
+            errors.push(ManifestError::Line {
+                line: line_number,
+                message,
+            })
+        };
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (binary_name, version) = match line.split_once('=') {
+            Some((binary_name, version)) => (binary_name.trim(), version.trim()),
+            None => {
+                error(format!("'{}' isn't a candidate=version line", line));
+                continue;
+            }
+        };
+        if !is_candidate_name(binary_name) {
+            error(format!("'{}' isn't a candidate name", binary_name));
+        } else if !is_version(version) {
+            error(format!("'{}' isn't a version of {}", version, binary_name));
+        } else if let Some(first) = seen.get(binary_name) {
+            error(format!(
+                "{} is already asked for on line {}",
+                binary_name, first
+            ));
+        } else {
+            seen.insert(binary_name.to_string(), line_number);
+            entries.push((binary_name.to_string(), version.to_string()));
+        }
+    }
+    (entries, errors)
+}
+
+fn is_candidate_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+}
+
+// versions end up in paths and commands, so only the characters SDKMAN's identifiers use pass
+fn is_version(version: &str) -> bool {
+    !version.is_empty()
+        && version
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '+' | '-'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_exported_snapshots() {
+        let content = format!(
+            "{}[versions]\njava = \"21.0.2-tem\"\ngradle = \"8.6\"\n",
+            header(Kind::Snapshot)
+        );
+        assert!(parse_toml(Kind::Snapshot, &content).is_ok());
+    }
+
+    #[test]
+    fn explains_what_is_wrong_with_a_manifest() {
+        assert_eq!(
+            parse_toml(Kind::Snapshot, "[versions]\njava = \"21\"\n").unwrap_err(),
+            vec![ManifestError::MissingSchema(Kind::Snapshot)]
+        );
+        assert_eq!(
+            parse_toml(Kind::Snapshot, &header(Kind::Settings)).unwrap_err(),
+            vec![ManifestError::WrongKind {
+                expected: Kind::Snapshot,
+                found: "sdkman-ui/settings".to_string(),
+            }]
+        );
+        let newer = "schema = \"sdkman-ui/settings\"\nschema_version = 2\n";
+        assert_eq!(
+            parse_toml(Kind::Settings, newer).unwrap_err(),
+            vec![ManifestError::NewerVersion(2)]
+        );
+        let content = format!(
+            "{}[versions]\nJava = \"21\"\ngradle = 8\n",
+            header(Kind::Snapshot)
+        );
+        assert_eq!(
+            parse_toml(Kind::Snapshot, &content).unwrap_err(),
+            vec![
+                invalid("versions.Java", "isn't a candidate name"),
+                invalid("versions.gradle", "must be a string"),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_invalid_sdkmanrc_lines() {
+        let content = "java=21.0.2-tem\nbroken\ngradle = 8 6\njava=17.0.10-tem\n";
+        let (entries, errors) = validate_sdkmanrc(content);
+        assert_eq!(
+            entries,
+            vec![("java".to_string(), "21.0.2-tem".to_string())]
+        );
+        assert_eq!(
+            errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec![
+                "Line 2: 'broken' isn't a candidate=version line",
+                "Line 3: '8 6' isn't a version of gradle",
+                "Line 4: java is already asked for on line 1",
+            ]
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use crate::remote::parse_candidates;
+use crate::remote::CandidateSource;
+use crate::remote::SdkmanApiError;
+
+// the recorded responses of the parser tests, so --mock works without a network
+const CANDIDATES: &str = include_str!("../tests/fixtures/candidates.txt");
+const GRADLE_VERSIONS: &str = include_str!("../tests/fixtures/versions-gradle.txt");
+const JAVA_VERSIONS: &str = include_str!("../tests/fixtures/versions-java.txt");
+
+// canned candidates and versions for development, demos and screenshots
+pub struct MockSource;
+
+impl CandidateSource for MockSource {
+    fn candidate_list(&self) -> Result<String, SdkmanApiError> {
+        Ok(CANDIDATES.to_string())
+    }
+
+    // candidates without a recording get their default version as the only one
+    fn candidate_versions(&self, binary_name: &str) -> Result<String, SdkmanApiError> {
+        match binary_name {
+            "gradle" => Ok(GRADLE_VERSIONS.to_string()),
+            "java" => Ok(JAVA_VERSIONS.to_string()),
+            _ => {
+                let candidate = parse_candidates(CANDIDATES.to_string())
+                    .into_iter()
+                    .find(|candidate| candidate.binary_name() == binary_name)
+                    .ok_or(SdkmanApiError::NotFound)?;
+                let version = candidate
+                    .default_version()
+                    .trim_start_matches('(')
+                    .trim_end_matches(')');
+                let frame = "=".repeat(80);
+                Ok(format!(
+                    "{frame}\nAvailable {} Versions\n{frame}\n     {}\n\n{frame}\n",
+                    candidate.name(),
+                    version
+                ))
+            }
+        }
+    }
+
+    // empty keeps the default version mentioned in the candidate list
+    fn default_version(&self, _binary_name: &str) -> Result<String, SdkmanApiError> {
+        Ok(String::new())
+    }
+
+    fn cacheable(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::fmt;
+
+// what is odd about the directory name of an installed version
+#[derive(Debug, Clone, PartialEq)]
+pub enum NameProblem {
+    // another version differs only by case, which breaks on case-insensitive file systems
+    CaseDuplicate(String),
+    // characters SDKMAN never uses, usually from copying a directory by hand
+    Unicode(Vec<char>),
+}
+
+impl fmt::Display for NameProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NameProblem::CaseDuplicate(other) => {
+                write!(f, "differs from {} only by case", other)
+            }
+            NameProblem::Unicode(chars) => write!(
+                f,
+                "contains {}",
+                chars
+                    .iter()
+                    .map(|c| format!("U+{:04X}", *c as u32))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+// the versions of a candidate whose names are likely to cause trouble
+pub fn check_versions<'a>(
+    versions: impl IntoIterator<Item = &'a String>,
+) -> Vec<(String, NameProblem)> {
+    let mut versions: Vec<&String> = versions.into_iter().collect();
+    versions.sort();
+    let mut by_lowercase: HashMap<String, Vec<&String>> = HashMap::new();
+    for version in &versions {
+        by_lowercase
+            .entry(version.to_lowercase())
+            .or_default()
+            .push(version);
+    }
+    let mut problems = Vec::new();
+    for version in versions {
+        let unusual: Vec<char> = version.chars().filter(|c| is_unusual(*c)).collect();
+        if !unusual.is_empty() {
+            problems.push((version.clone(), NameProblem::Unicode(unusual)));
+        }
+        if let Some(other) = by_lowercase[&version.to_lowercase()]
+            .iter()
+            .find(|other| **other != version)
+        {
+            problems.push((
+                version.clone(),
+                NameProblem::CaseDuplicate(other.to_string()),
+            ));
+        }
+    }
+    problems
+}
+
+// the name SDKMAN would have used: lookalikes replaced, invisible characters dropped
+pub fn normalize(version: &str) -> String {
+    version
+        .trim()
+        .chars()
+        .filter_map(|c| match c {
+            '\u{2010}'..='\u{2015}' | '\u{2212}' | '\u{FE63}' | '\u{FF0D}' => Some('-'),
+            '\u{FF0E}' | '\u{2024}' => Some('.'),
+            '\u{FF10}'..='\u{FF19}' => char::from_u32(c as u32 - 0xFF10 + '0' as u32),
+            '\u{FF21}'..='\u{FF3A}' | '\u{FF41}'..='\u{FF5A}' => {
+                char::from_u32(c as u32 - 0xFF01 + '!' as u32)
+            }
+            c if c.is_whitespace() => Some('-'),
+            c if c.is_control() || is_invisible(c) => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+fn is_unusual(c: char) -> bool {
+    !c.is_ascii() || c.is_ascii_control() || c.is_ascii_whitespace()
+}
+
+fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{200B}'..='\u{200F}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn finds_versions_differing_only_by_case() {
+        let versions = versions(&["21.0.2-tem", "21.0.2-TEM", "17.0.10-tem"]);
+        assert_eq!(
+            check_versions(&versions),
+            vec![
+                (
+                    "21.0.2-TEM".to_string(),
+                    NameProblem::CaseDuplicate("21.0.2-tem".to_string())
+                ),
+                (
+                    "21.0.2-tem".to_string(),
+                    NameProblem::CaseDuplicate("21.0.2-TEM".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_and_normalizes_unusual_characters() {
+        let versions = versions(&["8.6", "8.5\u{2013}rc1", "8.4\u{200B}"]);
+        assert_eq!(
+            check_versions(&versions),
+            vec![
+                (
+                    "8.4\u{200B}".to_string(),
+                    NameProblem::Unicode(vec!['\u{200B}'])
+                ),
+                (
+                    "8.5\u{2013}rc1".to_string(),
+                    NameProblem::Unicode(vec!['\u{2013}'])
+                ),
+            ]
+        );
+        assert_eq!(normalize("8.5\u{2013}rc1"), "8.5-rc1");
+        assert_eq!(normalize("8.4\u{200B}"), "8.4");
+        assert_eq!(normalize("\u{FF11}\u{FF17}\u{FF0E}0 tem"), "17.0-tem");
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(feature = "native")]
+use std::collections::HashMap;
+use std::fmt;
+
+#[cfg(feature = "native")]
+use serde::Deserialize;
+#[cfg(feature = "native")]
+use url::Url;
+
+#[cfg(feature = "native")]
+use crate::remote::SdkmanApiError;
+#[cfg(feature = "native")]
+use crate::util;
+
+#[cfg(feature = "native")]
+const MAVEN_CENTRAL_SEARCH: &str = "https://search.maven.org/solrsearch/select";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReleaseDate {
+    year: i64,
+    month: u32,
+    day: u32,
+}
+
+impl ReleaseDate {
+    pub fn from_unix_millis(millis: i64) -> ReleaseDate {
+        // civil date from days since the epoch, see http://howardhinnant.github.io/date_algorithms.html
+        let days = millis.div_euclid(86_400_000) + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+        let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        ReleaseDate { year, month, day }
+    }
+}
+
+impl fmt::Display for ReleaseDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+// candidates whose distributions are also published to Maven Central, which records release dates
+fn maven_coordinates(binary_name: &str) -> Option<(&'static str, &'static str)> {
+    match binary_name {
+        "ant" => Some(("org.apache.ant", "ant")),
+        "asciidoctorj" => Some(("org.asciidoctor", "asciidoctorj")),
+        "groovy" => Some(("org.apache.groovy", "groovy")),
+        "jbang" => Some(("dev.jbang", "jbang-cli")),
+        "kotlin" => Some(("org.jetbrains.kotlin", "kotlin-compiler")),
+        "maven" => Some(("org.apache.maven", "apache-maven")),
+        "micronaut" => Some(("io.micronaut.starter", "micronaut-cli")),
+        "quarkus" => Some(("io.quarkus", "quarkus-cli")),
+        "springboot" => Some(("org.springframework.boot", "spring-boot-cli")),
+        _ => None,
+    }
+}
+
+pub fn has_release_dates(binary_name: &str) -> bool {
+    maven_coordinates(binary_name).is_some()
+}
+
+#[cfg(feature = "native")]
+#[derive(Deserialize)]
+struct SearchResult {
+    response: SearchResponse,
+}
+
+#[cfg(feature = "native")]
+#[derive(Deserialize)]
+struct SearchResponse {
+    docs: Vec<SearchDoc>,
+}
+
+#[cfg(feature = "native")]
+#[derive(Deserialize)]
+struct SearchDoc {
+    v: String,
+    timestamp: i64,
+}
+
+#[cfg(feature = "native")]
+pub fn fetch_release_dates(
+    binary_name: &str,
+) -> Result<HashMap<String, ReleaseDate>, SdkmanApiError> {
+    let (group, artifact) = match maven_coordinates(binary_name) {
+        Some(coordinates) => coordinates,
+        None => return Ok(HashMap::new()),
+    };
+    let url = Url::parse_with_params(
+        MAVEN_CENTRAL_SEARCH,
+        &[
+            ("q", format!("g:{} AND a:{}", group, artifact)),
+            ("core", "gav".to_string()),
+            ("rows", "500".to_string()),
+            ("wt", "json".to_string()),
+        ],
+    )?;
+    util::fetch(url.as_str(), |res| {
+        let result: SearchResult = res.json()?;
+        Ok(result
+            .response
+            .docs
+            .into_iter()
+            .map(|doc| (doc.v, ReleaseDate::from_unix_millis(doc.timestamp)))
+            .collect())
+    })
+}
@@ -1,12 +1,34 @@
 use std::collections::HashMap;
 use std::env;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::RwLock;
+use std::thread;
+use std::time::Duration;
+use std::time::SystemTime;
 
 use lazy_static::lazy_static;
 use regex::Regex;
+#[cfg(feature = "native")]
 use reqwest::StatusCode;
+use serde::Deserialize;
+use serde::Serialize;
+#[cfg(feature = "native")]
 use url::Url;
 
+use crate::cache;
+use crate::cache::Namespace;
+use crate::category::Category;
+use crate::dirs::SdkmanDirs;
+use crate::links;
+use crate::links::QuickLink;
+use crate::release::ReleaseDate;
+#[cfg(feature = "native")]
+use crate::trace;
+use crate::trace::RequestId;
 use crate::util;
 
 type JavaVendor = String;
@@ -25,11 +47,12 @@ pub enum RemoteVersion {
         JavaDist,
         JavaStatus,
         JavaId,
+        Option<ReleaseDate>,
     ),
-    OtherVersion(String),
+    OtherVersion(String, Option<ReleaseDate>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct RemoteCandidate {
     name: String,
     binary_name: String,
@@ -74,29 +97,72 @@ impl RemoteCandidate {
     pub fn versions(&self) -> &Vec<RemoteVersion> {
         &self.versions
     }
+    pub fn category(&self) -> Category {
+        Category::of(&self.binary_name)
+    }
+    pub fn links(&self) -> &'static [QuickLink] {
+        links::of(&self.binary_name)
+    }
     pub fn with_versions(&mut self, versions: &[RemoteVersion]) -> &mut Self {
         self.versions = versions.to_vec();
         self
     }
+    pub fn with_release_dates(&mut self, dates: &HashMap<String, ReleaseDate>) -> &mut Self {
+        for version in self.versions.iter_mut() {
+            let released = dates.get(version.id()).copied();
+            version.set_released(released);
+        }
+        self
+    }
 }
 
 impl RemoteVersion {
     pub fn id(&self) -> &String {
         match self {
-            RemoteVersion::JavaVersion(_, _, _, _, _, id) => id,
-            RemoteVersion::OtherVersion(value) => value,
+            RemoteVersion::JavaVersion(_, _, _, _, _, id, _) => id,
+            RemoteVersion::OtherVersion(value, _) => value,
+        }
+    }
+    pub fn vendor(&self) -> &str {
+        match self {
+            RemoteVersion::JavaVersion(vendor, _, _, _, _, _, _) => vendor,
+            RemoteVersion::OtherVersion(_, _) => "",
+        }
+    }
+    pub fn version(&self) -> &String {
+        match self {
+            RemoteVersion::JavaVersion(_, _, version, _, _, _, _) => version,
+            RemoteVersion::OtherVersion(value, _) => value,
+        }
+    }
+    pub fn dist(&self) -> &str {
+        match self {
+            RemoteVersion::JavaVersion(_, _, _, dist, _, _, _) => dist,
+            RemoteVersion::OtherVersion(_, _) => "",
+        }
+    }
+    pub fn released(&self) -> Option<ReleaseDate> {
+        match self {
+            RemoteVersion::JavaVersion(_, _, _, _, _, _, released) => *released,
+            RemoteVersion::OtherVersion(_, released) => *released,
+        }
+    }
+    pub fn set_released(&mut self, date: Option<ReleaseDate>) {
+        match self {
+            RemoteVersion::JavaVersion(_, _, _, _, _, _, released) => *released = date,
+            RemoteVersion::OtherVersion(_, released) => *released = date,
         }
     }
     pub fn mk_string(&self, local_versions: &HashMap<String, bool>) -> String {
         match self {
-            RemoteVersion::JavaVersion(vendor, _, version, _, _, id) => {
+            RemoteVersion::JavaVersion(vendor, _, version, _, _, id, _) => {
                 let (status, usage) = self.get_status_and_usage(local_versions, id);
                 format!(
                     " {: <13} {: <20} {: <12} {: <10}",
                     vendor, version, status, usage
                 )
             }
-            RemoteVersion::OtherVersion(value) => {
+            RemoteVersion::OtherVersion(value, _) => {
                 let (status, usage) = self.get_status_and_usage(local_versions, value);
                 format!(" {: <20} {: >12} {: <10}", value, status, usage)
             }
@@ -136,10 +202,12 @@ impl FromStr for RemoteVersion {
                 util::string_at(&parts, 3),
                 util::string_at(&parts, 4),
                 util::string_at(&parts, 5),
+                None,
             ))
         } else {
             Ok(RemoteVersion::OtherVersion(
                 String::from_str(input.trim()).unwrap_or_default(),
+                None,
             ))
         }
     }
@@ -166,22 +234,16 @@ impl FromStr for RemoteCandidate {
         for line in input.lines() {
             if line.is_empty() {
                 continue;
-            } else if URI_REGEX.is_match(line) {
-                let uri = URI_REGEX
-                    .find(line)
-                    .map(|m| m.as_str())
-                    .unwrap_or("failed to extract the homepage");
-                homepage.push_str(uri);
-
-                let version = VERSION_REGEX
-                    .find_iter(line)
-                    .last()
-                    .map(|m| m.as_str())
-                    .unwrap_or("(unknown)");
-                default_version.push_str(version);
-
-                let idx = line.find(version).unwrap_or(line.len());
-                name = line.chars().take(idx - 1).collect();
+            } else if let Some(uri) = URI_REGEX.find(line).filter(|_| homepage.is_empty()) {
+                // the header is "<name> (<version>)   <homepage>"; the name may contain parentheses
+                // itself, and descriptions mentioning a link come after it
+                homepage.push_str(uri.as_str());
+                let header = &line[..uri.start()];
+                let version = VERSION_REGEX.find_iter(header).last();
+                default_version.push_str(version.map_or("(unknown)", |m| m.as_str()));
+                // match offsets are byte offsets on char boundaries, so this slicing is safe
+                let name_end = version.map_or(header.len(), |m| m.start());
+                name = header[..name_end].trim().to_string();
             } else if line.contains("$ sdk install") {
                 binary_name.push_str(line.split_whitespace().last().unwrap());
             } else {
@@ -210,19 +272,220 @@ pub enum SdkmanApiError {
     FailedResponseToString(#[from] std::io::Error),
     #[error("Url parsing failed")]
     UrlParsing(#[from] url::ParseError),
+    #[cfg(feature = "native")]
     #[error("Request failed")]
     RequestFailed(#[from] reqwest::Error),
     #[error("Bad request: {0}")]
     BadRequest(&'static str),
-    #[error("Server error: {0}")]
+    #[error("Not found: the API doesn't know this candidate or version, it may have been renamed or removed")]
+    NotFound,
+    #[error("Unknown candidate '{0}'")]
+    UnknownCandidate(String),
+    #[error("No version matches {0}")]
+    NoMatchingVersion(String),
+    #[error("Access denied ({0}): check the API URL and the credentials of your proxy")]
+    Unauthorized(u16),
+    #[error("Server error ({0}): the SDKMAN API has a problem, try again later")]
     ServerError(u16),
+    #[error("Unexpected response from the API: {0}")]
+    UnexpectedStatus(u16),
+    #[error("The API asked to wait, retrying in {} seconds", .0.as_secs())]
+    RateLimited(Duration),
+    #[error("Extracting the archive failed: {0}")]
+    ExtractionFailed(String),
+    #[error("Verifying the installation failed: {0}")]
+    VerificationFailed(String),
+    #[error("Verifying the signature failed: {0}")]
+    SignatureInvalid(String),
+    #[error("Cancelled")]
+    Cancelled,
+    #[error("{source} (request {id})")]
+    Traced {
+        id: RequestId,
+        source: Box<SdkmanApiError>,
+    },
+}
+
+impl SdkmanApiError {
+    // the error itself, without the request it happened in
+    pub fn root(&self) -> &SdkmanApiError {
+        match self {
+            SdkmanApiError::Traced { source, .. } => source.root(),
+            e => e,
+        }
+    }
+
+    // the error for a response that wasn't successful
+    pub fn from_status(status: u16) -> SdkmanApiError {
+        match status {
+            404 | 410 => SdkmanApiError::NotFound,
+            401 | 403 | 407 => SdkmanApiError::Unauthorized(status),
+            500..=599 => SdkmanApiError::ServerError(status),
+            _ => SdkmanApiError::UnexpectedStatus(status),
+        }
+    }
 }
 
 type BinaryName = String;
 
+const CATALOG_CACHE_KEY: &str = "candidates";
+const STABLE_API: &str = "https://api.sdkman.io/2";
+const BETA_API: &str = "https://beta.sdkman.io/2";
+const DEFAULT_VERSION_WORKERS: usize = 8;
+// when a 429 or 503 doesn't say how long to wait, and the longest wait that is honored
+#[cfg(feature = "native")]
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(30);
+#[cfg(feature = "native")]
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(600);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Channel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Channel::Stable => write!(f, "stable"),
+            Channel::Beta => write!(f, "beta"),
+        }
+    }
+}
+
+lazy_static! {
+    static ref CHANNEL: RwLock<Channel> = RwLock::new(Channel::default());
+    static ref PLATFORM_OVERRIDE: RwLock<Option<String>> = RwLock::new(None);
+    static ref FIXTURES_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+    static ref SOURCE: RwLock<Arc<dyn CandidateSource>> = RwLock::new(Arc::new(SdkmanApi));
+    static ref RATE_LIMITED_UNTIL: RwLock<Option<SystemTime>> = RwLock::new(None);
+}
+
+// where the texts describing the candidates and their versions come from
+pub trait CandidateSource: Send + Sync {
+    fn candidate_list(&self) -> Result<String, SdkmanApiError>;
+    fn candidate_versions(&self, binary_name: &str) -> Result<String, SdkmanApiError>;
+    fn default_version(&self, binary_name: &str) -> Result<String, SdkmanApiError>;
+    // the latest announcement of the SDKMAN team, e.g. about new releases
+    fn broadcast(&self) -> Result<String, SdkmanApiError> {
+        Ok(String::new())
+    }
+    // whether the texts are worth keeping in the cache
+    fn cacheable(&self) -> bool {
+        true
+    }
+}
+
+struct SdkmanApi;
+
+impl CandidateSource for SdkmanApi {
+    fn candidate_list(&self) -> Result<String, SdkmanApiError> {
+        fetch_text(&Endpoint::CandidateList)
+    }
+
+    fn candidate_versions(&self, binary_name: &str) -> Result<String, SdkmanApiError> {
+        fetch_text(&Endpoint::CandidateVersions(binary_name.to_string()))
+    }
+
+    fn default_version(&self, binary_name: &str) -> Result<String, SdkmanApiError> {
+        fetch_text(&Endpoint::DefaultVersion(binary_name.to_string()))
+    }
+
+    fn broadcast(&self) -> Result<String, SdkmanApiError> {
+        fetch_text(&Endpoint::Broadcast)
+    }
+}
+
+// replaces the SDKMAN API, e.g. with the canned data of api::mock
+pub fn set_source(source: Arc<dyn CandidateSource>) {
+    if let Ok(mut current) = SOURCE.write() {
+        *current = source;
+    }
+}
+
+fn source() -> Arc<dyn CandidateSource> {
+    match SOURCE.read() {
+        Ok(source) => source.clone(),
+        Err(_) => Arc::new(SdkmanApi),
+    }
+}
+
+pub fn set_channel(channel: Channel) {
+    if let Ok(mut current) = CHANNEL.write() {
+        *current = channel;
+    }
+}
+
+pub fn channel() -> Channel {
+    CHANNEL.read().map(|channel| *channel).unwrap_or_default()
+}
+
+// browsing the versions of another platform, e.g. the x86_64 builds on Apple silicon
+pub fn set_platform_override(platform: Option<String>) {
+    if let Ok(mut current) = PLATFORM_OVERRIDE.write() {
+        *current = platform.filter(|platform| !platform.trim().is_empty());
+    }
+}
+
+pub fn platform() -> String {
+    PLATFORM_OVERRIDE
+        .read()
+        .ok()
+        .and_then(|platform| platform.clone())
+        .or_else(|| env::var("SDKMAN_PLATFORM").ok())
+        .or_else(|| SdkmanDirs::resolve().ok().and_then(|dirs| dirs.platform()))
+        .unwrap_or_default()
+}
+
+// saves every response to the directory, named the way the fixture tests expect them
+pub fn record_fixtures(dir: Option<PathBuf>) {
+    if let Ok(mut current) = FIXTURES_DIR.write() {
+        *current = dir;
+    }
+}
+
+fn is_recording() -> bool {
+    FIXTURES_DIR.read().is_ok_and(|dir| dir.is_some())
+}
+
+fn record_fixture(endpoint: &Endpoint, text: &str) {
+    let dir = match FIXTURES_DIR.read().ok().and_then(|dir| dir.clone()) {
+        Some(dir) => dir,
+        None => return,
+    };
+    let file_name = match endpoint {
+        Endpoint::CandidateList => "candidates.txt".to_string(),
+        Endpoint::CandidateVersions(candidate) => format!("versions-{}.txt", candidate),
+        Endpoint::DefaultVersion(candidate) => format!("default-{}.txt", candidate),
+        Endpoint::Broadcast => "broadcast.txt".to_string(),
+        #[cfg(feature = "native")]
+        Endpoint::Download(..) => return,
+    };
+    let path = dir.join(file_name);
+    if let Err(e) = fs::create_dir_all(&dir).and_then(|_| fs::write(&path, text)) {
+        tracing::warn!("Failed to record {}: {}", path.display(), e);
+    }
+}
+
+// the stable channel is wherever the local SDKMAN points to, the beta channel has its own API
+pub fn api_url() -> Result<String, SdkmanApiError> {
+    match channel() {
+        Channel::Stable => {
+            Ok(env::var("SDKMAN_CANDIDATES_API").unwrap_or_else(|_| STABLE_API.to_string()))
+        }
+        Channel::Beta => Ok(BETA_API.to_string()),
+    }
+}
+
 enum Endpoint {
     CandidateList,
     CandidateVersions(BinaryName),
+    DefaultVersion(BinaryName),
+    #[cfg(feature = "native")]
+    Download(BinaryName, String),
+    Broadcast,
 }
 
 impl ToString for Endpoint {
@@ -233,51 +496,232 @@ impl ToString for Endpoint {
                 format!(
                     "/candidates/{}/{}/versions/list?installed=",
                     candidate,
-                    env::var("SDKMAN_PLATFORM").unwrap()
+                    platform()
                 )
             }
+            Self::DefaultVersion(candidate) => format!("/candidates/default/{}", candidate),
+            #[cfg(feature = "native")]
+            Self::Download(candidate, version) => {
+                format!("/broker/download/{}/{}/{}", candidate, version, platform())
+            }
+            Self::Broadcast => "/broadcast/latest".to_string(),
         }
     }
 }
 
 pub fn fetch_remote_candidates() -> Result<Vec<RemoteCandidate>, SdkmanApiError> {
-    let url = prepare_url(Endpoint::CandidateList)?;
-    let res = reqwest::blocking::get(url)?;
-    let status: StatusCode = res.status();
-    if status.is_success() {
-        res.text()
-            .map(parse_candidates)
-            .map_err(SdkmanApiError::RequestFailed)
-    } else {
-        Err(SdkmanApiError::ServerError(status.as_u16()))
+    let mut candidates = fetch_cached(
+        Namespace::Catalog,
+        &catalog_cache_key(),
+        Endpoint::CandidateList,
+    )
+    .map(parse_candidates)?;
+    fetch_default_versions(&mut candidates);
+    Ok(candidates)
+}
+
+// the blurb only mentions the default version in passing, the endpoint knows it for sure;
+// the one from the blurb stays if the endpoint can't be reached
+fn fetch_default_versions(candidates: &mut [RemoteCandidate]) {
+    let chunk_size = candidates.len().div_ceil(DEFAULT_VERSION_WORKERS).max(1);
+    thread::scope(|scope| {
+        for chunk in candidates.chunks_mut(chunk_size) {
+            scope.spawn(move || {
+                for candidate in chunk {
+                    match fetch_default_version(candidate.binary_name()) {
+                        Ok(version) if !version.is_empty() => {
+                            candidate.default_version = format!("({})", version);
+                        }
+                        Ok(_) => {}
+                        Err(e) => tracing::warn!(
+                            "Failed to fetch the default version of {}: {}",
+                            candidate.binary_name(),
+                            e
+                        ),
+                    }
+                }
+            });
+        }
+    });
+}
+
+// never cached, it's only asked for now and then
+pub fn fetch_broadcast() -> Result<String, SdkmanApiError> {
+    let source = source();
+    let text = source.broadcast()?;
+    if source.cacheable() {
+        record_fixture(&Endpoint::Broadcast, &text);
     }
+    Ok(text.trim().to_string())
+}
+
+pub fn fetch_default_version(binary_name: &str) -> Result<String, SdkmanApiError> {
+    fetch_cached(
+        Namespace::Catalog,
+        &format!("default-{}-{}", binary_name, channel()),
+        Endpoint::DefaultVersion(binary_name.to_string()),
+    )
+    .map(|text| text.trim().to_string())
 }
 
 pub fn fetch_candidate_versions(
     remote_candidate: &mut RemoteCandidate,
 ) -> Result<&RemoteCandidate, SdkmanApiError> {
-    let url = prepare_url(Endpoint::CandidateVersions(
-        remote_candidate.binary_name().clone(),
-    ))?;
-    let res = reqwest::blocking::get(url)?;
+    let text = fetch_cached(
+        Namespace::Versions,
+        &versions_cache_key(remote_candidate.binary_name()),
+        Endpoint::CandidateVersions(remote_candidate.binary_name().clone()),
+    )?;
+    Ok(&*remote_candidate.with_versions(&parse_available_versions(&text)))
+}
+
+// the channels have catalogs of their own
+pub fn catalog_cache_key() -> String {
+    format!("{}-{}", CATALOG_CACHE_KEY, channel())
+}
+
+// versions differ per platform and channel, so both are part of the key
+pub fn versions_cache_key(binary_name: &str) -> String {
+    format!("{}-{}-{}", binary_name, platform(), channel())
+}
+
+fn fetch_cached(
+    namespace: Namespace,
+    key: &str,
+    endpoint: Endpoint,
+) -> Result<String, SdkmanApiError> {
+    let source = source();
+    // a cached text may be days old, so recording asks the API every time
+    if source.cacheable() && !is_recording() {
+        if let Some(cached) = cache::get(namespace, key) {
+            return Ok(cached);
+        }
+    }
+    let text = match &endpoint {
+        Endpoint::CandidateList => source.candidate_list(),
+        Endpoint::CandidateVersions(candidate) => source.candidate_versions(candidate),
+        Endpoint::DefaultVersion(candidate) => source.default_version(candidate),
+        Endpoint::Broadcast => source.broadcast(),
+        #[cfg(feature = "native")]
+        Endpoint::Download(..) => Err(SdkmanApiError::BadRequest(
+            "downloads are not fetched as text",
+        )),
+    }?;
+    // the mock source would only record its own texts
+    if source.cacheable() {
+        cache::put(namespace, key, &text);
+        record_fixture(&endpoint, &text);
+    }
+    Ok(text)
+}
+
+#[cfg(feature = "native")]
+fn fetch_text(endpoint: &Endpoint) -> Result<String, SdkmanApiError> {
+    // the API isn't asked again before the time it named is up
+    if let Some(remaining) = rate_limit_remaining() {
+        return Err(SdkmanApiError::RateLimited(remaining));
+    }
+    let url = prepare_url(endpoint)?;
+    trace::traced("GET", url.as_str(), || {
+        let res = reqwest::blocking::get(url.clone())?;
+        let status: StatusCode = res.status();
+        trace::record_status(status.as_u16());
+        response_text(res)
+    })
+}
+
+#[cfg(feature = "native")]
+fn response_text(res: reqwest::blocking::Response) -> Result<String, SdkmanApiError> {
     let status: StatusCode = res.status();
     if status.is_success() {
-        res.text()
-            .map(move |text| &*remote_candidate.with_versions(&parse_available_versions(&text)))
-            .map_err(SdkmanApiError::RequestFailed)
+        res.text().map_err(SdkmanApiError::RequestFailed)
+    } else if status == StatusCode::TOO_MANY_REQUESTS || status == StatusCode::SERVICE_UNAVAILABLE {
+        let retry_after = res
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_retry_after)
+            .unwrap_or(DEFAULT_RETRY_AFTER)
+            .min(MAX_RETRY_AFTER);
+        if let Ok(mut until) = RATE_LIMITED_UNTIL.write() {
+            *until = Some(SystemTime::now() + retry_after);
+        }
+        Err(SdkmanApiError::RateLimited(retry_after))
     } else {
-        Err(SdkmanApiError::ServerError(status.as_u16()))
+        Err(SdkmanApiError::from_status(status.as_u16()))
+    }
+}
+
+// when the API may be asked again, if it asked to wait
+pub fn rate_limited_until() -> Option<SystemTime> {
+    RATE_LIMITED_UNTIL
+        .read()
+        .ok()
+        .and_then(|until| *until)
+        .filter(|until| *until > SystemTime::now())
+}
+
+#[cfg(feature = "native")]
+fn rate_limit_remaining() -> Option<Duration> {
+    rate_limited_until().and_then(|until| until.duration_since(SystemTime::now()).ok())
+}
+
+// either a number of seconds or an HTTP date
+#[cfg(feature = "native")]
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    match value.parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => httpdate::parse_http_date(value)
+            .ok()
+            .map(|date| date.duration_since(SystemTime::now()).unwrap_or_default()),
     }
 }
 
-fn prepare_url(endpoint: Endpoint) -> Result<String, SdkmanApiError> {
-    let base_url = env::var("SDKMAN_CANDIDATES_API")?;
+// the browser can't block on a request, the web frontend fetches the texts itself
+#[cfg(not(feature = "native"))]
+fn fetch_text(_endpoint: &Endpoint) -> Result<String, SdkmanApiError> {
+    Err(SdkmanApiError::BadRequest(
+        "blocking requests need the native feature",
+    ))
+}
+
+#[cfg(feature = "native")]
+pub fn submit_usage_counts(url: &str, payload: &str) -> Result<(), SdkmanApiError> {
+    let url = Url::parse(url)?;
+    trace::traced("POST", url.as_str(), || {
+        let res = reqwest::blocking::Client::new()
+            .post(url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(payload.to_string())
+            .send()?;
+        let status: StatusCode = res.status();
+        trace::record_status(status.as_u16());
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(SdkmanApiError::from_status(status.as_u16()))
+        }
+    })
+}
+
+#[cfg(feature = "native")]
+pub(crate) fn download_url(binary_name: &str, version: &str) -> Result<String, SdkmanApiError> {
+    prepare_url(&Endpoint::Download(
+        binary_name.to_string(),
+        version.to_string(),
+    ))
+}
+
+#[cfg(feature = "native")]
+fn prepare_url(endpoint: &Endpoint) -> Result<String, SdkmanApiError> {
+    let base_url = api_url()?;
     let complete_url = format!("{}{}", base_url, endpoint.to_string());
     let url = Url::parse(&complete_url)?;
     Ok(url.to_string())
 }
 
-fn parse_candidates(input: String) -> Vec<RemoteCandidate> {
+pub fn parse_candidates(input: String) -> Vec<RemoteCandidate> {
     let idx = input.find("-------------------------------").unwrap_or(0);
     let candidates: String = input.chars().skip(idx).collect();
     let pattern: String = candidates.chars().take_while(|c| *c == '-').collect();
@@ -291,17 +735,16 @@ fn parse_candidates(input: String) -> Vec<RemoteCandidate> {
         .collect()
 }
 
-fn parse_available_versions(input: &str) -> Vec<RemoteVersion> {
+pub fn parse_available_versions(input: &str) -> Vec<RemoteVersion> {
     if input.contains("Available Java Versions") {
         parse_available_java_versions(input)
     } else {
-        let versions = input
-            .lines()
-            .skip(3)
-            .take_while(|line| !line.starts_with("==="))
-            .collect::<Vec<&str>>()
-            .join(" ");
-        let mut strs: Vec<&str> = versions.split_whitespace().collect();
+        let lines: Vec<&str> = version_section(input).collect();
+        // candidates without versions for the platform come with a note instead, which has no digits
+        let mut strs: Vec<String> = split_columns(&lines)
+            .into_iter()
+            .filter(|version| version.chars().any(|c| c.is_ascii_digit()))
+            .collect();
         strs.sort_by(|s1, s2| alphanumeric_sort::compare_str(s2, s1));
         strs.iter()
             .map(|v| RemoteVersion::from_str(v).unwrap())
@@ -310,10 +753,312 @@ fn parse_available_versions(input: &str) -> Vec<RemoteVersion> {
 }
 
 fn parse_available_java_versions(input: &str) -> Vec<RemoteVersion> {
+    // the table starts below the dashes under its column names
+    let mut vendor = String::new();
+    version_section(input)
+        .skip_while(|line| !line.starts_with("---"))
+        .skip(1)
+        .filter(|line| line.contains(" | "))
+        .map(|line| {
+            let mut version = RemoteVersion::from_str(line).unwrap();
+            // only the first version of a vendor names it, the others belong to it as well
+            if let RemoteVersion::JavaVersion(version_vendor, ..) = &mut version {
+                if version_vendor.is_empty() {
+                    version_vendor.clone_from(&vendor);
+                } else {
+                    vendor.clone_from(version_vendor);
+                }
+            }
+            version
+        })
+        .collect()
+}
+
+// the versions are laid out in columns for an 80 character terminal; cutting the lines at the
+// column offsets instead of at every space keeps identifiers with spaces in them intact
+fn split_columns(lines: &[&str]) -> Vec<String> {
+    let rows: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
+    // the first row is always complete, so its cells tell where the columns start; a cell
+    // follows the indentation or a gap of two spaces or more
+    let header = rows
+        .iter()
+        .find(|row| row.iter().any(|c| *c != ' '))
+        .map_or(&[][..], |row| row.as_slice());
+    let starts: Vec<usize> = header
+        .iter()
+        .enumerate()
+        .filter(|(index, c)| {
+            let indented = header[..*index].iter().all(|c| *c == ' ');
+            let after_gap = *index >= 2 && header[index - 1] == ' ' && header[index - 2] == ' ';
+            **c != ' ' && (indented || after_gap)
+        })
+        .map(|(index, _)| index)
+        .collect();
+    let mut cells = Vec::new();
+    for row in &rows {
+        for (index, start) in starts.iter().enumerate() {
+            let end = starts
+                .get(index + 1)
+                .copied()
+                .unwrap_or(row.len())
+                .min(row.len());
+            if *start >= end {
+                continue;
+            }
+            let cell: String = row[*start..end].iter().collect();
+            // the markers for local, installed and current versions
+            let cell = cell.trim().trim_start_matches(['>', '*', '+', ' ']);
+            if !cell.is_empty() {
+                cells.push(cell.to_string());
+            }
+        }
+    }
+    cells
+}
+
+// the lines between the "===" framed header and the "===" line above the legend
+fn version_section(input: &str) -> impl Iterator<Item = &str> {
     input
         .lines()
-        .skip(5)
+        .skip_while(|line| !line.starts_with("==="))
+        .skip(1)
+        .skip_while(|line| !line.starts_with("==="))
+        .skip(1)
         .take_while(|line| !line.starts_with("==="))
-        .map(|line| RemoteVersion::from_str(line).unwrap())
-        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CANDIDATES: &str = include_str!("../tests/fixtures/candidates.txt");
+
+    #[test]
+    fn parses_every_candidate_blurb() {
+        let candidates = parse_candidates(CANDIDATES.to_string());
+        assert_eq!(
+            candidates.len(),
+            CANDIDATES.matches("$ sdk install").count()
+        );
+        for candidate in &candidates {
+            let binary_name = candidate.binary_name();
+            assert!(!candidate.name().is_empty(), "no name for {}", binary_name);
+            assert_eq!(candidate.name(), candidate.name().trim());
+            assert!(
+                binary_name.chars().all(|c| c.is_ascii_alphanumeric()),
+                "bad binary name '{}'",
+                binary_name
+            );
+            assert!(candidate.homepage().starts_with("http"));
+            assert!(
+                candidate.default_version().starts_with('(')
+                    && candidate.default_version().ends_with(')'),
+                "bad default version of {}: {}",
+                binary_name,
+                candidate.default_version()
+            );
+            assert!(!candidate.description().trim().is_empty());
+        }
+    }
+
+    #[test]
+    fn maps_statuses_to_errors() {
+        assert!(matches!(
+            SdkmanApiError::from_status(404),
+            SdkmanApiError::NotFound
+        ));
+        assert!(matches!(
+            SdkmanApiError::from_status(403),
+            SdkmanApiError::Unauthorized(403)
+        ));
+        assert!(matches!(
+            SdkmanApiError::from_status(502),
+            SdkmanApiError::ServerError(502)
+        ));
+        assert!(matches!(
+            SdkmanApiError::from_status(418),
+            SdkmanApiError::UnexpectedStatus(418)
+        ));
+    }
+
+    #[test]
+    fn keeps_parentheses_in_names() {
+        let candidates = parse_candidates(CANDIDATES.to_string());
+        let activemq = candidates
+            .iter()
+            .find(|candidate| candidate.binary_name() == "activemq")
+            .unwrap();
+        assert_eq!(activemq.name(), "Apache ActiveMQ (Classic)");
+        assert_eq!(activemq.default_version(), "(5.17.1)");
+    }
+
+    #[test]
+    fn tolerates_a_version_at_the_start_of_the_header() {
+        let candidate =
+            RemoteCandidate::from_str("(1.0.0)    https://example.org/\n\n  $ sdk install x")
+                .unwrap();
+        assert_eq!(candidate.name(), "");
+        assert_eq!(candidate.default_version(), "(1.0.0)");
+        assert_eq!(candidate.binary_name(), "x");
+    }
+
+    #[test]
+    fn slices_multibyte_names_on_char_boundaries() {
+        let candidate = RemoteCandidate::from_str(
+            "Ŝtrangé™ Tøøl (2.0)   https://example.org/\n\nA tool.\n  $ sdk install strange",
+        )
+        .unwrap();
+        assert_eq!(candidate.name(), "Ŝtrangé™ Tøøl");
+        assert_eq!(candidate.default_version(), "(2.0)");
+    }
+
+    #[test]
+    fn ignores_links_in_descriptions() {
+        let candidate = RemoteCandidate::from_str(
+            "Tool (1.0)   https://example.org/\n\nSee https://docs.example.org (2.0) for more.\n  $ sdk install tool",
+        )
+        .unwrap();
+        assert_eq!(candidate.name(), "Tool");
+        assert_eq!(candidate.homepage(), "https://example.org/");
+        assert_eq!(candidate.default_version(), "(1.0)");
+    }
+
+    #[test]
+    fn falls_back_without_a_version() {
+        let candidate =
+            RemoteCandidate::from_str("Tool   https://example.org/\n  $ sdk install tool").unwrap();
+        assert_eq!(candidate.name(), "Tool");
+        assert_eq!(candidate.default_version(), "(unknown)");
+    }
+
+    // replays the responses saved with --record-fixtures
+    #[test]
+    fn parses_recorded_fixtures() {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+            let text = fs::read_to_string(&path).unwrap();
+            if file_name == "candidates.txt" {
+                assert!(!parse_candidates(text).is_empty(), "no candidates");
+            } else if let Some(candidate) = file_name
+                .strip_prefix("versions-")
+                .and_then(|name| name.strip_suffix(".txt"))
+            {
+                let versions = parse_available_versions(&text);
+                // some candidates have no versions for the platform they were recorded on
+                assert!(
+                    !versions.is_empty() || text.contains("No versions available"),
+                    "no versions of {}",
+                    candidate
+                );
+                for version in &versions {
+                    let id = version.id();
+                    assert!(
+                        !id.is_empty()
+                            && id.trim() == id
+                            && !id.starts_with(['>', '*', '+'])
+                            && !id.contains('|'),
+                        "bad version of {}: '{}'",
+                        candidate,
+                        id
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn parses_versions() {
+        let versions = parse_available_versions(
+            "================================================================================
+Available Gradle Versions
+================================================================================
+     8.0.2               7.6                 7.5.1
+
+================================================================================
++ - local version
+================================================================================",
+        );
+        let ids: Vec<&String> = versions.iter().map(|version| version.id()).collect();
+        assert_eq!(ids, vec!["8.0.2", "7.6", "7.5.1"]);
+    }
+
+    #[test]
+    fn keeps_spaces_in_versions() {
+        let versions = parse_available_versions(
+            "================================================================================
+Available Foo Versions
+================================================================================
+     2.0 RC1             1.10.0              1.9.1
+   > 1.2.0-very-long-id  1.0 beta
+================================================================================",
+        );
+        let ids: Vec<&String> = versions.iter().map(|version| version.id()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "2.0 RC1",
+                "1.10.0",
+                "1.9.1",
+                "1.2.0-very-long-id",
+                "1.0 beta"
+            ]
+        );
+    }
+
+    #[test]
+    fn takes_the_columns_from_the_first_row() {
+        let versions = parse_available_versions(
+            "================================================================================
+Available Foo Versions
+================================================================================
+     2.0                 1.10.0              1.9.1
+     1.2  beta           1.0
+================================================================================",
+        );
+        let ids: Vec<&String> = versions.iter().map(|version| version.id()).collect();
+        assert_eq!(ids, vec!["2.0", "1.10.0", "1.9.1", "1.2  beta", "1.0"]);
+    }
+
+    #[test]
+    fn parses_java_versions() {
+        let versions = parse_available_java_versions(
+            "================================================================================
+Available Java Versions for Linux 64bit
+================================================================================
+ Vendor        | Use | Version      | Dist    | Status     | Identifier
+--------------------------------------------------------------------------------
+ Temurin       |     | 17.0.6       | tem     |            | 17.0.6-tem
+               |     | 11.0.18      | tem     |            | 11.0.18-tem
+================================================================================",
+        );
+        let ids: Vec<&String> = versions.iter().map(|version| version.id()).collect();
+        assert_eq!(ids, vec!["17.0.6-tem", "11.0.18-tem"]);
+        assert!(versions.iter().all(|version| version.vendor() == "Temurin"));
+    }
+
+    #[test]
+    fn parses_empty_version_lists() {
+        assert!(parse_available_versions(
+            "================================================================================
+Available Spark Versions
+================================================================================
+
+   No versions available for your platform.
+
+================================================================================",
+        )
+        .is_empty());
+        assert!(parse_available_java_versions(
+            "================================================================================
+Available Java Versions for Linux ARM 32bit Hard Float
+================================================================================
+ Vendor        | Use | Version      | Dist    | Status     | Identifier
+--------------------------------------------------------------------------------
+================================================================================",
+        )
+        .is_empty());
+        assert!(parse_available_versions("").is_empty());
+    }
 }
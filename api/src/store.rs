@@ -0,0 +1,472 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::RwLock;
+
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::fsutil;
+use crate::local::candidate_path;
+use crate::local::candidates_dir;
+use crate::local::dir_size;
+use crate::local::version_path;
+use crate::local::LocalCandidate;
+use crate::lock::CandidateLock;
+
+const CURRENT_LINK: &str = "current";
+const WRITE_PROBE: &str = ".sdkman-ui-write-probe";
+
+// the operations on $SDKMAN_CANDIDATES_DIR the install, use and delete flows are made of
+pub trait CandidateStore: Send + Sync {
+    fn scan(&self) -> io::Result<Vec<LocalCandidate>>;
+    // the candidates there are directories for, to scan one at a time
+    fn candidate_names(&self) -> io::Result<Vec<String>>;
+    // the versions of a single candidate, None once it's gone
+    fn scan_candidate(&self, binary_name: &str) -> io::Result<Option<LocalCandidate>>;
+    fn is_installed(&self, binary_name: &str, version: &str) -> bool;
+    // moves the unpacked content of a version into place
+    fn add_version(&self, binary_name: &str, version: &str, content: &Path) -> io::Result<()>;
+    fn remove_version(&self, binary_name: &str, version: &str) -> io::Result<()>;
+    fn current_version(&self, binary_name: &str) -> io::Result<Option<String>>;
+    fn set_current_version(&self, binary_name: &str, version: &str) -> io::Result<()>;
+    fn clear_current_version(&self, binary_name: &str) -> io::Result<()>;
+    fn version_size(&self, binary_name: &str, version: &str) -> io::Result<u64>;
+    // removes what's left of a candidate without versions, false if it still has some
+    fn remove_candidate(&self, binary_name: &str) -> io::Result<bool>;
+    // false for a shared installation owned by another user
+    fn is_writable(&self) -> bool {
+        true
+    }
+}
+
+// how the scan tells which version is current
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CurrentDetection {
+    // reads where the current link points without following it, which is safe on NFS and SSHFS
+    #[default]
+    ReadLink,
+    // resolves every version directory, which also sees through links to links but may hang
+    // on a network filesystem
+    Canonicalize,
+}
+
+lazy_static! {
+    static ref STORE: RwLock<Arc<dyn CandidateStore>> = RwLock::new(Arc::new(FsStore));
+    static ref CURRENT_DETECTION: RwLock<CurrentDetection> =
+        RwLock::new(CurrentDetection::default());
+}
+
+pub fn set_current_detection(detection: CurrentDetection) {
+    if let Ok(mut current) = CURRENT_DETECTION.write() {
+        *current = detection;
+    }
+}
+
+fn current_detection() -> CurrentDetection {
+    CURRENT_DETECTION
+        .read()
+        .map(|detection| *detection)
+        .unwrap_or_default()
+}
+
+// replaces the candidates directory, e.g. with a MemoryStore in tests
+pub fn set_store(store: Arc<dyn CandidateStore>) {
+    if let Ok(mut current) = STORE.write() {
+        *current = store;
+    }
+}
+
+pub fn store() -> Arc<dyn CandidateStore> {
+    match STORE.read() {
+        Ok(store) => store.clone(),
+        Err(_) => Arc::new(FsStore),
+    }
+}
+
+// the candidates directory of the local SDKMAN installation
+pub struct FsStore;
+
+impl CandidateStore for FsStore {
+    fn scan(&self) -> io::Result<Vec<LocalCandidate>> {
+        let detection = current_detection();
+        let mut local_candidates: Vec<LocalCandidate> = Vec::new();
+
+        for candidate_entry in fs::read_dir(candidates_dir()?)? {
+            let candidate_entry = candidate_entry?;
+            // the type comes with the directory listing, a stat on a network filesystem doesn't
+            if candidate_entry.file_type()?.is_file() {
+                continue;
+            }
+            let candidate_path = candidate_entry.path();
+            let binary_name = candidate_entry.file_name().to_string_lossy().to_string();
+            // one unreadable candidate, e.g. with a path too long for the mount, spoils no others
+            match scan_versions(&candidate_path, detection) {
+                Ok(local_versions) => {
+                    local_candidates.push(LocalCandidate::new(binary_name, local_versions))
+                }
+                Err(e) => tracing::warn!("Skipping {}: {}", candidate_path.display(), e),
+            }
+        }
+
+        Ok(local_candidates)
+    }
+
+    fn candidate_names(&self) -> io::Result<Vec<String>> {
+        let mut names = Vec::new();
+        for candidate_entry in fs::read_dir(candidates_dir()?)? {
+            let candidate_entry = candidate_entry?;
+            if !candidate_entry.file_type()?.is_file() {
+                names.push(candidate_entry.file_name().to_string_lossy().to_string());
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    fn scan_candidate(&self, binary_name: &str) -> io::Result<Option<LocalCandidate>> {
+        match scan_versions(&candidate_path(binary_name)?, current_detection()) {
+            Ok(versions) => Ok(Some(LocalCandidate::new(binary_name.to_string(), versions))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn is_installed(&self, binary_name: &str, version: &str) -> bool {
+        version_path(binary_name, version).is_ok_and(|path| path.is_dir())
+    }
+
+    fn add_version(&self, binary_name: &str, version: &str, content: &Path) -> io::Result<()> {
+        let _lock = CandidateLock::acquire(binary_name)?;
+        let target = version_path(binary_name, version)?;
+        // someone else may have installed it while this one was downloading
+        if target.exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!(
+                    "{} {} has been installed in the meantime",
+                    binary_name, version
+                ),
+            ));
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // the unpacked content may be on another filesystem, e.g. when tmp is linked elsewhere
+        fsutil::rename_dir(content, &target)
+    }
+
+    fn remove_version(&self, binary_name: &str, version: &str) -> io::Result<()> {
+        let _lock = CandidateLock::acquire(binary_name)?;
+        fs::remove_dir_all(version_path(binary_name, version)?)
+    }
+
+    fn current_version(&self, binary_name: &str) -> io::Result<Option<String>> {
+        match fs::read_link(current_link(binary_name)?) {
+            Ok(linked) => Ok(linked
+                .file_name()
+                .map(|version| version.to_string_lossy().to_string())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn set_current_version(&self, binary_name: &str, version: &str) -> io::Result<()> {
+        let _lock = CandidateLock::acquire(binary_name)?;
+        fsutil::replace_symlink(
+            &version_path(binary_name, version)?,
+            &current_link(binary_name)?,
+        )
+    }
+
+    fn clear_current_version(&self, binary_name: &str) -> io::Result<()> {
+        let _lock = CandidateLock::acquire(binary_name)?;
+        remove_current_link(binary_name)
+    }
+
+    fn version_size(&self, binary_name: &str, version: &str) -> io::Result<u64> {
+        dir_size(&version_path(binary_name, version)?)
+    }
+
+    fn remove_candidate(&self, binary_name: &str) -> io::Result<bool> {
+        let _lock = CandidateLock::acquire(binary_name)?;
+        let dir = candidate_path(binary_name)?;
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(e),
+        };
+        for entry in entries {
+            // is_dir() follows the current link, so only one pointing nowhere is left behind
+            if entry?.path().is_dir() {
+                return Ok(false);
+            }
+        }
+        fs::remove_dir_all(dir)?;
+        Ok(true)
+    }
+
+    // the permission bits don't tell about ACLs or read-only mounts, so it's tried out
+    fn is_writable(&self) -> bool {
+        let probe = match candidates_dir() {
+            Ok(dir) => dir.join(WRITE_PROBE),
+            Err(_) => return false,
+        };
+        let created = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&probe)
+            .is_ok();
+        let _ = fs::remove_file(&probe);
+        created
+    }
+}
+
+// the versions of a candidate, and which one is current
+fn scan_versions(
+    candidate_path: &Path,
+    detection: CurrentDetection,
+) -> io::Result<HashMap<String, bool>> {
+    let mut local_versions: HashMap<String, bool> = HashMap::new();
+    match detection {
+        CurrentDetection::ReadLink => {
+            for version_entry in fs::read_dir(candidate_path)? {
+                let version_entry = version_entry?;
+                let version_id = version_entry.file_name().to_string_lossy().to_string();
+                if version_id == CURRENT_LINK || version_entry.file_type()?.is_file() {
+                    continue;
+                }
+                local_versions.insert(version_id, false);
+            }
+            if let Ok(linked) = fs::read_link(candidate_path.join(CURRENT_LINK)) {
+                let current = linked.file_name().map(|name| name.to_string_lossy());
+                if let Some(current) = current.and_then(|name| local_versions.get_mut(&*name)) {
+                    *current = true;
+                }
+            }
+        }
+        CurrentDetection::Canonicalize => {
+            for version_dir in fs::read_dir(candidate_path)? {
+                let version_path = version_dir?.path();
+
+                if version_path.is_file() {
+                    continue;
+                }
+
+                let version_id = version_path
+                    .canonicalize()? // using canonicalize() follows a symlink and creates a canonized path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string();
+
+                // since we followed the symlink,
+                // one of the versions would be processed twice,
+                // and that version is the currently used one
+                let current = local_versions.contains_key(&version_id);
+                local_versions.insert(version_id, current);
+            }
+        }
+    }
+    Ok(local_versions)
+}
+
+fn current_link(binary_name: &str) -> io::Result<PathBuf> {
+    candidate_path(binary_name).map(|path| path.join(CURRENT_LINK))
+}
+
+fn remove_current_link(binary_name: &str) -> io::Result<()> {
+    let link = current_link(binary_name)?;
+    if fs::symlink_metadata(&link).is_ok() {
+        fs::remove_file(&link)?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct MemoryCandidate {
+    // the versions and their sizes
+    versions: BTreeMap<String, u64>,
+    current: Option<String>,
+}
+
+impl MemoryCandidate {
+    fn to_local(&self, binary_name: &str) -> LocalCandidate {
+        let versions = self
+            .versions
+            .keys()
+            .map(|version| {
+                let current = self.current.as_ref() == Some(version);
+                (version.clone(), current)
+            })
+            .collect();
+        LocalCandidate::new(binary_name.to_string(), versions)
+    }
+}
+
+// candidates kept in memory only, to exercise the flows without touching the disk
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    candidates: Mutex<BTreeMap<String, MemoryCandidate>>,
+}
+
+impl MemoryStore {
+    pub fn with_version(self, binary_name: &str, version: &str, size: u64) -> Self {
+        self.lock()
+            .entry(binary_name.to_string())
+            .or_default()
+            .versions
+            .insert(version.to_string(), size);
+        self
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, MemoryCandidate>> {
+        self.candidates.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn not_installed() -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, "the version is not installed")
+}
+
+impl CandidateStore for MemoryStore {
+    fn scan(&self) -> io::Result<Vec<LocalCandidate>> {
+        Ok(self
+            .lock()
+            .iter()
+            .map(|(binary_name, candidate)| candidate.to_local(binary_name))
+            .collect())
+    }
+
+    fn candidate_names(&self) -> io::Result<Vec<String>> {
+        Ok(self.lock().keys().cloned().collect())
+    }
+
+    fn scan_candidate(&self, binary_name: &str) -> io::Result<Option<LocalCandidate>> {
+        Ok(self
+            .lock()
+            .get(binary_name)
+            .map(|candidate| candidate.to_local(binary_name)))
+    }
+
+    fn is_installed(&self, binary_name: &str, version: &str) -> bool {
+        self.lock()
+            .get(binary_name)
+            .is_some_and(|candidate| candidate.versions.contains_key(version))
+    }
+
+    fn add_version(&self, binary_name: &str, version: &str, content: &Path) -> io::Result<()> {
+        let size = if content.is_dir() {
+            dir_size(content)?
+        } else {
+            0
+        };
+        self.lock()
+            .entry(binary_name.to_string())
+            .or_default()
+            .versions
+            .insert(version.to_string(), size);
+        Ok(())
+    }
+
+    fn remove_version(&self, binary_name: &str, version: &str) -> io::Result<()> {
+        self.lock()
+            .get_mut(binary_name)
+            .and_then(|candidate| candidate.versions.remove(version))
+            .map(|_| ())
+            .ok_or_else(not_installed)
+    }
+
+    fn current_version(&self, binary_name: &str) -> io::Result<Option<String>> {
+        Ok(self
+            .lock()
+            .get(binary_name)
+            .and_then(|candidate| candidate.current.clone()))
+    }
+
+    fn set_current_version(&self, binary_name: &str, version: &str) -> io::Result<()> {
+        match self.lock().get_mut(binary_name) {
+            Some(candidate) if candidate.versions.contains_key(version) => {
+                candidate.current = Some(version.to_string());
+                Ok(())
+            }
+            _ => Err(not_installed()),
+        }
+    }
+
+    fn clear_current_version(&self, binary_name: &str) -> io::Result<()> {
+        if let Some(candidate) = self.lock().get_mut(binary_name) {
+            candidate.current = None;
+        }
+        Ok(())
+    }
+
+    fn version_size(&self, binary_name: &str, version: &str) -> io::Result<u64> {
+        self.lock()
+            .get(binary_name)
+            .and_then(|candidate| candidate.versions.get(version).copied())
+            .ok_or_else(not_installed)
+    }
+
+    fn remove_candidate(&self, binary_name: &str) -> io::Result<bool> {
+        let mut candidates = self.lock();
+        match candidates.get(binary_name) {
+            Some(candidate) if candidate.versions.is_empty() => {
+                candidates.remove(binary_name);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+// the store is global, so the tests share one in which every test works on candidates of its own
+#[cfg(test)]
+pub(crate) fn memory_store() {
+    static STORE: std::sync::Once = std::sync::Once::new();
+    STORE.call_once(|| {
+        set_store(Arc::new(
+            MemoryStore::default()
+                .with_version("gradle", "8.0.2", 100)
+                .with_version("gradle", "7.6", 90)
+                .with_version("maven", "3.9.0", 10)
+                .with_version("kotlin", "1.8.10", 50)
+                .with_version("sbt", "1.8.2", 20)
+                .with_version("sbt", "1.9.0", 20)
+                .with_version("scala", "3.3.1", 30)
+                .with_version("java", "17.0.6-tem", 300),
+        ))
+    });
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_current_link_without_following_it() {
+        let dir = std::env::temp_dir().join(format!("sdkman-ui-scan-{}", std::process::id()));
+        fs::create_dir_all(dir.join("17.0.10-tem")).unwrap();
+        fs::create_dir_all(dir.join("21.0.2-tem")).unwrap();
+        fs::write(dir.join("notes.txt"), "").unwrap();
+        fsutil::symlink(&dir.join("21.0.2-tem"), &dir.join(CURRENT_LINK)).unwrap();
+
+        let versions = scan_versions(&dir, CurrentDetection::ReadLink).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            versions,
+            HashMap::from([
+                ("17.0.10-tem".to_string(), false),
+                ("21.0.2-tem".to_string(), true),
+            ])
+        );
+    }
+}
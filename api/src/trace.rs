@@ -0,0 +1,104 @@
+use std::fmt;
+#[cfg(feature = "native")]
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+#[cfg(feature = "native")]
+use std::time::Instant;
+
+#[cfg(feature = "native")]
+use tracing::field;
+#[cfg(feature = "native")]
+use tracing::Span;
+
+#[cfg(feature = "native")]
+use crate::remote::SdkmanApiError;
+
+#[cfg(feature = "native")]
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+// the requests and downloads that haven't finished yet
+pub fn in_flight() -> usize {
+    IN_FLIGHT.load(Ordering::SeqCst)
+}
+
+// counts a request as in flight until it's dropped, even if the request panics
+#[cfg(feature = "native")]
+struct InFlight;
+
+#[cfg(feature = "native")]
+impl InFlight {
+    fn start() -> InFlight {
+        IN_FLIGHT.fetch_add(1, Ordering::SeqCst);
+        InFlight
+    }
+}
+
+#[cfg(feature = "native")]
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// tells the log entries of one request apart from the others, and shows up in its errors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RequestId(u64);
+
+impl RequestId {
+    #[cfg(feature = "native")]
+    fn next() -> RequestId {
+        RequestId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "req-{:06}", self.0)
+    }
+}
+
+// runs a request in a span of its own with the URL, the duration and the status;
+// an error names the request, so it can be found in the log
+#[cfg(feature = "native")]
+pub(crate) fn traced<T>(
+    method: &str,
+    url: &str,
+    request: impl FnOnce() -> Result<T, SdkmanApiError>,
+) -> Result<T, SdkmanApiError> {
+    let id = RequestId::next();
+    let span = tracing::info_span!(
+        "request",
+        id = %id,
+        method,
+        url,
+        status = field::Empty,
+        elapsed_ms = field::Empty
+    );
+    let _entered = span.enter();
+    let started = Instant::now();
+    let in_flight = InFlight::start();
+    let result = request();
+    drop(in_flight);
+    span.record("elapsed_ms", started.elapsed().as_millis() as u64);
+    match result {
+        Ok(value) => {
+            tracing::info!("request finished");
+            Ok(value)
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "request failed");
+            Err(SdkmanApiError::Traced {
+                id,
+                source: Box::new(e),
+            })
+        }
+    }
+}
+
+// the status of the request whose span is entered
+#[cfg(feature = "native")]
+pub(crate) fn record_status(status: u16) {
+    Span::current().record("status", status);
+}
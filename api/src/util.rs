@@ -1,8 +1,46 @@
 use std::str::FromStr;
 
+#[cfg(feature = "native")]
+use reqwest::blocking::Response;
+
+#[cfg(feature = "native")]
+use crate::remote::SdkmanApiError;
+#[cfg(feature = "native")]
+use crate::trace;
+
 pub fn string_at(parts: &[&str], index: usize) -> String {
     parts
         .get(index)
         .map(|p| String::from_str(p).unwrap_or_default())
         .unwrap_or_default()
 }
+
+// a name for a file or directory, whatever the url or path it stands for
+pub fn cache_key(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+// a traced GET whose successful response is read by the given function
+#[cfg(feature = "native")]
+pub fn fetch<T>(
+    url: &str,
+    read: impl FnOnce(Response) -> Result<T, SdkmanApiError>,
+) -> Result<T, SdkmanApiError> {
+    trace::traced("GET", url, || {
+        let res = reqwest::blocking::get(url)?;
+        let status = res.status();
+        trace::record_status(status.as_u16());
+        if status.is_success() {
+            read(res)
+        } else {
+            Err(SdkmanApiError::from_status(status.as_u16()))
+        }
+    })
+}
+
+#[cfg(feature = "native")]
+pub fn fetch_text(url: &str) -> Result<String, SdkmanApiError> {
+    fetch(url, |res| res.text().map_err(SdkmanApiError::RequestFailed))
+}
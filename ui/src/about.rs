@@ -0,0 +1,67 @@
+use std::env;
+
+use eframe::egui::*;
+
+use api::remote;
+
+use crate::crash;
+
+const PADDING: f32 = 8.0;
+const REPOSITORY_URL: &str = "https://github.com/gerdreiss/sdkman-ui";
+const LICENSE: &str = "MIT License, Copyright 2021 Gerd Reiss";
+
+// false once the dialog is closed
+pub fn render_dialog(ctx: &CtxRef) -> bool {
+    let mut open = true;
+    Window::new("About sdkman-ui")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(Align2::CENTER_CENTER, [0., 0.])
+        .show(ctx, |ui| {
+            ui.add_space(PADDING);
+            ui.label("A desktop UI for SDKMAN, the software development kit manager.");
+            ui.add_space(PADDING);
+            Grid::new("about")
+                .num_columns(2)
+                .spacing([4. * PADDING, PADDING])
+                .show(ui, |ui| {
+                    ui.label("Version");
+                    ui.label(env!("CARGO_PKG_VERSION"));
+                    ui.end_row();
+                    ui.label("Commit");
+                    ui.monospace(env!("GIT_HASH"));
+                    ui.end_row();
+                    ui.label("SDKMAN");
+                    ui.label(crash::sdkman_version().unwrap_or_else(|| "not detected".to_string()));
+                    ui.end_row();
+                    ui.label("Platform");
+                    ui.label(format!(
+                        "{} ({} {})",
+                        remote::platform(),
+                        env::consts::OS,
+                        env::consts::ARCH
+                    ));
+                    ui.end_row();
+                    ui.label("License");
+                    ui.label(LICENSE);
+                    ui.end_row();
+                });
+            ui.add_space(PADDING);
+            ui.hyperlink(REPOSITORY_URL);
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui
+                    .button("Copy")
+                    .on_hover_text("Copy these details, e.g. for an issue")
+                    .clicked()
+                {
+                    ui.ctx().output().copied_text = crash::platform_info();
+                }
+                if ui.button("Close").clicked() {
+                    open = false;
+                }
+            });
+            ui.add_space(PADDING);
+        });
+    open
+}
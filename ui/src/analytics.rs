@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use eframe::egui::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use api::remote::submit_usage_counts;
+
+use crate::config::app_dir;
+use crate::util;
+
+const ANALYTICS_FILE: &str = "analytics.toml";
+const PADDING: f32 = 8.0;
+
+// only the number of installs per candidate is ever counted, nothing that identifies the user
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageCounts {
+    installs: BTreeMap<String, u64>,
+}
+
+impl UsageCounts {
+    pub fn load() -> UsageCounts {
+        util::load_toml(analytics_path(), "the usage statistics")
+    }
+
+    pub fn record_install(&mut self, binary_name: &str) -> std::io::Result<()> {
+        *self.installs.entry(binary_name.to_string()).or_insert(0) += 1;
+        self.save()
+    }
+
+    pub fn reset(&mut self) -> std::io::Result<()> {
+        self.installs.clear();
+        self.save()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.installs.is_empty()
+    }
+
+    // exactly what is sent, so the preview can show it verbatim
+    pub fn payload(&self) -> String {
+        serde_json::to_string_pretty(&serde_json::json!({
+            "app_version": env!("CARGO_PKG_VERSION"),
+            "installs": self.installs,
+        }))
+        .unwrap_or_default()
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        util::save_toml(&analytics_path()?, self)
+    }
+}
+
+// renders the preview of the data to submit; returns false once the preview should be closed
+pub fn render_preview(
+    ctx: &CtxRef,
+    usage_counts: &mut UsageCounts,
+    analytics_url: &str,
+    error_message: &mut Option<String>,
+) -> bool {
+    let mut open = true;
+    let payload = usage_counts.payload();
+    Window::new("Usage statistics")
+        .collapsible(false)
+        .anchor(Align2::CENTER_CENTER, [0., 0.])
+        .show(ctx, |ui| {
+            ui.add_space(PADDING);
+            ui.label("This is the complete data that will be sent:");
+            ui.add_space(PADDING);
+            ScrollArea::vertical().max_height(300.).show(ui, |ui| {
+                ui.monospace(&payload);
+            });
+            ui.add_space(PADDING);
+            ui.horizontal(|ui| {
+                ui.label("Destination:");
+                ui.monospace(if analytics_url.is_empty() {
+                    "none configured"
+                } else {
+                    analytics_url
+                });
+            });
+            ui.add_space(PADDING);
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        !analytics_url.is_empty() && !usage_counts.is_empty(),
+                        Button::new("Send"),
+                    )
+                    .clicked()
+                {
+                    match submit_usage_counts(analytics_url, &payload) {
+                        Ok(()) => {
+                            if let Err(e) = usage_counts.reset() {
+                                *error_message =
+                                    Some(format!("Resetting the usage counts failed with:\n{}", e));
+                            }
+                        }
+                        Err(e) => {
+                            *error_message =
+                                Some(format!("Sending the usage counts failed with:\n{}", e));
+                        }
+                    }
+                    open = false;
+                }
+                if ui.button("Reset counts").clicked() {
+                    if let Err(e) = usage_counts.reset() {
+                        *error_message =
+                            Some(format!("Resetting the usage counts failed with:\n{}", e));
+                    }
+                }
+                if ui.button("Cancel").clicked() {
+                    open = false;
+                }
+            });
+        });
+    open
+}
+
+fn analytics_path() -> std::io::Result<PathBuf> {
+    app_dir().map(|dir| dir.join(ANALYTICS_FILE))
+}
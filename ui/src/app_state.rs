@@ -0,0 +1,270 @@
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use api::cache;
+use api::cache::Namespace;
+use api::local::retrieve_candidate_names;
+use api::local::retrieve_local_candidate;
+use api::local::LocalCandidate;
+use api::remote::fetch_remote_candidates;
+use api::remote::RemoteCandidate;
+use api::remote::SdkmanApiError;
+
+use crate::repaint::RepaintNotifier;
+use crate::subscriptions::Inbox;
+use crate::tasks;
+
+// each part comes with the version it was set at
+#[derive(Default)]
+struct Shared {
+    remote: Option<(u64, Arc<Vec<Arc<RemoteCandidate>>>)>,
+    // why the latest reload of the catalog failed, until one goes through
+    remote_error: Option<(u64, Arc<SdkmanApiError>)>,
+    local: Option<(u64, Arc<Vec<LocalCandidate>>)>,
+    // rescans of single candidates since the last full scan, the latest one per candidate
+    local_changes: Vec<(u64, LocalChange)>,
+    inbox: Option<(u64, Arc<Inbox>)>,
+    // what the background refresh checks for new versions
+    subscriptions: Vec<String>,
+    // the full scan of the candidates directory running, if any
+    scan: Option<ScanProgress>,
+    // bumped by every scan started or cancelled, so an older scan knows to stop
+    scan_generation: u64,
+    // bumped on every update, so the UI only picks up what changed
+    version: u64,
+}
+
+// what the catalog and the local candidates are, updated by any thread;
+// the UI takes a snapshot whenever the version moved
+#[derive(Clone, Default)]
+pub struct AppState {
+    shared: Arc<RwLock<Shared>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ScanProgress {
+    pub scanned: usize,
+    pub total: usize,
+    pub current: String,
+}
+
+// a rescanned candidate, None once it's gone
+pub type LocalChange = (String, Option<LocalCandidate>);
+
+// the parts that changed since the version the UI has seen
+pub struct Update {
+    pub remote: Option<Arc<Vec<Arc<RemoteCandidate>>>>,
+    // to apply after remote
+    pub remote_error: Option<Arc<SdkmanApiError>>,
+    pub local: Option<Arc<Vec<LocalCandidate>>>,
+    // to apply after local
+    pub local_changes: Vec<LocalChange>,
+    pub inbox: Option<Arc<Inbox>>,
+    pub version: u64,
+}
+
+impl AppState {
+    pub fn set_remote(&self, remote: Vec<Arc<RemoteCandidate>>) {
+        let mut shared = self.shared.write().unwrap_or_else(|e| e.into_inner());
+        shared.version += 1;
+        shared.remote = Some((shared.version, Arc::new(remote)));
+        shared.remote_error = None;
+    }
+
+    // the whole catalog as last loaded, whatever the UI filtered it down to
+    pub fn remote(&self) -> Option<Arc<Vec<Arc<RemoteCandidate>>>> {
+        let shared = self.shared.read().unwrap_or_else(|e| e.into_inner());
+        shared.remote.as_ref().map(|(_, remote)| remote.clone())
+    }
+
+    pub fn set_remote_error(&self, e: SdkmanApiError) {
+        let mut shared = self.shared.write().unwrap_or_else(|e| e.into_inner());
+        shared.version += 1;
+        shared.remote_error = Some((shared.version, Arc::new(e)));
+    }
+
+    // fetches the catalog in the background, bypassing the cached list
+    pub fn reload_remote(&self, repaint: &RepaintNotifier) {
+        let state = self.clone();
+        let repaint = repaint.clone();
+        tasks::spawn(move || {
+            if let Err(e) = cache::invalidate(Namespace::Catalog) {
+                log!("Failed to clear the candidate cache: {}", e);
+            }
+            match fetch_remote_candidates() {
+                Ok(candidates) => state.set_remote(candidates.into_iter().map(Arc::new).collect()),
+                Err(e) => state.set_remote_error(e),
+            }
+            repaint.notify();
+        });
+    }
+
+    pub fn set_local(&self, local: Vec<LocalCandidate>) {
+        let mut shared = self.shared.write().unwrap_or_else(|e| e.into_inner());
+        shared.version += 1;
+        shared.local = Some((shared.version, Arc::new(local)));
+        // the full scan has them all
+        shared.local_changes.clear();
+    }
+
+    pub fn set_local_candidate(&self, binary_name: &str, local: Option<LocalCandidate>) {
+        let mut shared = self.shared.write().unwrap_or_else(|e| e.into_inner());
+        shared.version += 1;
+        let version = shared.version;
+        shared
+            .local_changes
+            .retain(|(_, (changed, _))| changed != binary_name);
+        shared
+            .local_changes
+            .push((version, (binary_name.to_string(), local)));
+    }
+
+    pub fn set_inbox(&self, inbox: Inbox) {
+        let mut shared = self.shared.write().unwrap_or_else(|e| e.into_inner());
+        shared.version += 1;
+        shared.inbox = Some((shared.version, Arc::new(inbox)));
+    }
+
+    pub fn set_subscriptions(&self, subscriptions: Vec<String>) {
+        let mut shared = self.shared.write().unwrap_or_else(|e| e.into_inner());
+        shared.subscriptions = subscriptions;
+    }
+
+    pub fn subscriptions(&self) -> Vec<String> {
+        let shared = self.shared.read().unwrap_or_else(|e| e.into_inner());
+        shared.subscriptions.clone()
+    }
+
+    // scans the candidates directory in the background one candidate at a time, so the list
+    // fills in as it goes; a newer scan or cancel_scan stops it
+    pub fn reload_local(&self, repaint: &RepaintNotifier) {
+        let generation = self.start_scan();
+        let state = self.clone();
+        let repaint = repaint.clone();
+        tasks::spawn(move || {
+            let binary_names = match retrieve_candidate_names() {
+                Ok(binary_names) => binary_names,
+                Err(e) => {
+                    log!("Failed to retrieve local candidates: {}", e);
+                    state.finish_scan(generation);
+                    repaint.notify();
+                    return;
+                }
+            };
+            let mut local = Vec::new();
+            for (scanned, binary_name) in binary_names.iter().enumerate() {
+                if !state.advance_scan(generation, scanned, binary_names.len(), binary_name) {
+                    return;
+                }
+                repaint.notify();
+                match retrieve_local_candidate(binary_name) {
+                    Ok(Some(candidate)) => {
+                        state.set_local_candidate(binary_name, Some(candidate.clone()));
+                        local.push(candidate);
+                    }
+                    Ok(None) => {}
+                    Err(e) => log!("Failed to scan {}: {}", binary_name, e),
+                }
+            }
+            // candidates removed in the meantime only drop out with the complete list
+            if state.finish_scan(generation) {
+                state.set_local(local);
+            }
+            repaint.notify();
+        });
+    }
+
+    fn start_scan(&self) -> u64 {
+        let mut shared = self.shared.write().unwrap_or_else(|e| e.into_inner());
+        shared.scan_generation += 1;
+        shared.scan = Some(ScanProgress::default());
+        shared.scan_generation
+    }
+
+    // false once the scan has been cancelled or replaced by a newer one
+    fn advance_scan(&self, generation: u64, scanned: usize, total: usize, current: &str) -> bool {
+        let mut shared = self.shared.write().unwrap_or_else(|e| e.into_inner());
+        if shared.scan_generation != generation {
+            return false;
+        }
+        shared.scan = Some(ScanProgress {
+            scanned,
+            total,
+            current: current.to_string(),
+        });
+        true
+    }
+
+    fn finish_scan(&self, generation: u64) -> bool {
+        let mut shared = self.shared.write().unwrap_or_else(|e| e.into_inner());
+        if shared.scan_generation != generation {
+            return false;
+        }
+        shared.scan = None;
+        true
+    }
+
+    // what has been scanned so far stays
+    pub fn cancel_scan(&self) {
+        let mut shared = self.shared.write().unwrap_or_else(|e| e.into_inner());
+        shared.scan_generation += 1;
+        shared.scan = None;
+    }
+
+    pub fn scan_progress(&self) -> Option<ScanProgress> {
+        let shared = self.shared.read().unwrap_or_else(|e| e.into_inner());
+        shared.scan.clone()
+    }
+
+    // rescans only the candidates a job touched, instead of the whole directory
+    pub fn reload_local_candidates(&self, binary_names: Vec<String>, repaint: &RepaintNotifier) {
+        let state = self.clone();
+        let repaint = repaint.clone();
+        tasks::spawn(move || {
+            for binary_name in binary_names {
+                match retrieve_local_candidate(&binary_name) {
+                    Ok(local) => state.set_local_candidate(&binary_name, local),
+                    Err(e) => log!("Failed to rescan {}: {}", binary_name, e),
+                }
+            }
+            repaint.notify();
+        });
+    }
+
+    // whatever was set after the given version, and the version it's at now
+    pub fn update_since(&self, seen: u64) -> Option<Update> {
+        let shared = self.shared.read().unwrap_or_else(|e| e.into_inner());
+        if shared.version == seen {
+            return None;
+        }
+        Some(Update {
+            remote: shared
+                .remote
+                .as_ref()
+                .filter(|(version, _)| *version > seen)
+                .map(|(_, remote)| remote.clone()),
+            remote_error: shared
+                .remote_error
+                .as_ref()
+                .filter(|(version, _)| *version > seen)
+                .map(|(_, e)| e.clone()),
+            local: shared
+                .local
+                .as_ref()
+                .filter(|(version, _)| *version > seen)
+                .map(|(_, local)| local.clone()),
+            local_changes: shared
+                .local_changes
+                .iter()
+                .filter(|(version, _)| *version > seen)
+                .map(|(_, change)| change.clone())
+                .collect(),
+            inbox: shared
+                .inbox
+                .as_ref()
+                .filter(|(version, _)| *version > seen)
+                .map(|(_, inbox)| inbox.clone()),
+            version: shared.version,
+        })
+    }
+}
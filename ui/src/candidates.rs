@@ -1,15 +1,140 @@
 use std::borrow::Cow;
+use std::cmp::Reverse;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::env;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use std::sync::mpsc::Sender;
+use std::sync::mpsc::TryRecvError;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use std::time::SystemTime;
 
 use eframe::egui::*;
 use image::GenericImageView;
+use serde::Deserialize;
+use serde::Serialize;
 
+use api::alias;
+use api::arch;
+use api::arch::ArchMismatch;
+use api::category;
+use api::category::Category;
+use api::category::Metadata;
+use api::dirs;
+use api::dirs::SdkmanDirs;
+use api::download;
+use api::links::QuickLink;
 use api::local::*;
+use api::release::*;
 use api::remote::*;
+use api::sdkman::Sdkman;
+use api::store::store;
+use api::trace;
+
+use crate::about;
+use crate::analytics;
+use crate::analytics::UsageCounts;
+use crate::app_state::AppState;
+use crate::columns::Column;
+use crate::columns::ColumnLayout;
+use crate::compare::Comparison;
+use crate::config::Config;
+use crate::config::DoubleClickAction;
+use crate::config::PaneLayout;
+use crate::config_watch::ConfigWatch;
+use crate::crash;
+use crate::dashboard::Dashboard;
+use crate::diagnostics;
+use crate::disk_watch::DiskWatch;
+use crate::doctor::Doctor;
+use crate::doctor::DoctorOutcome;
+use crate::download_preview::DownloadPreviewOutcome;
+use crate::download_preview::DownloadPreviewWindow;
+use crate::elevation;
+use crate::empty;
+use crate::empty::EmptyState;
+use crate::environment::Environment;
+use crate::environment::EnvironmentOutcome;
+use crate::environment::SDKMANRC;
+use crate::export;
+use crate::export::CatalogFormat;
+use crate::filters;
+use crate::filters::EditorOutcome;
+use crate::filters::SmartFilter;
+use crate::history::Action;
+use crate::history::History;
+use crate::homepage::HomepagePanel;
+use crate::jobs;
+use crate::jobs::Jobs;
+use crate::jobs::Step;
+use crate::journal;
+use crate::journal::Journal;
+use crate::journal::Recovery;
+use crate::last_used;
+use crate::local_install;
+use crate::local_install::LocalInstall;
+use crate::logs;
+use crate::onboarding::Onboarding;
+use crate::perf::PerfOverlay;
+use crate::profiles::Profile;
+use crate::profiles::ProfileEditor;
+use crate::profiles::ProfileOutcome;
+use crate::projects;
+use crate::projects::Projects;
+use crate::projects::ProjectsOutcome;
+use crate::protected::Protected;
+use crate::refresh::spawn_background_refresh;
+use crate::remove_all::RemoveAll;
+use crate::repaint::RepaintNotifier;
+use crate::run::RunOutcome;
+use crate::run::RunWindow;
+use crate::search;
+use crate::settings::Settings;
+use crate::settings::SettingsOutcome;
+use crate::shims;
+use crate::snapshots;
+use crate::snapshots::Snapshots;
+use crate::snapshots::SnapshotsOutcome;
+use crate::snapshots::SnapshotsWindow;
+use crate::state::UiState;
+use crate::subscriptions;
+use crate::subscriptions::Inbox;
+use crate::subscriptions::InboxAction;
+use crate::summary;
+use crate::summary::SummaryWindow;
+use crate::switch_preview::SwitchPreview;
+use crate::switch_preview::SwitchPreviewOutcome;
+use crate::tasks;
+use crate::time_machine::TimeMachine;
+use crate::time_machine::TimeMachineOutcome;
+use crate::transaction::Transaction;
+use crate::util;
 
 const PADDING: f32 = 8.0;
 const WHITE: Color32 = Color32::from_rgb(255, 255, 255);
 const CYAN: Color32 = Color32::from_rgb(0, 255, 255);
+const BADGE: Color32 = Color32::from_rgb(0, 96, 96);
+const DEPRECATED_BADGE: Color32 = Color32::from_rgb(160, 90, 0);
+const NETWORK_FRAMES: [&str; 4] = ["◐", "◓", "◑", "◒"];
+// roughly three lines of a candidate card
+const DESCRIPTION_PREVIEW_CHARS: usize = 300;
+// how long quitting waits for the background tasks
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+// runs the journaled jobs without a window
+pub const RESUME_JOBS_FLAG: &str = "--resume-jobs";
+const LIST_PANE_WIDTH: f32 = 220.;
+const ACTIVITY_PANE_WIDTH: f32 = 360.;
+// the most recent log lines in the activity pane
+const LOG_LINES: usize = 200;
+const READ_ONLY_MESSAGE: &str =
+    "The candidates directory of this SDKMAN installation is read-only for you";
 
 #[derive(PartialEq)]
 pub struct Logo {
@@ -17,42 +142,262 @@ pub struct Logo {
     pub pixels: Vec<Color32>,
 }
 
+// the remote model is shared, not copied, between the background threads and the UI
 #[derive(Debug, Clone, PartialEq)]
 pub struct Candidate {
-    name: String,
-    default_version: String,
-    url: String,
-    description: String,
+    model: Arc<RemoteCandidate>,
     installation_instruction: String,
-    versions: Vec<RemoteVersion>,
+    // only set if the description is too long to be shown in full
+    short_description: Option<String>,
+    // the version row clicked last, the snippets are for it
+    selected_version: Option<String>,
+    // installed, but no longer in the remote catalog
+    archived: bool,
+}
+
+// the sdk commands the card of the selected candidate offers to copy
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Snippet {
+    Use,
+    Home,
+}
+
+impl Snippet {
+    fn command(&self, binary_name: &str, version: &str) -> String {
+        let command = match self {
+            Snippet::Use => "use",
+            Snippet::Home => "home",
+        };
+        format!("sdk {} {} {}", command, binary_name, version)
+    }
+
+    fn hint(&self) -> &'static str {
+        match self {
+            Snippet::Use => "Use this version in the current shell",
+            Snippet::Home => "Print where this version is installed",
+        }
+    }
 }
 
 impl Candidate {
-    fn from_model(remote_candidate: &RemoteCandidate) -> Candidate {
+    fn from_model(model: Arc<RemoteCandidate>) -> Candidate {
+        Candidate {
+            installation_instruction: format!("$ sdk install {}", model.binary_name()),
+            short_description: shorten(model.description(), DESCRIPTION_PREVIEW_CHARS),
+            selected_version: None,
+            archived: false,
+            model,
+        }
+    }
+    // an installed candidate the catalog dropped, made of its local versions only
+    fn archived(local_candidate: &LocalCandidate) -> Candidate {
+        let binary_name = local_candidate.binary_name();
+        let mut model = RemoteCandidate::new(
+            binary_name.clone(),
+            binary_name.clone(),
+            "Archived upstream: the candidate is no longer in the SDKMAN catalog. Its installed \
+             versions can still be used and deleted."
+                .to_string(),
+            String::new(),
+            String::new(),
+        );
+        let mut versions: Vec<RemoteVersion> = local_candidate
+            .versions()
+            .keys()
+            .map(|version| RemoteVersion::OtherVersion(version.clone(), None))
+            .collect();
+        versions.sort_by(|v1, v2| alphanumeric_sort::compare_str(v2.id(), v1.id()));
+        model.with_versions(&versions);
         Candidate {
-            name: remote_candidate.name().clone(),
-            default_version: remote_candidate.default_version().clone(),
-            url: remote_candidate.homepage().clone(),
-            description: remote_candidate.description().clone(),
-            installation_instruction: format!("$ sdk install {}", remote_candidate.binary_name()),
-            versions: remote_candidate.versions().to_vec(),
-        }
-    }
-    fn to_model(&self) -> RemoteCandidate {
-        RemoteCandidate::new(
-            self.name.clone(),
-            self.installation_instruction
-                .split_whitespace()
-                .last()
-                .unwrap_or(&self.name.to_lowercase())
-                .to_owned(),
-            self.description.clone(),
-            self.url.clone(),
-            self.default_version.clone(),
+            archived: true,
+            ..Candidate::from_model(Arc::new(model))
+        }
+    }
+    pub(crate) fn name(&self) -> &String {
+        self.model.name()
+    }
+    pub(crate) fn default_version(&self) -> &String {
+        self.model.default_version()
+    }
+    pub(crate) fn binary_name(&self) -> &str {
+        self.model.binary_name()
+    }
+    pub(crate) fn homepage(&self) -> &String {
+        self.model.homepage()
+    }
+    pub(crate) fn description(&self) -> &String {
+        self.model.description()
+    }
+    pub(crate) fn category(&self) -> Category {
+        self.model.category()
+    }
+    pub(crate) fn links(&self) -> &'static [QuickLink] {
+        self.model.links()
+    }
+    pub(crate) fn versions(&self) -> &[RemoteVersion] {
+        self.model.versions()
+    }
+    pub(crate) fn is_archived(&self) -> bool {
+        self.archived
+    }
+    // the clicked version, or the default one
+    fn snippet_version(&self) -> &str {
+        self.selected_version
+            .as_deref()
+            .unwrap_or_else(|| util::strip_parens(self.default_version()))
+    }
+    // the versions are fetched into a copy, the shared model stays untouched
+    fn load_versions(&self) -> Result<Candidate, SdkmanApiError> {
+        // the API doesn't know about archived candidates, their versions are the installed ones
+        if self.archived {
+            return Ok(self.clone());
+        }
+        let mut model = (*self.model).clone();
+        Sdkman::new().load_versions(&mut model)?;
+        Ok(Candidate::from_model(Arc::new(model)))
+    }
+}
+
+// the clicked row stands out
+fn version_label(text: String, selected: bool) -> Label {
+    let label = Label::new(text).sense(Sense::click());
+    if selected {
+        label.strong()
+    } else {
+        label
+    }
+}
+
+// opens below the row on a right click and closes with the next click anywhere else
+fn context_menu(ui: &Ui, row: &Response, id_source: &str, add_contents: impl FnOnce(&mut Ui)) {
+    let popup_id = ui.make_persistent_id(id_source);
+    if row.secondary_clicked() {
+        ui.memory().toggle_popup(popup_id);
+    }
+    popup::popup_below_widget(ui, popup_id, row, add_contents);
+}
+
+// what the app itself kept around for a deleted version; the candidate directory is taken care of
+// by the job once the last version is gone
+fn forget_deleted(binary_name: &str, version: &str) {
+    match shims::remove_stale(binary_name, version) {
+        Ok(true) => log!("Removed the shim of {} {}", binary_name, version),
+        Ok(false) => {}
+        Err(e) => log!(
+            "Failed to remove the shim of {} {}: {}",
+            binary_name,
+            version,
+            e
+        ),
+    }
+    if let Err(e) = last_used::forget(binary_name, version) {
+        log!(
+            "Failed to forget when {} {} was used: {}",
+            binary_name,
+            version,
+            e
+        );
+    }
+}
+
+// cuts the text at the last word boundary before the limit
+fn shorten(text: &str, max_chars: usize) -> Option<String> {
+    let (cut, _) = text.char_indices().nth(max_chars)?;
+    let short = text[..cut]
+        .rfind(char::is_whitespace)
+        .map_or(&text[..cut], |space| &text[..space]);
+    Some(format!("{}…", short.trim_end()))
+}
+
+// the confirmation step of the "install latest" quick action
+pub struct QuickInstall {
+    binary_name: String,
+    // a version or a shorthand like latest, lts or 21-tem
+    alias: String,
+    default_version: String,
+    versions: Vec<RemoteVersion>,
+    set_current: bool,
+}
+
+impl QuickInstall {
+    fn new(candidate: &Candidate) -> QuickInstall {
+        QuickInstall {
+            binary_name: candidate.binary_name().to_string(),
+            alias: "default".to_string(),
+            default_version: candidate.default_version().clone(),
+            versions: candidate.versions().to_vec(),
+            set_current: true,
+        }
+    }
+
+    fn resolved(&self) -> Option<String> {
+        alias::resolve(
+            &self.binary_name,
+            &self.alias,
+            &self.versions,
+            &self.default_version,
         )
     }
+
+    fn steps(&self) -> Vec<Step> {
+        let version = match self.resolved() {
+            Some(version) => version,
+            None => return Vec::new(),
+        };
+        let mut steps = vec![Step::new(&self.binary_name, &version, Action::Install)];
+        if self.set_current {
+            steps.push(Step::new(&self.binary_name, &version, Action::SetCurrent));
+        }
+        steps
+    }
+}
+
+// quitting while jobs are running
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quit {
+    Asking,
+    // quits as soon as the jobs are done
+    WhenDone,
+    // the user chose in the app what happens to the jobs
+    Decided,
+}
+
+// what to fetch again once the API lets the app ask again
+#[derive(Debug, Clone, PartialEq)]
+pub enum Retry {
+    Catalog,
+    Versions(String),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tab {
+    Candidates,
+    Dashboard,
+    Projects,
+    Doctor,
+    Subscriptions,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DetailTab {
+    Versions,
+    Info,
+    Files,
+    History,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionSort {
+    Default,
+    NewestFirst,
+    OldestFirst,
+}
+
+// a delete and the files of the projects that still use its version
+type Usage = (Step, Vec<PathBuf>);
+
 pub struct SdkmanApp {
     app_name: &'static str,
     app_heading: &'static str,
@@ -63,6 +408,73 @@ pub struct SdkmanApp {
     candidate_search_dialog: bool,
     candidate_search_term: String,
     error_message: Option<String>,
+    config: Config,
+    onboarding: Option<Onboarding>,
+    background_refresh: Option<Sender<()>>,
+    tab: Tab,
+    dashboard: Dashboard,
+    projects: Projects,
+    detail_tab: DetailTab,
+    version_files: HashMap<(String, String), Vec<LocalFile>>,
+    history: History,
+    settings: Option<Settings>,
+    usage_counts: UsageCounts,
+    analytics_preview: Option<String>,
+    crash_report: Option<PathBuf>,
+    version_sort: VersionSort,
+    release_dates: HashMap<String, HashMap<String, ReleaseDate>>,
+    version_sizes: HashMap<(String, String), u64>,
+    // what sdk home prints for a version, looked up once
+    sdk_homes: HashMap<(String, String), Option<PathBuf>>,
+    repaint: RepaintNotifier,
+    perf: PerfOverlay,
+    expanded_descriptions: HashSet<String>,
+    catalog_error: Option<String>,
+    installed_only: bool,
+    jobs: Jobs,
+    quick_install: Option<QuickInstall>,
+    transaction: Transaction,
+    stage_changes: bool,
+    interrupted_jobs: Vec<Journal>,
+    arch_mismatches: HashMap<(String, String), Option<ArchMismatch>>,
+    active_filter: Option<SmartFilter>,
+    filter_editor: Option<SmartFilter>,
+    comparison: Comparison,
+    vendor_filter: Option<String>,
+    saved_state: UiState,
+    environment: Option<Environment>,
+    local_install: Option<LocalInstall>,
+    // the title and steps of a job deleting versions that projects still use
+    delete_warning: Option<(String, Vec<Step>, Vec<Usage>)>,
+    headless: bool,
+    installations: Vec<SdkmanDirs>,
+    read_only: bool,
+    doctor: Doctor,
+    scheduled_retry: Option<Retry>,
+    retry_ticker: Option<SystemTime>,
+    state: AppState,
+    state_version: u64,
+    quit: Option<Quit>,
+    resume_in_background: bool,
+    homepage: Option<HomepagePanel>,
+    inbox: Inbox,
+    weekly_summary: SummaryWindow,
+    about: bool,
+    remove_all: Option<RemoveAll>,
+    config_watch: Option<ConfigWatch>,
+    protected: Protected,
+    snapshots: Option<SnapshotsWindow>,
+    profile_editor: Option<ProfileEditor>,
+    time_machine: Option<TimeMachine>,
+    download_preview: Option<DownloadPreviewWindow>,
+    scan_on_start: bool,
+    summary_pending: bool,
+    run_window: Option<RunWindow>,
+    switch_preview: Option<SwitchPreview>,
+    disk_watch: Option<DiskWatch>,
+    export_catalog: bool,
+    // the versions of the candidate that was open when the app was closed
+    restoring: Option<Receiver<Candidate>>,
 }
 
 impl Default for SdkmanApp {
@@ -85,29 +497,1411 @@ impl Default for SdkmanApp {
             candidate_search_dialog: false,
             candidate_search_term: String::default(),
             error_message: None,
+            config: Config::default(),
+            onboarding: None,
+            background_refresh: None,
+            tab: Tab::Candidates,
+            dashboard: Dashboard::default(),
+            projects: Projects::default(),
+            detail_tab: DetailTab::Versions,
+            version_files: HashMap::new(),
+            history: History::default(),
+            settings: None,
+            usage_counts: UsageCounts::default(),
+            analytics_preview: None,
+            crash_report: None,
+            version_sort: VersionSort::Default,
+            release_dates: HashMap::new(),
+            version_sizes: HashMap::new(),
+            sdk_homes: HashMap::new(),
+            repaint: RepaintNotifier::default(),
+            perf: PerfOverlay::default(),
+            expanded_descriptions: HashSet::new(),
+            catalog_error: None,
+            installed_only: false,
+            jobs: Jobs::default(),
+            quick_install: None,
+            transaction: Transaction::default(),
+            stage_changes: false,
+            interrupted_jobs: Vec::new(),
+            arch_mismatches: HashMap::new(),
+            active_filter: None,
+            filter_editor: None,
+            comparison: Comparison::default(),
+            vendor_filter: None,
+            saved_state: UiState::default(),
+            environment: None,
+            local_install: None,
+            delete_warning: None,
+            headless: false,
+            installations: Vec::new(),
+            read_only: false,
+            doctor: Doctor::default(),
+            scheduled_retry: None,
+            retry_ticker: None,
+            state: AppState::default(),
+            state_version: 0,
+            quit: None,
+            resume_in_background: false,
+            homepage: None,
+            inbox: Inbox::default(),
+            weekly_summary: SummaryWindow::default(),
+            about: false,
+            remove_all: None,
+            config_watch: None,
+            protected: Protected::default(),
+            snapshots: None,
+            profile_editor: None,
+            time_machine: None,
+            download_preview: None,
+            scan_on_start: false,
+            summary_pending: false,
+            run_window: None,
+            switch_preview: None,
+            disk_watch: None,
+            export_catalog: false,
+            restoring: None,
         }
     }
 }
 
 impl SdkmanApp {
     pub fn new(
-        remote_candidates: &[RemoteCandidate],
-        local_candidates: &[LocalCandidate],
+        remote_candidates: Result<Vec<RemoteCandidate>, String>,
+        local_candidates: Vec<LocalCandidate>,
+        config: Config,
     ) -> SdkmanApp {
-        SdkmanApp {
-            candidates: remote_candidates
-                .iter()
-                .map(|remote_candidate| Candidate::from_model(remote_candidate))
-                .collect(),
-            local_candidates: local_candidates.to_vec(),
+        let catalog_error = remote_candidates.as_ref().err().cloned();
+        let mut candidates: Vec<Candidate> = remote_candidates
+            .unwrap_or_default()
+            .into_iter()
+            .map(|remote_candidate| Candidate::from_model(Arc::new(remote_candidate)))
+            .collect();
+        SdkmanApp::pin_first(&mut candidates, &config);
+        SdkmanApp::add_archived(&mut candidates, &local_candidates);
+        let state = UiState::load();
+        // the catalog comes in on its own once the API lets the app ask again
+        let scheduled_retry = catalog_error
+            .as_ref()
+            .filter(|_| rate_limited_until().is_some())
+            .map(|_| Retry::Catalog);
+        let mut app = SdkmanApp {
+            scheduled_retry,
+            detail_tab: state.detail_tab,
+            version_sort: state.version_sort,
+            vendor_filter: state.vendor_filter.clone(),
+            saved_state: state,
+            candidates,
+            catalog_error,
+            local_candidates,
+            onboarding: if config.onboarded {
+                None
+            } else {
+                Some(Onboarding::new(&config))
+            },
+            jobs: Jobs::new(config.verify_installs),
+            config,
+            history: History::load(),
+            usage_counts: UsageCounts::load(),
+            crash_report: crash::pending_report(),
+            interrupted_jobs: Journal::recover(),
+            installations: dirs::installations(),
+            inbox: Inbox::load(),
+            protected: Protected::load(),
             ..Default::default()
+        };
+        app.state
+            .set_subscriptions(app.config.subscriptions.clone());
+        app.check_permissions();
+        if let Some(binary_name) = app.saved_state.selected_candidate.clone() {
+            app.restore_candidate(binary_name);
         }
+        app
+    }
+
+    // the versions are fetched in the background, the window shows up without waiting for them
+    fn restore_candidate(&mut self, binary_name: String) {
+        let candidate = match self
+            .candidates
+            .iter()
+            .find(|candidate| candidate.binary_name() == binary_name)
+        {
+            Some(candidate) => candidate.clone(),
+            None => return,
+        };
+        let (sender, receiver) = mpsc::channel();
+        let repaint = self.repaint.clone();
+        tasks::spawn(move || match candidate.load_versions() {
+            Ok(candidate) => {
+                let _ = sender.send(candidate);
+                repaint.notify();
+            }
+            Err(e) => log!("Failed to restore the versions of {}: {}", binary_name, e),
+        });
+        self.restoring = Some(receiver);
+    }
+
+    pub fn receive_restored_candidate(&mut self) {
+        let candidate = match self.restoring.as_ref().map(Receiver::try_recv) {
+            Some(Ok(candidate)) => candidate,
+            Some(Err(TryRecvError::Disconnected)) => {
+                self.restoring = None;
+                return;
+            }
+            Some(Err(TryRecvError::Empty)) | None => return,
+        };
+        self.restoring = None;
+        // a candidate opened in the meantime stays
+        if self.selected_candidate.is_none() {
+            self.selected_candidate = Some(candidate);
+        }
+    }
+
+    // a root-owned installation is changed with administrator rights where the system can ask
+    // for them, and only looked at otherwise
+    fn check_permissions(&mut self) {
+        let writable = store().is_writable();
+        let elevated = !writable && elevation::available();
+        self.read_only = !writable && !elevated;
+        self.jobs.set_elevated(elevated);
     }
 
     pub fn app_name(&self) -> &str {
         self.app_name
     }
 
+    pub fn apply_theme(&self, ctx: &CtxRef) {
+        self.config.theme.apply(ctx);
+    }
+
+    fn pin_first(candidates: &mut [Candidate], config: &Config) {
+        // stable sort, so the remote order is kept within pinned and unpinned candidates
+        candidates.sort_by_key(|candidate| !config.is_pinned(candidate.binary_name()));
+    }
+
+    // installed candidates the catalog doesn't list anymore come last; without a catalog there's
+    // nothing to tell them by
+    fn add_archived(candidates: &mut Vec<Candidate>, local_candidates: &[LocalCandidate]) {
+        candidates.retain(|candidate| !candidate.archived);
+        if candidates.is_empty() {
+            return;
+        }
+        let mut archived: Vec<Candidate> = local_candidates
+            .iter()
+            .filter(|local| {
+                !local.versions().is_empty()
+                    && !candidates
+                        .iter()
+                        .any(|candidate| candidate.binary_name() == local.binary_name())
+            })
+            .map(Candidate::archived)
+            .collect();
+        archived.sort_by(|c1, c2| c1.binary_name().cmp(c2.binary_name()));
+        candidates.append(&mut archived);
+    }
+
+    // background threads can only wake up the UI once the frame is known
+    pub fn start_background_tasks(&mut self, frame: &eframe::epi::Frame<'_>) {
+        self.repaint = RepaintNotifier::new(frame.repaint_signal());
+        self.background_refresh =
+            SdkmanApp::start_background_refresh(&self.config, &self.state, &self.repaint);
+        SdkmanApp::load_categories(&self.config, &self.repaint);
+        self.config_watch = Some(ConfigWatch::spawn(&self.repaint));
+        self.disk_watch = Some(DiskWatch::spawn(&self.repaint));
+        if self.scan_on_start {
+            self.state.reload_local(&self.repaint);
+        }
+        if self.config.weekly_summary && summary::due() {
+            // it compares with the installed versions, so it waits for the scan
+            if self.scan_on_start {
+                self.summary_pending = true;
+            } else {
+                self.load_weekly_summary();
+            }
+        }
+    }
+
+    // the installed versions stream in once the window is open instead of before
+    pub fn scan_local_on_start(&mut self) {
+        self.scan_on_start = true;
+    }
+
+    fn load_weekly_summary(&mut self) {
+        self.weekly_summary.load(
+            self.candidates
+                .iter()
+                .filter(|c| !c.archived)
+                .map(|c| c.model.clone())
+                .collect(),
+            self.local_candidates.clone(),
+            &self.repaint,
+        );
+    }
+
+    // the grouping in the list changes once the metadata file is there
+    fn load_categories(config: &Config, repaint: &RepaintNotifier) {
+        let url = config.categories_url.trim().to_string();
+        if url.is_empty() {
+            category::set_metadata(Metadata::default());
+            return;
+        }
+        let repaint = repaint.clone();
+        tasks::spawn(move || match category::fetch_metadata(&url) {
+            Ok(metadata) => {
+                category::set_metadata(metadata);
+                repaint.notify();
+            }
+            Err(e) => log!("Fetching the categories from {} failed: {}", url, e),
+        });
+    }
+
+    fn start_background_refresh(
+        config: &Config,
+        state: &AppState,
+        repaint: &RepaintNotifier,
+    ) -> Option<Sender<()>> {
+        if config.onboarded && config.background_refresh {
+            Some(spawn_background_refresh(
+                Duration::from_secs(config.refresh_interval_minutes * 60),
+                state.clone(),
+                repaint.clone(),
+            ))
+        } else {
+            None
+        }
+    }
+
+    // picks up whatever the background threads changed since the last frame
+    pub fn receive_state(&mut self) {
+        let update = match self.state.update_since(self.state_version) {
+            Some(update) => update,
+            None => return,
+        };
+        self.state_version = update.version;
+        // the complete list only arrives once the scan has finished
+        let local_scanned = update.local.is_some();
+        let catalog_changed =
+            update.remote.is_some() || update.local.is_some() || !update.local_changes.is_empty();
+        if let Some(models) = update.remote {
+            let mut candidates: Vec<Candidate> =
+                models.iter().cloned().map(Candidate::from_model).collect();
+            SdkmanApp::pin_first(&mut candidates, &self.config);
+            self.candidates = candidates;
+            self.catalog_error = None;
+            self.installed_only = false;
+        }
+        if let Some(e) = update.remote_error {
+            // the empty list shows the error by itself
+            if !SdkmanApp::schedule_retry(&e, Retry::Catalog, &mut self.scheduled_retry)
+                && !self.candidates.is_empty()
+            {
+                self.error_message = Some(format!(
+                    "Refreshing the list of candidates failed with:\n{}",
+                    e
+                ));
+            }
+            self.catalog_error = Some(e.to_string());
+        }
+        if let Some(local_candidates) = update.local {
+            self.local_candidates = local_candidates.as_ref().clone();
+            self.version_sizes.clear();
+            self.sdk_homes.clear();
+            self.arch_mismatches.clear();
+            self.version_files.clear();
+            self.dashboard = Dashboard::default();
+        }
+        for (binary_name, local) in update.local_changes {
+            self.apply_local_change(&binary_name, local);
+        }
+        if catalog_changed {
+            SdkmanApp::add_archived(&mut self.candidates, &self.local_candidates);
+            self.projects.resolve(&self.local_candidates);
+            if self.time_machine.is_some() {
+                self.time_machine = Some(TimeMachine::new(&self.history, &self.local_candidates));
+            }
+        }
+        if local_scanned && self.summary_pending {
+            self.summary_pending = false;
+            self.load_weekly_summary();
+        }
+        if let Some(inbox) = update.inbox {
+            self.inbox = inbox.as_ref().clone();
+        }
+    }
+
+    // only what belongs to the rescanned candidate is forgotten
+    fn apply_local_change(&mut self, binary_name: &str, local: Option<LocalCandidate>) {
+        let index = self
+            .local_candidates
+            .iter()
+            .position(|candidate| candidate.binary_name() == binary_name);
+        match (index, local) {
+            (Some(index), Some(local)) => self.local_candidates[index] = local,
+            (Some(index), None) => {
+                self.local_candidates.remove(index);
+            }
+            (None, Some(local)) => self.local_candidates.push(local),
+            (None, None) => {}
+        }
+        self.version_sizes
+            .retain(|(name, _), _| name != binary_name);
+        self.sdk_homes.retain(|(name, _), _| name != binary_name);
+        self.arch_mismatches
+            .retain(|(name, _), _| name != binary_name);
+        self.version_files
+            .retain(|(name, _), _| name != binary_name);
+        self.dashboard = Dashboard::default();
+    }
+
+    pub fn receive_jobs(&mut self) {
+        let finished = self.jobs.poll();
+        if finished.is_empty() {
+            return;
+        }
+        let mut touched = Vec::new();
+        for job in finished {
+            touched.extend(job.touched);
+            for step in &job.completed {
+                if let Err(e) = self
+                    .history
+                    .record(&step.binary_name, &step.version, step.action)
+                {
+                    self.error_message = Some(format!("Recording the action failed with:\n{}", e));
+                }
+                if step.action == Action::Delete {
+                    forget_deleted(&step.binary_name, &step.version);
+                }
+                if step.action == Action::Install && self.config.analytics_enabled {
+                    if let Err(e) = self.usage_counts.record_install(&step.binary_name) {
+                        self.error_message =
+                            Some(format!("Counting the installation failed with:\n{}", e));
+                    }
+                }
+            }
+            if let Some(e) = job.error {
+                self.error_message = Some(format!("{} failed with:\n{}", job.title, e));
+            }
+        }
+        // whatever the jobs changed on disk is picked up again
+        touched.sort();
+        touched.dedup();
+        self.state.reload_local_candidates(touched, &self.repaint);
+    }
+
+    fn reload_local_candidates(&mut self) {
+        self.state.reload_local(&self.repaint);
+    }
+
+    // the other installation has its own candidates, platform and cache
+    fn switch_installation(&mut self, root: PathBuf) {
+        self.config.sdkman_dir = Some(root);
+        self.config.configure_installation();
+        self.config.configure_cache();
+        if let Err(e) = self.config.save() {
+            self.error_message = Some(format!("Saving the installation failed with:\n{}", e));
+        }
+        self.selected_candidate = None;
+        self.check_permissions();
+        self.reload_local_candidates();
+    }
+
+    pub fn render_jobs(&mut self, ctx: &CtxRef) {
+        if let Some(steps) = self.transaction.render_review(ctx) {
+            let title = format!("Apply {} pending changes", steps.len());
+            self.jobs.submit(title, steps, &self.repaint);
+        }
+        let docked = self.three_panes();
+        if self.jobs.render(ctx, &self.repaint, docked) {
+            self.open_cleanup();
+        }
+    }
+
+    fn three_panes(&self) -> bool {
+        self.tab == Tab::Candidates && self.config.pane_layout == PaneLayout::ThreePanes
+    }
+
+    // the list and the jobs with the log next to the versions of the selected candidate
+    pub fn render_panes(&mut self, ctx: &CtxRef) {
+        if !self.three_panes() {
+            return;
+        }
+        let mut clicked = None;
+        SidePanel::left("candidate_list")
+            .resizable(true)
+            .default_width(LIST_PANE_WIDTH)
+            .show(ctx, |ui| {
+                ui.add_space(PADDING);
+                ScrollArea::vertical().show(ui, |ui| {
+                    for candidate in &self.candidates {
+                        let selected = self.selected_candidate.as_ref().is_some_and(|selected| {
+                            selected.binary_name() == candidate.binary_name()
+                        });
+                        let pin = if self.config.is_pinned(candidate.binary_name()) {
+                            "📌 "
+                        } else {
+                            ""
+                        };
+                        if ui
+                            .selectable_label(selected, format!("{}{}", pin, candidate.name()))
+                            .clicked()
+                        {
+                            clicked = Some((candidate.binary_name().to_string(), selected));
+                        }
+                    }
+                });
+            });
+        SidePanel::right("activity")
+            .resizable(true)
+            .default_width(ACTIVITY_PANE_WIDTH)
+            .show(ctx, |ui| {
+                ui.add_space(PADDING);
+                ui.heading("Jobs");
+                self.jobs.render_docked(ui, &self.repaint);
+                ui.separator();
+                ui.heading("Log");
+                ScrollArea::vertical().id_source("log").show(ui, |ui| {
+                    // the newest first
+                    for line in logs::snapshot().iter().rev().take(LOG_LINES) {
+                        ui.add(Label::new(line).small().wrap(true));
+                    }
+                });
+            });
+        match clicked {
+            // a second click closes the candidate again
+            Some((_, true)) => self.selected_candidate = None,
+            Some((binary_name, false)) => self.open_candidate(&binary_name),
+            None => {}
+        }
+    }
+
+    // files dropped onto the window, e.g. the .sdkmanrc of a project
+    pub fn handle_dropped_files(&mut self, ctx: &CtxRef) {
+        let dropped: Vec<PathBuf> = ctx
+            .input()
+            .raw
+            .dropped_files
+            .iter()
+            .filter_map(|file| file.path.clone())
+            .collect();
+        for path in dropped {
+            // a project directory stands for its .sdkmanrc
+            let path = if path.is_dir() {
+                path.join(SDKMANRC)
+            } else {
+                path
+            };
+            if local_install::is_archive(&path) && self.read_only {
+                self.error_message = Some(READ_ONLY_MESSAGE.to_string());
+            } else if local_install::is_archive(&path) {
+                match &self.selected_candidate {
+                    Some(candidate) => {
+                        self.local_install = Some(LocalInstall::new(
+                            candidate.binary_name(),
+                            path,
+                            &self.repaint,
+                        ))
+                    }
+                    None => {
+                        self.error_message = Some(format!(
+                            "Open the candidate to install {} for first",
+                            path.display()
+                        ))
+                    }
+                }
+            } else if path.file_name() == Some(OsStr::new(SDKMANRC)) {
+                match Environment::load(&path, &self.local_candidates) {
+                    Ok(environment) => self.environment = Some(environment),
+                    Err(e) => {
+                        self.error_message =
+                            Some(format!("Reading {} failed with:\n{}", path.display(), e))
+                    }
+                }
+            } else {
+                self.error_message = Some(format!(
+                    "Only {} files and SDK archives can be dropped here, not {}",
+                    SDKMANRC,
+                    path.display()
+                ));
+            }
+        }
+    }
+
+    pub fn render_environment(&mut self, ctx: &CtxRef) {
+        let outcome = match self.environment.as_mut() {
+            Some(environment) => environment.render(ctx, &self.local_candidates, &self.jobs),
+            None => return,
+        };
+        match outcome {
+            EnvironmentOutcome::Open => {}
+            // stays open, so the versions turn green as they get installed
+            EnvironmentOutcome::Install(_) if self.read_only => {
+                self.error_message = Some(READ_ONLY_MESSAGE.to_string());
+            }
+            EnvironmentOutcome::Install(steps) => {
+                self.jobs
+                    .submit(jobs::describe(&steps), steps, &self.repaint);
+            }
+            EnvironmentOutcome::Close => self.environment = None,
+        }
+    }
+
+    pub fn render_local_install(&mut self, ctx: &CtxRef) {
+        let decision = match self.local_install.as_mut() {
+            Some(local_install) => local_install.render(ctx, &self.local_candidates),
+            None => return,
+        };
+        match decision {
+            None => {}
+            Some(Ok(step)) => {
+                let steps = vec![step];
+                self.jobs
+                    .submit(jobs::describe(&steps), steps, &self.repaint);
+                self.local_install = None;
+            }
+            Some(Err(())) => self.local_install = None,
+        }
+    }
+
+    pub fn render_remove_all(&mut self, ctx: &CtxRef) {
+        let decision = match self.remove_all.as_mut() {
+            Some(remove_all) => remove_all.render(ctx),
+            None => return,
+        };
+        match decision {
+            None => {}
+            Some(Ok(steps)) => {
+                if let Some(first) = steps.first() {
+                    let title = format!("Remove {} versions of {}", steps.len(), first.binary_name);
+                    let usages = SdkmanApp::delete_usages(&self.config.projects, &steps);
+                    if usages.is_empty() {
+                        self.stage_or_submit(title, steps);
+                    } else {
+                        self.delete_warning = Some((title, steps, usages));
+                    }
+                }
+                self.remove_all = None;
+            }
+            Some(Err(())) => self.remove_all = None,
+        }
+    }
+
+    // the deletes among the steps of versions that registered projects still ask for
+    fn delete_usages(projects: &[PathBuf], steps: &[Step]) -> Vec<Usage> {
+        steps
+            .iter()
+            .filter(|step| step.action == Action::Delete)
+            .map(|step| {
+                let usages = projects::find_usages(projects, &step.binary_name, &step.version);
+                (step.clone(), usages)
+            })
+            .filter(|(_, usages)| !usages.is_empty())
+            .collect()
+    }
+
+    fn stage_or_submit(&mut self, title: String, steps: Vec<Step>) {
+        if self.stage_changes {
+            for step in steps {
+                self.transaction.stage(step);
+            }
+        } else {
+            self.jobs.submit(title, steps, &self.repaint);
+        }
+    }
+
+    // deleting a version that registered projects still ask for needs a second thought
+    pub fn render_delete_warning(&mut self, ctx: &CtxRef) {
+        let usages = match &self.delete_warning {
+            Some((_, _, usages)) => usages,
+            None => return,
+        };
+        let mut confirmed = None;
+        Window::new("Version in use")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0., 0.])
+            .show(ctx, |ui| {
+                for (step, files) in usages {
+                    ui.add_space(PADDING);
+                    ui.label(format!(
+                        "{} {} is still used by:",
+                        step.binary_name, step.version
+                    ));
+                    for file in files {
+                        ui.monospace(file.display().to_string());
+                    }
+                }
+                ui.add_space(PADDING);
+                ui.horizontal(|ui| {
+                    if ui.button("Delete anyway").clicked() {
+                        confirmed = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        confirmed = Some(false);
+                    }
+                });
+            });
+        if let Some(confirmed) = confirmed {
+            if let Some((title, steps, _)) = self.delete_warning.take().filter(|_| confirmed) {
+                self.stage_or_submit(title, steps);
+            }
+        }
+    }
+
+    pub fn render_switch_preview(&mut self, ctx: &CtxRef) {
+        let outcome = match &self.switch_preview {
+            Some(preview) => preview.render(ctx),
+            None => return,
+        };
+        match outcome {
+            SwitchPreviewOutcome::Open => {}
+            SwitchPreviewOutcome::Switch => {
+                if let Some(preview) = self.switch_preview.take() {
+                    let step = preview.step;
+                    if self.stage_changes {
+                        self.transaction.stage(step);
+                    } else {
+                        self.jobs
+                            .submit(step.to_string(), vec![step], &self.repaint);
+                    }
+                }
+            }
+            SwitchPreviewOutcome::Cancel => self.switch_preview = None,
+        }
+    }
+
+    pub fn render_onboarding(&mut self, ctx: &CtxRef) {
+        let candidates: Vec<(String, String)> = self
+            .candidates
+            .iter()
+            .map(|candidate| {
+                (
+                    candidate.name().clone(),
+                    candidate.binary_name().to_string(),
+                )
+            })
+            .collect();
+        let finished = self.onboarding.as_mut().and_then(|onboarding| {
+            onboarding.render(ctx, &candidates, self.local_candidates.len())
+        });
+        if let Some(config) = finished {
+            if let Err(e) = config.save() {
+                self.error_message = Some(format!("Saving the configuration failed with:\n{}", e));
+            }
+            config.theme.apply(ctx);
+            SdkmanApp::pin_first(&mut self.candidates, &config);
+            self.background_refresh =
+                SdkmanApp::start_background_refresh(&config, &self.state, &self.repaint);
+            self.config = config;
+            self.onboarding = None;
+        }
+    }
+
+    pub fn render_settings(&mut self, ctx: &CtxRef) {
+        let outcome = match self.settings.as_mut() {
+            Some(settings) => settings.render(ctx),
+            None => return,
+        };
+        match outcome {
+            SettingsOutcome::Open => {}
+            SettingsOutcome::PreviewAnalytics(url) => {
+                self.analytics_preview = Some(url);
+            }
+            SettingsOutcome::ExportDiagnostics => {
+                match diagnostics::export(&self.config, &self.local_candidates) {
+                    Ok(archive) => {
+                        log!("Exported the diagnostics to {}", archive.display());
+                        if let Some(dir) = archive.parent() {
+                            if let Err(e) = util::open_path(dir) {
+                                log!("Failed to open {}: {}", dir.display(), e);
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        self.error_message =
+                            Some(format!("Exporting the diagnostics failed with:\n{}", e))
+                    }
+                }
+            }
+            SettingsOutcome::Saved(config) => {
+                if let Err(e) = config.save() {
+                    self.error_message =
+                        Some(format!("Saving the configuration failed with:\n{}", e));
+                }
+                self.apply_config(*config);
+                self.settings = None;
+            }
+            SettingsOutcome::Cancelled => {
+                self.config.theme.apply(ctx);
+                self.settings = None;
+            }
+        }
+        if let Some(url) = &self.analytics_preview {
+            if !analytics::render_preview(ctx, &mut self.usage_counts, url, &mut self.error_message)
+            {
+                self.analytics_preview = None;
+            }
+        }
+    }
+
+    // takes over a configuration without a restart, from the settings or the file itself
+    fn apply_config(&mut self, config: Config) {
+        config.configure_cache();
+        config.configure_scan();
+        config.configure_downloads();
+        self.jobs.set_verify_installs(config.verify_installs);
+        config.configure_api();
+        // the channels have catalogs of their own
+        if config.channel != self.config.channel {
+            self.candidates.clear();
+            self.selected_candidate = None;
+            self.state.reload_remote(&self.repaint);
+        }
+        // the versions shown are the ones of the old platform
+        if config.platform_override != self.config.platform_override {
+            self.selected_candidate = None;
+        }
+        if config.categories_url != self.config.categories_url {
+            SdkmanApp::load_categories(&config, &self.repaint);
+        }
+        SdkmanApp::pin_first(&mut self.candidates, &config);
+        if config.background_refresh != self.config.background_refresh
+            || config.refresh_interval_minutes != self.config.refresh_interval_minutes
+        {
+            self.background_refresh =
+                SdkmanApp::start_background_refresh(&config, &self.state, &self.repaint);
+        }
+        if config.subscriptions != self.config.subscriptions {
+            self.state.set_subscriptions(config.subscriptions.clone());
+        }
+        self.config = config;
+    }
+
+    // an edit of the config file, e.g. by a dotfile manager, applies while the app is running
+    pub fn receive_config(&mut self, ctx: &CtxRef) {
+        let config = match self.config_watch.as_ref().and_then(ConfigWatch::poll) {
+            Some(config) => config,
+            None => return,
+        };
+        // the app's own saves come back here too
+        if config == self.config {
+            return;
+        }
+        log!("Applying the changed configuration");
+        // another installation is a switch of its own
+        if config.sdkman_dir != self.config.sdkman_dir {
+            config.configure_installation();
+            self.selected_candidate = None;
+            self.check_permissions();
+            self.reload_local_candidates();
+        }
+        config.theme.apply(ctx);
+        self.apply_config(config);
+    }
+
+    // installs ask first while the SDKMAN volume is nearly full
+    pub fn receive_disk_space(&mut self) {
+        let free = match self.disk_watch.as_ref().and_then(DiskWatch::poll) {
+            Some(free) => free,
+            None => return,
+        };
+        let threshold = self.config.low_disk_space_mb.saturating_mul(1024 * 1024);
+        let low = Some(free).filter(|free| *free < threshold);
+        if low.is_some() != self.jobs.low_disk_space().is_some() {
+            match low {
+                Some(free) => log!("Low disk space: {} free", util::format_size(free)),
+                None => log!("Enough disk space again"),
+            }
+        }
+        self.jobs.set_low_disk_space(low, &self.repaint);
+    }
+
+    pub fn begin_frame(&mut self, ctx: &CtxRef) {
+        self.perf.begin_frame(ctx);
+        self.run_scheduled_retry();
+    }
+
+    // a failed fetch is repeated once the time named by the API's Retry-After is up
+    fn run_scheduled_retry(&mut self) {
+        let until = match rate_limited_until() {
+            Some(until) => until,
+            None => {
+                self.retry_ticker = None;
+                match self.scheduled_retry.take() {
+                    Some(Retry::Catalog) => self.state.reload_remote(&self.repaint),
+                    Some(Retry::Versions(binary_name)) => self.open_candidate(&binary_name),
+                    None => {}
+                }
+                return;
+            }
+        };
+        // the countdown in the footer needs a repaint every second
+        if self.scheduled_retry.is_some() && self.retry_ticker != Some(until) {
+            self.retry_ticker = Some(until);
+            let repaint = self.repaint.clone();
+            tasks::spawn(move || {
+                while SystemTime::now() < until {
+                    thread::sleep(Duration::from_secs(1));
+                    repaint.notify();
+                }
+            });
+        }
+    }
+
+    // true if the error only asks to wait, in which case the fetch is repeated later
+    fn schedule_retry(
+        e: &SdkmanApiError,
+        retry: Retry,
+        scheduled_retry: &mut Option<Retry>,
+    ) -> bool {
+        if matches!(e.root(), SdkmanApiError::RateLimited(_)) {
+            *scheduled_retry = Some(retry);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn end_frame(&mut self, ctx: &CtxRef, frame: &eframe::epi::Frame<'_>) {
+        self.perf.end_frame();
+        self.perf.render(ctx, frame.info().cpu_usage);
+        self.save_ui_state();
+    }
+
+    pub fn render_quit_prompt(&mut self, ctx: &CtxRef, frame: &mut eframe::epi::Frame<'_>) {
+        match self.quit {
+            Some(Quit::Asking) => {}
+            Some(Quit::WhenDone) if !self.jobs.is_busy() => {
+                self.quit = Some(Quit::Decided);
+                frame.quit();
+                return;
+            }
+            _ => return,
+        }
+        Window::new("Quit sdkman-ui?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0., 0.])
+            .show(ctx, |ui| {
+                ui.add_space(PADDING);
+                ui.label("Jobs are still running. They can be finished first, cancelled, or continued in the background after the window is closed.");
+                ui.label("Cancelled jobs can be resumed or cleaned up on the next start.");
+                ui.add_space(PADDING);
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Wait")
+                        .on_hover_text("Quit once the jobs are done")
+                        .clicked()
+                    {
+                        self.quit = Some(Quit::WhenDone);
+                    }
+                    if ui.button("Cancel jobs").clicked() {
+                        self.quit = Some(Quit::Decided);
+                        frame.quit();
+                    }
+                    // root-owned installations need a password for every step, nobody is there to enter it
+                    if ui
+                        .add_enabled(
+                            !self.jobs.is_elevated(),
+                            Button::new("Continue in background"),
+                        )
+                        .clicked()
+                    {
+                        self.quit = Some(Quit::Decided);
+                        self.resume_in_background = true;
+                        frame.quit();
+                    }
+                    if ui.button("Don't quit").clicked() {
+                        self.quit = None;
+                    }
+                });
+            });
+    }
+
+    // stops the background work before the process exits: running downloads are cancelled,
+    // keeping what they got, and their jobs stay in the journal to be resumed on the next start
+    pub fn shutdown(&mut self) {
+        // eframe can't keep the window manager from closing the window, so its close button
+        // can't ask; the running jobs go on in the background rather than being cancelled, unless
+        // they need a password that nobody is there to enter
+        if self.quit != Some(Quit::Decided) && self.jobs.is_busy() && !self.jobs.is_elevated() {
+            log!("Closed by the window manager, continuing the jobs in the background");
+            self.resume_in_background = true;
+        }
+        self.background_refresh = None;
+        self.config_watch = None;
+        self.disk_watch = None;
+        jobs::shut_down();
+        download::cancel_all();
+        self.save_ui_state();
+        self.queue_pending_steps();
+        let stopped = tasks::wait(SHUTDOWN_TIMEOUT);
+        if !stopped {
+            log!(
+                "Quitting with {} background tasks still running",
+                tasks::pending()
+            );
+        }
+        // the cancelled jobs are left in the journal for a process without a window, but only
+        // once this one is done writing it; otherwise they're offered again on the next start
+        if self.resume_in_background && !stopped {
+            log!(
+                "Leaving the jobs for the next start instead of continuing them in the background"
+            );
+        } else if self.resume_in_background {
+            // the failed and cancelled jobs hold on to their journals, which keeps the other
+            // process from taking them over
+            self.jobs = Jobs::default();
+            self.interrupted_jobs.clear();
+            let resumed = env::current_exe().and_then(|exe| {
+                Command::new(exe)
+                    .arg(RESUME_JOBS_FLAG)
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .spawn()
+            });
+            if let Err(e) = resumed {
+                log!("Failed to continue the jobs in the background: {}", e);
+            }
+        }
+    }
+
+    // staged changes and the ones waiting for a password are offered again on the next start
+    fn queue_pending_steps(&mut self) {
+        let pending = [self.transaction.take(), self.jobs.take_awaiting()];
+        for steps in pending.into_iter().filter(|steps| !steps.is_empty()) {
+            match Journal::queue(steps) {
+                Ok(()) => log!("Queued the pending changes for the next start"),
+                Err(e) => log!("Failed to queue the pending changes: {}", e),
+            }
+        }
+    }
+
+    // a fixed view for the screenshots, independent of what the user left open
+    pub fn show_view(&mut self, tab: Tab, binary_name: Option<&str>) {
+        self.headless = true;
+        self.crash_report = None;
+        self.interrupted_jobs.clear();
+        self.selected_candidate = None;
+        self.detail_tab = DetailTab::Versions;
+        self.tab = tab;
+        if let Some(binary_name) = binary_name {
+            self.open_candidate(binary_name);
+        }
+    }
+
+    // written whenever the view changes, so even a crash doesn't lose it
+    fn save_ui_state(&mut self) {
+        if self.headless {
+            return;
+        }
+        let selected_candidate = match &self.selected_candidate {
+            Some(candidate) => Some(candidate.binary_name().to_string()),
+            // the candidate being restored stays saved until its versions are there
+            None if self.restoring.is_some() => self.saved_state.selected_candidate.clone(),
+            None => None,
+        };
+        let state = UiState {
+            selected_candidate,
+            detail_tab: self.detail_tab,
+            version_sort: self.version_sort,
+            vendor_filter: self.vendor_filter.clone(),
+        };
+        if state != self.saved_state {
+            if let Err(e) = state.save() {
+                log!("Failed to save the UI state: {}", e);
+            }
+            self.saved_state = state;
+        }
+    }
+
+    pub fn render_weekly_summary(&mut self, ctx: &CtxRef) {
+        if let Some(binary_name) = self.weekly_summary.render(ctx) {
+            self.open_candidate(&binary_name);
+        }
+    }
+
+    pub fn render_homepage(&mut self, ctx: &CtxRef) {
+        if let Some(panel) = &mut self.homepage {
+            if !panel.render(ctx) {
+                self.homepage = None;
+            }
+        }
+    }
+
+    pub fn render_snapshots(&mut self, ctx: &CtxRef) {
+        let outcome = match self.snapshots.as_mut() {
+            Some(window) => window.render(ctx, &self.local_candidates),
+            None => return,
+        };
+        match outcome {
+            SnapshotsOutcome::Open => {}
+            SnapshotsOutcome::Restore(..) if self.read_only => {
+                self.error_message = Some(READ_ONLY_MESSAGE.to_string());
+            }
+            SnapshotsOutcome::Restore(name, steps) => {
+                // a single job, so a failure can be rolled back as a whole
+                let title = format!("Restore the snapshot {}", name);
+                self.stage_or_submit(title, steps);
+                self.snapshots = None;
+            }
+            SnapshotsOutcome::Close => self.snapshots = None,
+        }
+    }
+
+    pub fn render_download_preview(&mut self, ctx: &CtxRef) {
+        let outcome = match self.download_preview.as_mut() {
+            Some(window) => window.render(ctx, &self.repaint),
+            None => return,
+        };
+        match outcome {
+            DownloadPreviewOutcome::Open => {}
+            DownloadPreviewOutcome::Install if self.read_only => {
+                self.error_message = Some(READ_ONLY_MESSAGE.to_string());
+            }
+            DownloadPreviewOutcome::Install => {
+                if let Some(window) = self.download_preview.take() {
+                    let step = Step::new(&window.binary_name, &window.version, Action::Install);
+                    if self.stage_changes {
+                        self.transaction.stage(step);
+                    } else {
+                        self.jobs
+                            .submit(step.to_string(), vec![step], &self.repaint);
+                    }
+                }
+            }
+            DownloadPreviewOutcome::Close => self.download_preview = None,
+        }
+    }
+
+    pub fn render_run(&mut self, ctx: &CtxRef) {
+        let outcome = match self.run_window.as_mut() {
+            Some(window) => window.render(ctx, &self.repaint),
+            None => return,
+        };
+        match outcome {
+            RunOutcome::Open => {}
+            RunOutcome::Terminal => {
+                if let Some(window) = &self.run_window {
+                    let requirements = [(window.binary_name.clone(), window.version.clone())];
+                    if let Err(e) = projects::launch_in_terminal(
+                        None,
+                        &requirements,
+                        &self.config.terminal_command,
+                        &self.local_candidates,
+                    ) {
+                        self.error_message =
+                            Some(format!("Launching the terminal failed with:\n{}", e));
+                    }
+                }
+            }
+            RunOutcome::Close => self.run_window = None,
+        }
+    }
+
+    pub fn render_time_machine(&mut self, ctx: &CtxRef) {
+        let outcome = match &self.time_machine {
+            Some(time_machine) => time_machine.render(ctx),
+            None => return,
+        };
+        match outcome {
+            TimeMachineOutcome::Open => {}
+            TimeMachineOutcome::Revert(..) if self.read_only => {
+                self.error_message = Some(READ_ONLY_MESSAGE.to_string());
+            }
+            TimeMachineOutcome::Revert(time, steps) => {
+                let title = format!("Revert to state as of {}", util::format_date(time));
+                self.stage_or_submit(title, steps);
+                self.time_machine = None;
+            }
+            TimeMachineOutcome::Close => self.time_machine = None,
+        }
+    }
+
+    // the whole catalog with what's installed of it, e.g. for a team wiki or an audit
+    pub fn render_export_catalog(&mut self, ctx: &CtxRef) {
+        if !self.export_catalog {
+            return;
+        }
+        let mut format = None;
+        let mut open = true;
+        let catalog = self.state.remote();
+        Window::new("Export the catalog")
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label("Name, binary, default version, homepage and installed versions");
+                ui.label("of every candidate, copied to the clipboard.");
+                ui.add_space(PADDING);
+                ui.horizontal(|ui| {
+                    let loaded = catalog.is_some();
+                    if ui
+                        .add_enabled(loaded, Button::new("Copy as CSV"))
+                        .on_disabled_hover_text("The catalog is still loading")
+                        .clicked()
+                    {
+                        format = Some(CatalogFormat::Csv);
+                    }
+                    if ui
+                        .add_enabled(loaded, Button::new("Copy as Markdown"))
+                        .on_hover_text("A table for a wiki or a README")
+                        .on_disabled_hover_text("The catalog is still loading")
+                        .clicked()
+                    {
+                        format = Some(CatalogFormat::Markdown);
+                    }
+                });
+            });
+        // the candidate list may be filtered, the catalog it was loaded from isn't
+        if let (Some(format), Some(catalog)) = (format, catalog) {
+            let remote_candidates: Vec<RemoteCandidate> = catalog
+                .iter()
+                .map(|candidate| candidate.as_ref().clone())
+                .collect();
+            ctx.output().copied_text = format.render(&remote_candidates, &self.local_candidates);
+            log!(
+                "Copied the catalog of {} candidates",
+                remote_candidates.len()
+            );
+            open = false;
+        }
+        self.export_catalog = open;
+    }
+
+    pub fn render_about(&mut self, ctx: &CtxRef) {
+        if self.about && !about::render_dialog(ctx) {
+            self.about = false;
+        }
+    }
+
+    pub fn render_crash_report(&mut self, ctx: &CtxRef) {
+        if let Some(report_dir) = &self.crash_report {
+            if !crash::render_dialog(ctx, report_dir) {
+                self.crash_report = None;
+            }
+        }
+    }
+
+    pub fn render_interrupted_jobs(&mut self, ctx: &CtxRef) {
+        let recovery = match self.interrupted_jobs.first() {
+            Some(journal) => journal::render_recovery_dialog(ctx, journal),
+            None => return,
+        };
+        if let Some(recovery) = recovery {
+            let journal = self.interrupted_jobs.remove(0);
+            match recovery {
+                Recovery::CleanUp => self.jobs.clean_up(journal, &self.repaint),
+                Recovery::Resume => self.jobs.resume(journal, &self.repaint),
+                Recovery::Restage => {
+                    for step in journal.steps() {
+                        self.transaction.stage(step.clone());
+                    }
+                    self.stage_changes = true;
+                    journal.discard();
+                }
+                Recovery::Discard => journal.discard(),
+                Recovery::Ignore => {}
+            }
+        }
+    }
+
+    pub fn render_tabs(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.tab, Tab::Candidates, "Candidates");
+            if ui
+                .selectable_value(&mut self.tab, Tab::Dashboard, "Dashboard")
+                .clicked()
+            {
+                self.dashboard.load(&self.local_candidates, &self.repaint);
+            }
+            if ui
+                .selectable_value(&mut self.tab, Tab::Projects, "Projects")
+                .clicked()
+            {
+                self.projects
+                    .load(&self.config.projects, &self.local_candidates);
+            }
+            if ui
+                .selectable_value(&mut self.tab, Tab::Doctor, "Doctor")
+                .clicked()
+            {
+                self.doctor.load(&self.local_candidates, &self.repaint);
+            }
+            let unread = self.inbox.unread();
+            let subscriptions = if unread > 0 {
+                format!("Subscriptions ({})", unread)
+            } else {
+                "Subscriptions".to_string()
+            };
+            ui.selectable_value(&mut self.tab, Tab::Subscriptions, subscriptions);
+        });
+        ui.add(Separator::default());
+    }
+
+    pub fn tab(&self) -> Tab {
+        self.tab
+    }
+
+    pub fn render_dashboard(&mut self, ctx: &CtxRef, ui: &mut Ui) {
+        if let Some(err) = &self.error_message {
+            SdkmanApp::render_error(ctx, err);
+        }
+        if let Some(binary_name) = self.dashboard.render(
+            ui,
+            &self.candidates,
+            &self.local_candidates,
+            &self.protected,
+            &self.repaint,
+        ) {
+            self.open_candidate(&binary_name);
+        }
+    }
+
+    pub fn render_doctor(&mut self, ctx: &CtxRef, ui: &mut Ui) {
+        if let Some(err) = &self.error_message {
+            SdkmanApp::render_error(ctx, err);
+        }
+        let outcome = self
+            .doctor
+            .render(ui, &self.local_candidates, &self.repaint);
+        match outcome {
+            None => {}
+            Some(DoctorOutcome::Rename { .. }) if self.read_only => {
+                self.error_message = Some(READ_ONLY_MESSAGE.to_string());
+            }
+            Some(DoctorOutcome::Rename {
+                binary_name,
+                version,
+                to,
+            }) => {
+                match Sdkman::new().rename(&binary_name, &version, &to) {
+                    Ok(()) => log!("renamed {} {} to {}", binary_name, version, to),
+                    Err(e) => {
+                        self.error_message =
+                            Some(format!("Renaming the version failed with:\n{}", e))
+                    }
+                }
+                self.state
+                    .reload_local_candidates(vec![binary_name], &self.repaint);
+            }
+        }
+    }
+
+    pub fn render_projects(&mut self, ctx: &CtxRef, ui: &mut Ui) {
+        if let Some(err) = &self.error_message {
+            SdkmanApp::render_error(ctx, err);
+        }
+        let outcome = self
+            .projects
+            .render(ui, &self.config.projects, &self.local_candidates);
+        match outcome {
+            None => {}
+            Some(ProjectsOutcome::Add(project)) => {
+                self.config.projects.push(project);
+                if let Err(e) = self.config.save() {
+                    self.error_message = Some(format!("Saving the project failed with:\n{}", e));
+                }
+            }
+            Some(ProjectsOutcome::Remove(project)) => {
+                self.config.projects.retain(|p| *p != project);
+                if let Err(e) = self.config.save() {
+                    self.error_message = Some(format!("Removing the project failed with:\n{}", e));
+                }
+            }
+            Some(ProjectsOutcome::Terminal(project, requirements)) => {
+                if let Err(e) = projects::launch_terminal(
+                    &project,
+                    &requirements,
+                    &self.config.terminal_command,
+                    &self.local_candidates,
+                ) {
+                    self.error_message =
+                        Some(format!("Launching the terminal failed with:\n{}", e));
+                }
+            }
+            Some(ProjectsOutcome::Sync(_)) if self.read_only => {
+                self.error_message = Some(READ_ONLY_MESSAGE.to_string());
+            }
+            Some(ProjectsOutcome::Sync(steps)) => {
+                self.jobs
+                    .submit(jobs::describe(&steps), steps, &self.repaint);
+            }
+        }
+    }
+
+    pub fn render_subscriptions(&mut self, _ctx: &CtxRef, ui: &mut Ui) {
+        let action = match self.inbox.render(ui, &self.config.subscriptions) {
+            Some(action) => action,
+            None => return,
+        };
+        match action {
+            InboxAction::Open(binary_name) => self.open_candidate(&binary_name),
+            InboxAction::Read(index) => self.mark_read(Some(index)),
+            InboxAction::ReadAll => self.mark_read(None),
+            InboxAction::CheckNow => self.check_subscriptions(),
+        }
+    }
+
+    fn mark_read(&mut self, index: Option<usize>) {
+        match Inbox::update(|inbox| inbox.mark_read(index)) {
+            Ok(inbox) => self.inbox = inbox,
+            Err(e) => self.error_message = Some(format!("Marking as read failed with:\n{}", e)),
+        }
+    }
+
+    fn check_subscriptions(&self) {
+        subscriptions::check_in_background(
+            self.candidates
+                .iter()
+                .filter(|c| !c.archived)
+                .map(|c| c.model.clone())
+                .collect(),
+            self.config.subscriptions.clone(),
+            &self.state,
+            &self.repaint,
+        );
+    }
+
+    // the versions that haven't been used for a while are at the bottom of the dashboard
+    fn open_cleanup(&mut self) {
+        self.tab = Tab::Dashboard;
+        self.dashboard.reload(&self.local_candidates, &self.repaint);
+    }
+
+    fn open_candidate(&mut self, binary_name: &str) {
+        let candidate = match self
+            .candidates
+            .iter()
+            .find(|candidate| candidate.binary_name() == binary_name)
+        {
+            Some(candidate) => candidate,
+            None => {
+                self.error_message = Some(format!("Unknown candidate '{}'", binary_name));
+                return;
+            }
+        };
+        match candidate.load_versions() {
+            Ok(candidate_with_versions) => {
+                self.selected_candidate = Some(candidate_with_versions);
+                self.tab = Tab::Candidates;
+            }
+            Err(e) => {
+                self.selected_candidate = None;
+                if !SdkmanApp::schedule_retry(
+                    &e,
+                    Retry::Versions(binary_name.to_string()),
+                    &mut self.scheduled_retry,
+                ) {
+                    self.error_message = Some(format!(
+                        "Fetching available candidate versions failed with:\n{}",
+                        e
+                    ));
+                }
+            }
+        }
+    }
+
     pub fn configure_fonts(&self, ctx: &CtxRef) {
         let mut font_def = FontDefinitions::default();
         font_def.font_data.insert(
@@ -140,8 +1934,74 @@ impl SdkmanApp {
             selected_candidate,
             candidate_search_dialog,
             candidate_search_term: _,
-            error_message,
+            error_message: _,
+            config,
+            onboarding: _,
+            background_refresh: _,
+            tab: _,
+            dashboard: _,
+            projects: _,
+            detail_tab: _,
+            version_files: _,
+            history,
+            settings,
+            usage_counts: _,
+            analytics_preview: _,
+            crash_report: _,
+            version_sort: _,
+            release_dates: _,
+            version_sizes: _,
+            sdk_homes: _,
+            repaint,
+            perf: _,
+            expanded_descriptions: _,
+            catalog_error: _,
+            installed_only,
+            jobs,
+            quick_install: _,
+            transaction,
+            stage_changes: _,
+            interrupted_jobs: _,
+            arch_mismatches: _,
+            active_filter,
+            filter_editor,
+            comparison: _,
+            vendor_filter: _,
+            saved_state: _,
+            environment: _,
+            local_install: _,
+            delete_warning: _,
+            headless: _,
+            installations,
+            read_only: _,
+            doctor: _,
+            scheduled_retry: _,
+            retry_ticker: _,
+            state,
+            state_version: _,
+            quit,
+            resume_in_background: _,
+            homepage: _,
+            inbox: _,
+            weekly_summary: _,
+            about,
+            remove_all: _,
+            config_watch: _,
+            protected: _,
+            snapshots,
+            profile_editor,
+            time_machine,
+            download_preview: _,
+            scan_on_start: _,
+            summary_pending: _,
+            run_window: _,
+            switch_preview: _,
+            disk_watch: _,
+            export_catalog,
+            restoring: _,
         } = self;
+        let mut switch_to = None;
+        let mut switch_profile = None;
         // define a TopBottomPanel widget
         TopBottomPanel::top("top_panel").show(ctx, |ui| {
             ui.add_space(10.);
@@ -165,45 +2025,87 @@ impl SdkmanApp {
                             new_tab: modifiers.any(),
                         });
                     }
-                });
-                // Candidates
-                ui.vertical_centered(|ui| {
-                    ui.heading(*app_heading);
-                });
-                // controls
-                ui.with_layout(Layout::right_to_left(), |ui| {
-                    ui.add_space(10.);
-                    // Close button
+                });
+                // Candidates
+                ui.vertical_centered(|ui| {
+                    ui.heading(*app_heading);
+                });
+                // controls
+                ui.with_layout(Layout::right_to_left(), |ui| {
+                    ui.add_space(10.);
+                    // Close button
+                    if ui
+                        .add(Button::new("❌").text_style(TextStyle::Body))
+                        .on_hover_text("Close")
+                        .clicked()
+                    {
+                        if jobs.is_busy() {
+                            *quit = Some(Quit::Asking);
+                        } else {
+                            frame.quit();
+                        }
+                    }
+                    // Refresh button
+                    if ui
+                        .add(Button::new("🔄").text_style(TextStyle::Body))
+                        .on_hover_text("Refresh the catalog and rescan the installed versions")
+                        .clicked()
+                    {
+                        state.reload_local(repaint);
+                        state.reload_remote(repaint);
+                        *selected_candidate = None;
+                    }
+                    // Snapshots button
+                    if ui
+                        .add(Button::new("📸").text_style(TextStyle::Body))
+                        .on_hover_text("Snapshots of the current versions")
+                        .clicked()
+                    {
+                        *snapshots = Some(SnapshotsWindow::open());
+                    }
+                    // Time machine button
+                    if ui
+                        .add(Button::new("🕘").text_style(TextStyle::Body))
+                        .on_hover_text("When the current versions changed")
+                        .clicked()
+                    {
+                        *time_machine = Some(TimeMachine::new(history, local_candidates));
+                    }
+                    // Export button
+                    if ui
+                        .add(Button::new("📤").text_style(TextStyle::Body))
+                        .on_hover_text("Export the candidate catalog")
+                        .clicked()
+                    {
+                        *export_catalog = true;
+                    }
+                    // About button
                     if ui
-                        .add(Button::new("❌").text_style(TextStyle::Body))
-                        .on_hover_text("Close")
+                        .add(Button::new("ℹ").text_style(TextStyle::Body))
+                        .on_hover_text("About")
                         .clicked()
                     {
-                        frame.quit();
+                        *about = true;
                     }
-                    // Refresh button
+                    // Settings button
                     if ui
-                        .add(Button::new("🔄").text_style(TextStyle::Body))
-                        .on_hover_text("Refresh")
+                        .add(Button::new("⚙").text_style(TextStyle::Body))
+                        .on_hover_text("Settings")
                         .clicked()
                     {
-                        match fetch_remote_candidates() {
-                            Ok(models) => {
-                                let cands: Vec<Candidate> = models
-                                    .iter()
-                                    .map(|model| Candidate::from_model(model))
-                                    .collect();
-                                *candidates = cands;
-                                *selected_candidate = None;
-                            }
-                            Err(e) => {
-                                *selected_candidate = None;
-                                *error_message = Some(format!(
-                                    "Refreshing the list of candidates failed with:\n{}",
-                                    e
-                                ));
-                            }
-                        }
+                        *settings = Some(Settings::new(config));
+                    }
+                    // Pending changes button
+                    if !transaction.is_empty()
+                        && ui
+                            .add(
+                                Button::new(format!("📝 {}", transaction.len()))
+                                    .text_style(TextStyle::Body),
+                            )
+                            .on_hover_text("Review pending changes")
+                            .clicked()
+                    {
+                        transaction.review();
                     }
                     // Search button
                     if ui
@@ -236,11 +2138,230 @@ impl SdkmanApp {
                             .cloned()
                             .collect();
                         *selected_candidate = None;
+                        *installed_only = true;
+                    }
+                    // Smart filters
+                    if ui
+                        .add(Button::new("✎").text_style(TextStyle::Body))
+                        .on_hover_text("Edit smart filter")
+                        .clicked()
+                    {
+                        *filter_editor = Some(active_filter.clone().unwrap_or_default());
+                    }
+                    ComboBox::from_id_source("smart_filter")
+                        .selected_text(
+                            active_filter
+                                .as_ref()
+                                .map_or("All candidates", |filter| filter.name.as_str()),
+                        )
+                        .show_ui(ui, |ui| {
+                            if ui
+                                .selectable_label(active_filter.is_none(), "All candidates")
+                                .clicked()
+                            {
+                                *active_filter = None;
+                            }
+                            for filter in &config.smart_filters {
+                                if ui
+                                    .selectable_label(
+                                        active_filter.as_ref() == Some(filter),
+                                        &filter.name,
+                                    )
+                                    .clicked()
+                                {
+                                    *active_filter = Some(filter.clone());
+                                    *selected_candidate = None;
+                                }
+                            }
+                        });
+                    // Profiles
+                    if ui
+                        .add(Button::new("👤").text_style(TextStyle::Body))
+                        .on_hover_text("Edit the profile")
+                        .clicked()
+                    {
+                        let active = config
+                            .active_profile
+                            .as_ref()
+                            .and_then(|name| config.profiles.iter().find(|p| p.name == *name));
+                        let draft = active.cloned().unwrap_or_else(|| Profile {
+                            pinned_candidates: config.pinned_candidates.clone(),
+                            filter: active_filter.as_ref().map(|filter| filter.name.clone()),
+                            ..Default::default()
+                        });
+                        *profile_editor = Some(ProfileEditor::new(draft));
+                    }
+                    if !config.profiles.is_empty() {
+                        ComboBox::from_id_source("profile")
+                            .selected_text(config.active_profile.as_deref().unwrap_or("No profile"))
+                            .show_ui(ui, |ui| {
+                                if ui
+                                    .selectable_label(config.active_profile.is_none(), "No profile")
+                                    .clicked()
+                                {
+                                    switch_profile = Some(None);
+                                }
+                                for profile in &config.profiles {
+                                    let active =
+                                        config.active_profile.as_ref() == Some(&profile.name);
+                                    if ui.selectable_label(active, &profile.name).clicked()
+                                        && !active
+                                    {
+                                        switch_profile = Some(Some(profile.name.clone()));
+                                    }
+                                }
+                            })
+                            .response
+                            .on_hover_text("Pins, current versions and filter at once");
+                    }
+                    // Installation switcher
+                    if installations.len() > 1 {
+                        let current = config
+                            .sdkman_dir
+                            .as_ref()
+                            .or_else(|| installations.first().map(|dirs| &dirs.root));
+                        ui.add_enabled_ui(!jobs.is_busy(), |ui| {
+                            ComboBox::from_id_source("installation")
+                                .selected_text(
+                                    current
+                                        .map_or(String::new(), |root| root.display().to_string()),
+                                )
+                                .show_ui(ui, |ui| {
+                                    for dirs in installations.iter() {
+                                        if ui
+                                            .selectable_label(
+                                                current == Some(&dirs.root),
+                                                dirs.root.display().to_string(),
+                                            )
+                                            .clicked()
+                                            && current != Some(&dirs.root)
+                                        {
+                                            switch_to = Some(dirs.root.clone());
+                                        }
+                                    }
+                                });
+                        })
+                        .response
+                        .on_hover_text("The SDKMAN installation to manage");
                     }
                 });
             });
             ui.add_space(10.);
         });
+        if let Some(root) = switch_to {
+            self.switch_installation(root);
+        }
+        if let Some(name) = switch_profile {
+            self.switch_profile(name);
+        }
+    }
+
+    // pins, filters and restores the current versions the profile names; none just forgets it
+    fn switch_profile(&mut self, name: Option<String>) {
+        let profile = match name
+            .as_ref()
+            .and_then(|name| self.config.profiles.iter().find(|p| p.name == *name))
+        {
+            Some(profile) => profile.clone(),
+            None => {
+                self.config.active_profile = None;
+                self.save_profiles();
+                return;
+            }
+        };
+        if let Some(snapshot) = &profile.snapshot {
+            let versions = match Snapshots::load().get(snapshot) {
+                Some(versions) => versions.clone(),
+                None => {
+                    self.error_message = Some(format!(
+                        "The snapshot {} of the profile {} doesn't exist anymore",
+                        snapshot, profile.name
+                    ));
+                    return;
+                }
+            };
+            match snapshots::restore_steps(&versions, &self.local_candidates) {
+                Ok(steps) if steps.is_empty() => {}
+                Ok(_) if self.read_only => {
+                    self.error_message = Some(READ_ONLY_MESSAGE.to_string());
+                    return;
+                }
+                Ok(steps) => {
+                    let title = format!("Switch to the profile {}", profile.name);
+                    self.stage_or_submit(title, steps);
+                }
+                Err(missing) => {
+                    self.error_message = Some(format!(
+                        "Switching to the profile {} failed, these versions aren't installed:\n{}",
+                        profile.name,
+                        missing.join("\n")
+                    ));
+                    return;
+                }
+            }
+        }
+        self.config.pinned_candidates = profile.pinned_candidates.clone();
+        SdkmanApp::pin_first(&mut self.candidates, &self.config);
+        self.active_filter = profile.filter.as_ref().and_then(|name| {
+            self.config
+                .smart_filters
+                .iter()
+                .find(|filter| filter.name == *name)
+                .cloned()
+        });
+        self.selected_candidate = None;
+        self.config.active_profile = Some(profile.name);
+        self.save_profiles();
+    }
+
+    fn save_profiles(&mut self) {
+        if let Err(e) = self.config.save() {
+            self.error_message = Some(format!("Saving the profiles failed with:\n{}", e));
+        }
+    }
+
+    pub fn render_profile_editor(&mut self, ctx: &CtxRef) {
+        let outcome = match self.profile_editor.as_mut() {
+            Some(editor) => {
+                let saved = self
+                    .config
+                    .profiles
+                    .iter()
+                    .any(|profile| profile.name == editor.name());
+                editor.render(
+                    ctx,
+                    saved,
+                    &self.config.pinned_candidates,
+                    &self.config.smart_filters,
+                )
+            }
+            None => return,
+        };
+        match outcome {
+            ProfileOutcome::Open => {}
+            ProfileOutcome::Save(profile) => {
+                match self
+                    .config
+                    .profiles
+                    .iter_mut()
+                    .find(|saved| saved.name == profile.name)
+                {
+                    Some(saved) => *saved = profile,
+                    None => self.config.profiles.push(profile),
+                }
+                self.save_profiles();
+                self.profile_editor = None;
+            }
+            ProfileOutcome::Delete(name) => {
+                self.config.profiles.retain(|profile| profile.name != name);
+                if self.config.active_profile.as_ref() == Some(&name) {
+                    self.config.active_profile = None;
+                }
+                self.save_profiles();
+                self.profile_editor = None;
+            }
+            ProfileOutcome::Cancel => self.profile_editor = None,
+        }
     }
 
     fn render_error(ctx: &CtxRef, message: &str) {
@@ -262,6 +2383,70 @@ impl SdkmanApp {
             candidate_search_dialog,
             candidate_search_term,
             error_message,
+            config,
+            onboarding: _,
+            background_refresh: _,
+            tab: _,
+            dashboard: _,
+            projects: _,
+            detail_tab,
+            version_files,
+            history,
+            settings: _,
+            usage_counts: _,
+            analytics_preview: _,
+            crash_report: _,
+            version_sort,
+            release_dates,
+            version_sizes,
+            sdk_homes,
+            repaint,
+            perf,
+            expanded_descriptions,
+            catalog_error,
+            installed_only,
+            jobs,
+            quick_install,
+            transaction,
+            stage_changes,
+            interrupted_jobs: _,
+            arch_mismatches,
+            active_filter,
+            filter_editor,
+            comparison,
+            vendor_filter,
+            saved_state: _,
+            environment: _,
+            local_install: _,
+            delete_warning,
+            headless: _,
+            installations: _,
+            read_only,
+            doctor: _,
+            scheduled_retry,
+            retry_ticker: _,
+            state,
+            state_version: _,
+            quit: _,
+            resume_in_background: _,
+            homepage,
+            inbox: _,
+            weekly_summary: _,
+            about: _,
+            remove_all,
+            config_watch: _,
+            protected,
+            snapshots: _,
+            profile_editor: _,
+            time_machine: _,
+            download_preview,
+            scan_on_start: _,
+            summary_pending: _,
+            run_window,
+            switch_preview,
+            disk_watch: _,
+            export_catalog: _,
+            restoring: _,
         } = self;
 
         if ui.input().key_pressed(Key::Escape) {
@@ -275,6 +2460,21 @@ impl SdkmanApp {
             SdkmanApp::render_error(ctx, err);
         }
 
+        let confirmed = quick_install.as_mut().and_then(|confirmation| {
+            SdkmanApp::render_quick_install(ctx, confirmation, local_candidates)
+        });
+        if let Some(confirmed) = confirmed {
+            if let Some(confirmation) = quick_install.take().filter(|_| confirmed) {
+                let steps = confirmation.steps();
+                let title = steps
+                    .iter()
+                    .map(|step| step.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                jobs.submit(title, steps, repaint);
+            }
+        }
+
         if *candidate_search_dialog {
             SdkmanApp::render_search_dialog(
                 ctx,
@@ -286,43 +2486,273 @@ impl SdkmanApp {
             );
         }
 
+        comparison.render(ctx, local_candidates);
+
+        let outcome = filter_editor.as_mut().map(|draft| {
+            let saved = config
+                .smart_filters
+                .iter()
+                .any(|filter| filter.name == draft.name);
+            filters::render_editor(ctx, draft, saved)
+        });
+        match outcome {
+            None | Some(EditorOutcome::Open) => {}
+            Some(EditorOutcome::Save(filter)) => {
+                match config
+                    .smart_filters
+                    .iter_mut()
+                    .find(|saved| saved.name == filter.name)
+                {
+                    Some(saved) => *saved = filter.clone(),
+                    None => config.smart_filters.push(filter.clone()),
+                }
+                if let Err(e) = config.save() {
+                    *error_message = Some(format!("Saving the smart filter failed with:\n{}", e));
+                }
+                *active_filter = Some(filter);
+                *filter_editor = None;
+            }
+            Some(EditorOutcome::Delete(name)) => {
+                config.smart_filters.retain(|filter| filter.name != name);
+                if let Err(e) = config.save() {
+                    *error_message = Some(format!("Deleting the smart filter failed with:\n{}", e));
+                }
+                *active_filter = None;
+                *filter_editor = None;
+            }
+            Some(EditorOutcome::Cancel) => *filter_editor = None,
+        }
+
+        if *read_only {
+            ui.add_space(PADDING);
+            ui.colored_label(
+                Color32::YELLOW,
+                "🔒 The candidates directory isn't writable for you, so installing, deleting and \
+                 switching versions is turned off. A version's sudo button copies the command \
+                 that does it from a terminal instead.",
+            );
+            ui.add_space(PADDING);
+        }
+
+        if candidates.is_empty() {
+            let empty_state = match catalog_error {
+                Some(e) => EmptyState::FetchFailed(e.as_str()),
+                None if *installed_only => EmptyState::NothingInstalled,
+                None => EmptyState::NoCandidates,
+            };
+            if empty::render(ui, &empty_state) {
+                state.reload_remote(repaint);
+                *installed_only = false;
+            }
+            return;
+        }
+
+        // the list has a pane of its own
+        if config.pane_layout == PaneLayout::ThreePanes && selected_candidate.is_none() {
+            ui.add_space(PADDING);
+            ui.label("Select a candidate in the list to see its versions.");
+            return;
+        }
+
         // render candidates
-        for curr in candidates {
+        let mut shown = 0;
+        let mut subscribed = false;
+        for curr in candidates.iter_mut() {
             // check whether to display the selected candidate only
             let candidate = if selected_candidate.is_none()
-                || curr.name == selected_candidate.as_ref().unwrap().name
+                || curr.name() == selected_candidate.as_ref().unwrap().name()
             {
                 curr
             } else {
                 continue;
             };
 
+            let local_candidate = local_candidates
+                .iter()
+                .find(|local| local.binary_name() == candidate.binary_name());
+            if let Some(filter) = active_filter.as_ref() {
+                if selected_candidate.is_none() && !filter.matches(candidate, local_candidate) {
+                    continue;
+                }
+            }
+            shown += 1;
+
             ui.add_space(PADDING);
+            perf.count_widgets(1);
 
             // render name, default version, and homepage URL
             ui.horizontal(|ui| {
                 // render name and default version
                 ui.with_layout(Layout::left_to_right(), |ui| {
-                    let btn_label = format!("{} {} ⤴", candidate.name, candidate.default_version);
+                    let pin = if config.is_pinned(candidate.binary_name()) {
+                        "📌 "
+                    } else {
+                        ""
+                    };
+                    let btn_label = format!(
+                        "{}{} {} ⤴",
+                        pin,
+                        candidate.name(),
+                        candidate.default_version()
+                    );
                     let title_btn = Button::new(btn_label)
                         .text_style(TextStyle::Body)
                         .text_color(WHITE);
                     let added = ui.add(title_btn).on_hover_ui(|ui| {
                         show_tooltip_text(
                             ui.ctx(),
-                            Id::new(&candidate.name),
+                            Id::new(candidate.name()),
                             "Click to display all available versions",
                         );
                     });
                     // handle clicks on the name and default version
                     if added.clicked() {
-                        match fetch_candidate_versions(&mut candidate.to_model()) {
+                        match candidate.load_versions() {
                             Ok(candidate_with_versions) => {
-                                *selected_candidate =
-                                    Some(Candidate::from_model(candidate_with_versions));
+                                *selected_candidate = Some(candidate_with_versions);
                             }
                             Err(e) => {
                                 *selected_candidate = None;
+                                if !SdkmanApp::schedule_retry(
+                                    &e,
+                                    Retry::Versions(candidate.binary_name().to_string()),
+                                    scheduled_retry,
+                                ) {
+                                    *error_message = Some(format!(
+                                        "Fetching available candidate versions failed with:\n{}",
+                                        e
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                    // render the local state of the candidate
+                    if let Some(local_candidate) = local_candidate {
+                        SdkmanApp::render_local_badges(ui, local_candidate);
+                    }
+                    if candidate.is_archived() {
+                        ui.add(
+                            Label::new(" archived upstream ")
+                                .small()
+                                .text_color(WHITE)
+                                .background_color(Color32::DARK_RED),
+                        )
+                        .on_hover_text(
+                            "No longer in the SDKMAN catalog, so there are no updates to check for",
+                        );
+                    }
+                });
+
+                // render homepage URL
+                ui.with_layout(Layout::right_to_left(), |ui| {
+                    ui.style_mut().visuals.hyperlink_color = CYAN;
+                    if !candidate.homepage().is_empty() {
+                        ui.add(Hyperlink::new(candidate.homepage()).text(candidate.homepage()));
+                    }
+                    // right to left, so the links are added in reverse
+                    for link in candidate.links().iter().rev() {
+                        ui.add(Hyperlink::new(link.url).text(link.kind.icon()))
+                            .on_hover_text(format!("{}: {}", link.kind.label(), link.url));
+                    }
+                    if config.homepage_panel
+                        && !candidate.homepage().is_empty()
+                        && ui
+                            .add(Button::new("📖").text_style(TextStyle::Body))
+                            .on_hover_text("Read the homepage here")
+                            .clicked()
+                    {
+                        // a second click closes it again
+                        let showing = homepage
+                            .as_ref()
+                            .is_some_and(|panel| panel.is_for(candidate.homepage()));
+                        *homepage = if showing {
+                            None
+                        } else {
+                            Some(HomepagePanel::open(
+                                candidate.name(),
+                                candidate.homepage(),
+                                repaint,
+                            ))
+                        };
+                    }
+                    let subscription = config
+                        .subscriptions
+                        .iter()
+                        .position(|name| name == candidate.binary_name());
+                    let (bell, hover) = match subscription {
+                        Some(_) => ("🔕", "Unsubscribe from the new versions"),
+                        None => ("🔔", "Subscribe to the new versions"),
+                    };
+                    // there are no new versions of archived candidates to hear about
+                    if !candidate.is_archived()
+                        && ui
+                            .add(Button::new(bell).text_style(TextStyle::Body))
+                            .on_hover_text(hover)
+                            .clicked()
+                    {
+                        match subscription {
+                            Some(index) => {
+                                config.subscriptions.remove(index);
+                            }
+                            None => {
+                                config
+                                    .subscriptions
+                                    .push(candidate.binary_name().to_string());
+                                subscribed = true;
+                            }
+                        }
+                        if let Err(e) = config.save() {
+                            *error_message =
+                                Some(format!("Saving the subscriptions failed with:\n{}", e));
+                        }
+                        state.set_subscriptions(config.subscriptions.clone());
+                    }
+                    if let Some(local_candidate) =
+                        local_candidate.filter(|local| !*read_only && !local.versions().is_empty())
+                    {
+                        if ui
+                            .add(Button::new("🗑").text_style(TextStyle::Body))
+                            .on_hover_text("Remove all versions")
+                            .clicked()
+                        {
+                            *remove_all = Some(RemoveAll::new(local_candidate, protected, repaint));
+                        }
+                    }
+                    // install latest stable quick action, or whatever version a shorthand names
+                    let latest = util::strip_parens(candidate.default_version());
+                    if !candidate.is_archived()
+                        && ui
+                            .add_enabled(!*read_only, Button::new("⬇").text_style(TextStyle::Body))
+                            .on_hover_text(format!(
+                                "Install the latest stable version {} or another one by shorthand",
+                                latest
+                            ))
+                            .clicked()
+                    {
+                        // the shorthands are resolved against all versions
+                        match candidate.load_versions() {
+                            Ok(loaded) => *quick_install = Some(QuickInstall::new(&loaded)),
+                            Err(e) => {
+                                *error_message = Some(format!(
+                                    "Fetching available candidate versions failed with:\n{}",
+                                    e
+                                ))
+                            }
+                        }
+                    }
+                    // side by side comparison, picked one after the other
+                    let compare_hint = match comparison.waiting_for() {
+                        Some(first) => format!("Compare {} with this candidate", first.name()),
+                        None => "Compare with another candidate".to_string(),
+                    };
+                    if ui
+                        .add(Button::new("⚖").text_style(TextStyle::Body))
+                        .on_hover_text(compare_hint)
+                        .clicked()
+                    {
+                        match candidate.load_versions() {
+                            Ok(candidate_with_versions) => comparison.add(candidate_with_versions),
+                            Err(e) => {
                                 *error_message = Some(format!(
                                     "Fetching available candidate versions failed with:\n{}",
                                     e
@@ -331,135 +2761,923 @@ impl SdkmanApp {
                         }
                     }
                 });
+            });
 
-                // render homepage URL
-                ui.with_layout(Layout::right_to_left(), |ui| {
-                    ui.style_mut().visuals.hyperlink_color = CYAN;
-                    ui.add(Hyperlink::new(&candidate.url).text(&candidate.url));
+            ui.add_space(PADDING);
+            // render description, long ones are truncated until expanded
+            let expanded = expanded_descriptions.contains(candidate.binary_name());
+            let description = match &candidate.short_description {
+                Some(short) if !expanded => short,
+                _ => candidate.description(),
+            };
+            ui.add(
+                Label::new(description)
+                    .wrap(true)
+                    .text_style(eframe::egui::TextStyle::Body),
+            );
+            if candidate.short_description.is_some() {
+                let toggle = if expanded { "show less" } else { "show more" };
+                if ui.small_button(toggle).clicked() {
+                    if expanded {
+                        expanded_descriptions.remove(candidate.binary_name());
+                    } else {
+                        expanded_descriptions.insert(candidate.binary_name().to_string());
+                    }
+                }
+            }
+
+            ui.add_space(PADDING);
+            // render installation instruction
+            ui.with_layout(Layout::right_to_left(), |ui| {
+                let installation = Label::new(&candidate.installation_instruction)
+                    .text_style(eframe::egui::TextStyle::Body);
+                ui.add(installation);
+            });
+            if let Some(selected) = selected_candidate.as_ref() {
+                for snippet in [Snippet::Use, Snippet::Home] {
+                    let command =
+                        snippet.command(selected.binary_name(), selected.snippet_version());
+                    ui.with_layout(Layout::right_to_left(), |ui| {
+                        if ui.small_button("📋").on_hover_text("Copy").clicked() {
+                            ui.ctx().output().copied_text = command.clone();
+                        }
+                        ui.add(
+                            Label::new(format!("$ {}", command))
+                                .text_style(eframe::egui::TextStyle::Body),
+                        )
+                        .on_hover_text(snippet.hint());
+                    });
+                }
+                // what sdk home would print, if the version is installed
+                let key = (
+                    selected.binary_name().to_string(),
+                    selected.snippet_version().to_string(),
+                );
+                let home = sdk_homes
+                    .entry(key)
+                    .or_insert_with(|| {
+                        sdk_home(selected.binary_name(), selected.snippet_version()).ok()
+                    })
+                    .as_ref();
+                if let Some(home) = home {
+                    let home = home.display().to_string();
+                    ui.with_layout(Layout::right_to_left(), |ui| {
+                        if ui.small_button("📋").on_hover_text("Copy").clicked() {
+                            ui.ctx().output().copied_text = home.clone();
+                        }
+                        ui.monospace(&home);
+                    });
+                }
+            }
+
+            ui.add_space(PADDING);
+            ui.add(Separator::default());
+
+            if let Some(selected) = selected_candidate {
+                if *detail_tab == DetailTab::Versions {
+                    perf.count_widgets(selected.versions().len());
+                }
+            }
+            if selected_candidate.is_some() {
+                if config.show_release_dates {
+                    SdkmanApp::load_release_dates(selected_candidate, release_dates, error_message);
+                }
+                let mut rescan = false;
+                let requested = SdkmanApp::render_selected_candidate(
+                    ui,
+                    selected_candidate,
+                    local_candidate,
+                    detail_tab,
+                    version_files,
+                    history,
+                    config,
+                    protected,
+                    download_preview,
+                    run_window,
+                    version_sort,
+                    vendor_filter,
+                    version_sizes,
+                    arch_mismatches,
+                    stage_changes,
+                    *read_only,
+                    &mut rescan,
+                    error_message,
+                );
+                if rescan {
+                    state.reload_local_candidates(
+                        vec![candidate.binary_name().to_string()],
+                        repaint,
+                    );
+                }
+                if let Some((version, action)) = requested {
+                    let step = Step::new(candidate.binary_name(), &version, action);
+                    let usages =
+                        SdkmanApp::delete_usages(&config.projects, std::slice::from_ref(&step));
+                    let current = local_candidate.and_then(|local| local.current_version());
+                    if !usages.is_empty() {
+                        *delete_warning = Some((step.to_string(), vec![step], usages));
+                    } else if let (Action::SetCurrent, true, Some(current)) =
+                        (action, config.preview_switches, current)
+                    {
+                        *switch_preview = Some(SwitchPreview::new(step, current));
+                    } else if *stage_changes {
+                        transaction.stage(step);
+                    } else {
+                        jobs.submit(step.to_string(), vec![step], repaint);
+                    }
+                }
+            }
+        }
+        // learn the versions there are now, so only later ones end up in the inbox
+        if subscribed {
+            subscriptions::check_in_background(
+                candidates.iter().map(|c| c.model.clone()).collect(),
+                config.subscriptions.clone(),
+                state,
+                repaint,
+            );
+        }
+        if shown == 0 {
+            if let Some(filter) = active_filter.as_ref() {
+                ui.add_space(PADDING);
+                ui.label(format!(
+                    "No candidate matches the filter '{}'.",
+                    filter.name
+                ));
+            }
+        }
+
+        ui.add_space(7. * PADDING);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn render_selected_candidate(
+        ui: &mut Ui,
+        selected_candidate: &mut Option<Candidate>,
+        local_candidate: Option<&LocalCandidate>,
+        detail_tab: &mut DetailTab,
+        version_files: &mut HashMap<(String, String), Vec<LocalFile>>,
+        history: &History,
+        config: &mut Config,
+        protected: &mut Protected,
+        download_preview: &mut Option<DownloadPreviewWindow>,
+        run_window: &mut Option<RunWindow>,
+        version_sort: &mut VersionSort,
+        vendor_filter: &mut Option<String>,
+        version_sizes: &mut HashMap<(String, String), u64>,
+        arch_mismatches: &mut HashMap<(String, String), Option<ArchMismatch>>,
+        stage_changes: &mut bool,
+        read_only: bool,
+        rescan: &mut bool,
+        error_message: &mut Option<String>,
+    ) -> Option<(String, Action)> {
+        ui.add_space(PADDING);
+        ui.horizontal(|ui| {
+            ui.with_layout(Layout::left_to_right(), |ui| {
+                ui.add_space(PADDING);
+                ui.selectable_value(detail_tab, DetailTab::Versions, "Versions");
+                ui.selectable_value(detail_tab, DetailTab::Info, "Info");
+                ui.selectable_value(detail_tab, DetailTab::Files, "Files");
+                ui.selectable_value(detail_tab, DetailTab::History, "History");
+            });
+            ui.with_layout(Layout::right_to_left(), |ui| {
+                ui.add_space(PADDING);
+                ui.with_layout(Layout::top_down(Align::RIGHT), |ui| {
+                    let reload_btn_label = Label::new("🔄")
+                        .text_style(eframe::egui::TextStyle::Body)
+                        .sense(Sense::click());
+                    if ui
+                        .add(reload_btn_label)
+                        .on_hover_text("Reload versions and rescan the installed ones")
+                        .clicked()
+                    {
+                        SdkmanApp::reload_versions(selected_candidate, error_message);
+                        *rescan = true;
+                    }
+                });
+                ui.with_layout(Layout::top_down(Align::RIGHT), |ui| {
+                    let close_btn_label = Label::new("❌")
+                        .wrap(true)
+                        .text_style(eframe::egui::TextStyle::Body)
+                        .sense(Sense::click());
+                    if ui
+                        .add(close_btn_label)
+                        .on_hover_ui(|ui| {
+                            show_tooltip_text(
+                                ui.ctx(),
+                                Id::new(selected_candidate.as_ref().map(|c| c.name()).unwrap()),
+                                "Close",
+                            );
+                        })
+                        .clicked()
+                    {
+                        *selected_candidate = None;
+                    }
                 });
             });
+        });
+        let candidate = match selected_candidate {
+            Some(candidate) => candidate.clone(),
+            None => return None,
+        };
+        let mut requested = None;
+        ui.add_space(2. * PADDING);
+        match detail_tab {
+            DetailTab::Versions if candidate.versions().is_empty() => {
+                let platform = platform();
+                if empty::render(ui, &EmptyState::NoVersions(&platform)) {
+                    SdkmanApp::reload_versions(selected_candidate, error_message);
+                }
+            }
+            DetailTab::Versions => {
+                let mut selected_version = candidate.selected_version.clone();
+                requested = SdkmanApp::render_versions_tab(
+                    ui,
+                    &candidate,
+                    &mut selected_version,
+                    local_candidate,
+                    config,
+                    protected,
+                    download_preview,
+                    run_window,
+                    version_sort,
+                    vendor_filter,
+                    version_sizes,
+                    arch_mismatches,
+                    stage_changes,
+                    read_only,
+                    error_message,
+                );
+                if let Some(selected) = selected_candidate {
+                    selected.selected_version = selected_version;
+                }
+            }
+            DetailTab::Info => SdkmanApp::render_candidate_info(ui, &candidate),
+            DetailTab::Files => {
+                SdkmanApp::render_candidate_files(ui, local_candidate, version_files)
+            }
+            DetailTab::History => {
+                SdkmanApp::render_candidate_history(ui, candidate.binary_name(), history)
+            }
+        }
+        ui.add_space(3. * PADDING);
+        requested
+    }
+
+    fn reload_versions(
+        selected_candidate: &mut Option<Candidate>,
+        error_message: &mut Option<String>,
+    ) {
+        let candidate = match selected_candidate {
+            Some(candidate) => candidate,
+            None => return,
+        };
+        let binary_name = candidate.binary_name().to_string();
+        if let Err(e) = Sdkman::new().forget_versions(&binary_name) {
+            log!(
+                "Failed to clear the cached versions of {}: {}",
+                binary_name,
+                e
+            );
+        }
+        match candidate.load_versions() {
+            Ok(candidate_with_versions) => {
+                *candidate = candidate_with_versions;
+            }
+            Err(e) => {
+                *error_message = Some(format!(
+                    "Fetching available candidate versions failed with:\n{}",
+                    e
+                ));
+            }
+        }
+    }
 
-            ui.add_space(PADDING);
-            // render description
-            let description = Label::new(&candidate.description)
-                .wrap(true)
-                .text_style(eframe::egui::TextStyle::Body);
-            ui.add(description);
+    #[allow(clippy::too_many_arguments)]
+    fn render_versions_tab(
+        ui: &mut Ui,
+        candidate: &Candidate,
+        selected_version: &mut Option<String>,
+        local_candidate: Option<&LocalCandidate>,
+        config: &mut Config,
+        protected: &mut Protected,
+        download_preview: &mut Option<DownloadPreviewWindow>,
+        run_window: &mut Option<RunWindow>,
+        version_sort: &mut VersionSort,
+        vendor_filter: &mut Option<String>,
+        version_sizes: &mut HashMap<(String, String), u64>,
+        arch_mismatches: &mut HashMap<(String, String), Option<ArchMismatch>>,
+        stage_changes: &mut bool,
+        read_only: bool,
+        error_message: &mut Option<String>,
+    ) -> Option<(String, Action)> {
+        let mut requested = None;
+        let binary_name = candidate.binary_name().to_string();
+        let empty_versions = HashMap::new();
+        let local_versions = local_candidate
+            .map(|c| c.versions())
+            .unwrap_or(&empty_versions);
 
-            ui.add_space(PADDING);
-            // render installation instruction
+        ui.horizontal(|ui| {
+            ui.label("Export versions:");
+            if ui
+                .button("CSV")
+                .on_hover_text("Copy all versions as CSV")
+                .clicked()
+            {
+                ui.ctx().output().copied_text =
+                    export::versions_to_csv(&binary_name, candidate.versions(), local_versions);
+            }
+            if ui
+                .button("JSON")
+                .on_hover_text("Copy all versions as JSON")
+                .clicked()
+            {
+                ui.ctx().output().copied_text =
+                    export::versions_to_json(&binary_name, candidate.versions(), local_versions);
+            }
             ui.with_layout(Layout::right_to_left(), |ui| {
-                let installation = Label::new(&candidate.installation_instruction)
-                    .text_style(eframe::egui::TextStyle::Body);
-                ui.add(installation);
+                ui.checkbox(stage_changes, "Stage changes")
+                    .on_hover_text("Collect the changes and apply them together after a review");
             });
+        });
+        ui.add_space(PADDING);
 
-            ui.add_space(PADDING);
-            ui.add(Separator::default());
+        // only the Java versions come in columns worth configuring
+        let layout = if binary_name == "java" {
+            let mut layout = config.column_layout(&binary_name);
+            CollapsingHeader::new("Columns")
+                .id_source("version_columns")
+                .show(ui, |ui| {
+                    if layout.render_editor(ui) {
+                        config
+                            .column_layouts
+                            .insert(binary_name.clone(), layout.clone());
+                        if let Err(e) = config.save() {
+                            *error_message =
+                                Some(format!("Saving the column layout failed with:\n{}", e));
+                        }
+                    }
+                });
+            ui.add(Label::new(format!(" {}", layout.header())).strong());
+            Some(layout)
+        } else {
+            None
+        };
 
-            if selected_candidate.is_some() {
-                SdkmanApp::render_selected_candidate(
-                    ui,
-                    selected_candidate,
-                    local_candidates.iter().find(|local_candidate| {
-                        if let Some(remote_candidate) = &selected_candidate {
-                            local_candidate.binary_name()
-                                == remote_candidate
-                                    .installation_instruction
-                                    .split_whitespace()
-                                    .last()
-                                    .unwrap_or_default()
-                        } else {
-                            false
+        // the JDKs are told apart by their vendors
+        if binary_name == "java" {
+            let mut vendors: Vec<&str> = Vec::new();
+            for version in candidate.versions() {
+                if !vendors.contains(&version.vendor()) {
+                    vendors.push(version.vendor());
+                }
+            }
+            ui.horizontal(|ui| {
+                ui.label("Vendor:");
+                ComboBox::from_id_source("vendor_filter")
+                    .selected_text(vendor_filter.as_deref().unwrap_or("All vendors"))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(vendor_filter, None, "All vendors");
+                        for vendor in vendors {
+                            ui.selectable_value(vendor_filter, Some(vendor.to_string()), vendor);
                         }
-                    }),
+                    });
+            });
+        }
+        let vendor = vendor_filter.as_deref().filter(|_| binary_name == "java");
+        let mut versions: Vec<&RemoteVersion> = candidate
+            .versions()
+            .iter()
+            .filter(|version| vendor.is_none_or(|vendor| version.vendor() == vendor))
+            .collect();
+        if config.show_release_dates {
+            SdkmanApp::render_release_date_header(ui, version_sort);
+            match version_sort {
+                VersionSort::Default => {}
+                VersionSort::NewestFirst => {
+                    versions.sort_by_key(|version| Reverse(version.released()))
+                }
+                VersionSort::OldestFirst => versions.sort_by_key(|version| version.released()),
+            }
+        }
+        // render all available versions
+        for selected_candidate_version in versions {
+            let size = match &layout {
+                Some(layout)
+                    if layout.is_visible(Column::Size)
+                        && local_versions.contains_key(selected_candidate_version.id()) =>
+                {
+                    SdkmanApp::version_size(
+                        version_sizes,
+                        &binary_name,
+                        selected_candidate_version.id(),
+                    )
+                }
+                _ => None,
+            };
+            let mismatch = if local_versions.contains_key(selected_candidate_version.id()) {
+                SdkmanApp::arch_mismatch(
+                    arch_mismatches,
+                    &binary_name,
+                    selected_candidate_version.id(),
+                )
+            } else {
+                None
+            };
+            if let Some(action) = SdkmanApp::render_selected_candidate_version(
+                ui,
+                &binary_name,
+                selected_candidate_version,
+                selected_version,
+                local_versions,
+                config.show_release_dates,
+                layout.as_ref(),
+                size,
+                mismatch,
+                config.double_click_action,
+                &config.terminal_command,
+                protected,
+                download_preview,
+                run_window,
+                read_only,
+                error_message,
+            ) {
+                requested = Some((selected_candidate_version.id().clone(), action));
+            }
+        }
+        requested
+    }
+
+    // walking a version directory is expensive, so the size is computed once per version
+    fn version_size(
+        version_sizes: &mut HashMap<(String, String), u64>,
+        binary_name: &str,
+        version: &str,
+    ) -> Option<u64> {
+        let key = (binary_name.to_string(), version.to_string());
+        if let Some(size) = version_sizes.get(&key) {
+            return Some(*size);
+        }
+        match version_disk_usage(binary_name, version) {
+            Ok(size) => {
+                version_sizes.insert(key, size);
+                Some(size)
+            }
+            Err(e) => {
+                log!(
+                    "Failed to compute the size of {} {}: {}",
+                    binary_name,
+                    version,
+                    e
                 );
+                None
             }
         }
+    }
 
-        ui.add_space(7. * PADDING);
+    // the binaries are inspected once per version, like their size
+    fn arch_mismatch<'a>(
+        arch_mismatches: &'a mut HashMap<(String, String), Option<ArchMismatch>>,
+        binary_name: &str,
+        version: &str,
+    ) -> Option<&'a ArchMismatch> {
+        arch_mismatches
+            .entry((binary_name.to_string(), version.to_string()))
+            .or_insert_with(|| match arch::check_version(binary_name, version) {
+                Ok(mismatch) => mismatch,
+                Err(e) => {
+                    log!(
+                        "Failed to inspect the binaries of {} {}: {}",
+                        binary_name,
+                        version,
+                        e
+                    );
+                    None
+                }
+            })
+            .as_ref()
     }
 
-    fn render_selected_candidate(
-        ui: &mut Ui,
+    fn load_release_dates(
         selected_candidate: &mut Option<Candidate>,
-        local_candidate: Option<&LocalCandidate>,
+        release_dates: &mut HashMap<String, HashMap<String, ReleaseDate>>,
+        error_message: &mut Option<String>,
     ) {
-        ui.add_space(PADDING);
-        ui.horizontal(|ui| {
-            ui.with_layout(Layout::left_to_right(), |ui| {
+        let candidate = match selected_candidate {
+            Some(candidate) => candidate,
+            None => return,
+        };
+        let binary_name = candidate.binary_name().to_string();
+        if !has_release_dates(&binary_name) {
+            return;
+        }
+        // fetched once per candidate, failures included, so a broken lookup isn't retried every frame
+        let dates = release_dates.entry(binary_name.clone()).or_insert_with(|| {
+            match fetch_release_dates(&binary_name) {
+                Ok(dates) => dates,
+                Err(e) => {
+                    *error_message = Some(format!(
+                        "Fetching the release dates of '{}' failed with:\n{}",
+                        binary_name, e
+                    ));
+                    HashMap::new()
+                }
+            }
+        });
+        // only copy the shared model once there is actually something to fill in
+        let missing = candidate
+            .versions()
+            .iter()
+            .any(|version| version.released().is_none() && dates.contains_key(version.id()));
+        if missing {
+            Arc::make_mut(&mut candidate.model).with_release_dates(dates);
+        }
+    }
+
+    // returns whether the installation was confirmed once the user decided
+    fn render_quick_install(
+        ctx: &CtxRef,
+        confirmation: &mut QuickInstall,
+        local_candidates: &[LocalCandidate],
+    ) -> Option<bool> {
+        let mut confirmed = None;
+        let resolved = confirmation.resolved();
+        let installed = resolved.as_ref().is_some_and(|version| {
+            local_candidates
+                .iter()
+                .find(|local| *local.binary_name() == confirmation.binary_name)
+                .is_some_and(|local| local.versions().contains_key(version))
+        });
+        Window::new("Install latest")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0., 0.])
+            .show(ctx, |ui| {
                 ui.add_space(PADDING);
-                ui.add(
-                    Label::new(format!(
-                        "Available {} versions",
-                        selected_candidate.as_ref().unwrap().name
-                    ))
-                    .wrap(true)
-                    .text_style(eframe::egui::TextStyle::Body),
+                ui.horizontal(|ui| {
+                    ui.label(format!("Install {}", confirmation.binary_name));
+                    ui.text_edit_singleline(&mut confirmation.alias)
+                        .on_hover_text("A version or a shorthand: default, latest, 3.9, and for Java lts, 21-tem or lts-zulu");
+                });
+                match &resolved {
+                    Some(version) if installed => {
+                        ui.label(format!("{} is installed already", version))
+                    }
+                    Some(version) => ui.label(format!("→ {}", version)),
+                    None => ui.colored_label(Color32::YELLOW, "No version matches"),
+                };
+                let deprecation = resolved.as_ref().and_then(|id| {
+                    let dist = confirmation
+                        .versions
+                        .iter()
+                        .find(|version| version.id() == id)
+                        .map_or("", |version| version.dist());
+                    category::deprecation(&confirmation.binary_name, id, dist)
+                });
+                if let Some(reason) = deprecation {
+                    ui.colored_label(Color32::YELLOW, format!("Deprecated: {}", reason));
+                }
+                ui.checkbox(
+                    &mut confirmation.set_current,
+                    "Set it as the current version",
                 );
-            });
-            ui.with_layout(Layout::right_to_left(), |ui| {
                 ui.add_space(PADDING);
-                ui.with_layout(Layout::top_down(Align::RIGHT), |ui| {
-                    let close_btn_label = Label::new("❌")
-                        .wrap(true)
-                        .text_style(eframe::egui::TextStyle::Body)
-                        .sense(Sense::click());
+                ui.horizontal(|ui| {
                     if ui
-                        .add(close_btn_label)
-                        .on_hover_ui(|ui| {
-                            show_tooltip_text(
-                                ui.ctx(),
-                                Id::new(selected_candidate.as_ref().map(|c| &c.name).unwrap()),
-                                "Close",
-                            );
-                        })
+                        .add_enabled(resolved.is_some() && !installed, Button::new("Install"))
                         .clicked()
                     {
-                        *selected_candidate = None;
+                        confirmed = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        confirmed = Some(false);
                     }
                 });
             });
+        confirmed
+    }
+
+    fn render_local_badges(ui: &mut Ui, local_candidate: &LocalCandidate) {
+        let badge = |text: String| {
+            Label::new(format!(" {} ", text))
+                .small()
+                .text_color(WHITE)
+                .background_color(BADGE)
+        };
+        ui.add(badge(format!(
+            "{} installed",
+            local_candidate.versions().len()
+        )));
+        if let Some(current) = local_candidate.current_version() {
+            ui.add(badge(format!("current: {}", current)));
+        }
+    }
+
+    fn render_release_date_header(ui: &mut Ui, version_sort: &mut VersionSort) {
+        ui.horizontal(|ui| {
+            ui.with_layout(Layout::left_to_right(), |ui| {
+                let label = match version_sort {
+                    VersionSort::Default => "Released",
+                    VersionSort::NewestFirst => "Released ⬇",
+                    VersionSort::OldestFirst => "Released ⬆",
+                };
+                if ui
+                    .add(Button::new(label).frame(false))
+                    .on_hover_text("Sort by release date")
+                    .clicked()
+                {
+                    *version_sort = match version_sort {
+                        VersionSort::Default => VersionSort::NewestFirst,
+                        VersionSort::NewestFirst => VersionSort::OldestFirst,
+                        VersionSort::OldestFirst => VersionSort::Default,
+                    };
+                }
+            });
         });
-        // render all available versions
-        ui.add_space(2. * PADDING);
-        for selected_candidate_version in selected_candidate
-            .as_ref()
-            .map(|c| c.versions.to_vec())
-            .unwrap_or_default()
-        {
-            SdkmanApp::render_selected_candidate_version(
-                ui,
-                &selected_candidate_version,
-                local_candidate
-                    .map(|c| c.versions())
-                    .unwrap_or(&HashMap::new()),
-            );
+    }
+
+    fn render_candidate_info(ui: &mut Ui, candidate: &Candidate) {
+        Grid::new("candidate_info")
+            .num_columns(2)
+            .spacing([4. * PADDING, PADDING])
+            .show(ui, |ui| {
+                ui.label("Name:");
+                ui.label(candidate.name());
+                ui.end_row();
+                ui.label("Binary:");
+                ui.monospace(candidate.binary_name());
+                ui.end_row();
+                ui.label("Category:");
+                ui.label(candidate.category().to_string());
+                ui.end_row();
+                ui.label("Default version:");
+                ui.label(util::strip_parens(candidate.default_version()));
+                ui.end_row();
+                ui.label("Homepage:");
+                ui.style_mut().visuals.hyperlink_color = CYAN;
+                ui.add(Hyperlink::new(candidate.homepage()).text(candidate.homepage()));
+                ui.end_row();
+            });
+        ui.add_space(PADDING);
+        ui.add(
+            Label::new(candidate.description())
+                .wrap(true)
+                .text_style(eframe::egui::TextStyle::Body),
+        );
+    }
+
+    fn render_candidate_files(
+        ui: &mut Ui,
+        local_candidate: Option<&LocalCandidate>,
+        version_files: &mut HashMap<(String, String), Vec<LocalFile>>,
+    ) {
+        let local_candidate = match local_candidate {
+            Some(local_candidate) => local_candidate,
+            None => {
+                ui.label("No versions of this candidate are installed.");
+                return;
+            }
+        };
+        let mut versions: Vec<&String> = local_candidate.versions().keys().collect();
+        versions.sort_by(|v1, v2| alphanumeric_sort::compare_str(v2, v1));
+        for version in versions {
+            let path = version_path(local_candidate.binary_name(), version)
+                .map(|path| path.to_string_lossy().to_string())
+                .unwrap_or_else(|_| version.clone());
+            CollapsingHeader::new(path)
+                .id_source(version)
+                .show(ui, |ui| {
+                    if let Ok(home) = home_path(local_candidate.binary_name(), version) {
+                        if home.ends_with("Contents/Home") {
+                            ui.label(format!("Home: {}", home.display()));
+                        }
+                    }
+                    // directory sizes are expensive to compute, so do it only once per version
+                    let key = (local_candidate.binary_name().clone(), version.clone());
+                    if !version_files.contains_key(&key) {
+                        match list_version_files(local_candidate.binary_name(), version) {
+                            Ok(files) => {
+                                version_files.insert(key.clone(), files);
+                            }
+                            Err(e) => {
+                                ui.label(format!("Failed to list the files: {}", e));
+                                return;
+                            }
+                        }
+                    }
+                    Grid::new(("version_files", version))
+                        .striped(true)
+                        .num_columns(2)
+                        .spacing([4. * PADDING, 0.])
+                        .show(ui, |ui| {
+                            for file in version_files.get(&key).into_iter().flatten() {
+                                if file.is_dir() {
+                                    ui.monospace(format!("{}/", file.name()));
+                                } else {
+                                    ui.monospace(file.name());
+                                }
+                                ui.label(util::format_size(file.size()));
+                                ui.end_row();
+                            }
+                        });
+                });
         }
-        ui.add_space(3. * PADDING);
     }
 
+    fn render_candidate_history(ui: &mut Ui, binary_name: &str, history: &History) {
+        let mut entries = history.for_candidate(binary_name).peekable();
+        if entries.peek().is_none() {
+            ui.label("No actions recorded for this candidate yet.");
+            return;
+        }
+        Grid::new("candidate_history")
+            .striped(true)
+            .num_columns(3)
+            .spacing([4. * PADDING, 0.])
+            .show(ui, |ui| {
+                for entry in entries {
+                    ui.label(util::format_elapsed(entry.timestamp));
+                    ui.label(entry.action.to_string());
+                    ui.monospace(&entry.version);
+                    ui.end_row();
+                }
+            });
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn render_selected_candidate_version(
         ui: &mut Ui,
+        binary_name: &str,
         version: &RemoteVersion,
+        selected_version: &mut Option<String>,
         local_versions: &HashMap<String, bool>,
-    ) {
+        show_release_dates: bool,
+        layout: Option<&ColumnLayout>,
+        size: Option<u64>,
+        mismatch: Option<&ArchMismatch>,
+        double_click_action: DoubleClickAction,
+        terminal_command: &str,
+        protected: &mut Protected,
+        download_preview: &mut Option<DownloadPreviewWindow>,
+        run_window: &mut Option<RunWindow>,
+        read_only: bool,
+        error_message: &mut Option<String>,
+    ) -> Option<Action> {
+        let mut action = None;
+        let selected = selected_version.as_deref() == Some(version.id().as_str());
         ui.horizontal(|ui| {
-            ui.with_layout(Layout::left_to_right(), |ui| {
-                ui.label(version.mk_string(local_versions));
-            });
+            let row = ui
+                .with_layout(Layout::left_to_right(), |ui| match layout {
+                    Some(layout) => ui.add(version_label(
+                        format!(" {}", layout.row(version, local_versions, size)),
+                        selected,
+                    )),
+                    None => {
+                        if show_release_dates {
+                            ui.monospace(
+                                version
+                                    .released()
+                                    .map(|released| released.to_string())
+                                    .unwrap_or_else(|| " ".repeat(10)),
+                            );
+                        }
+                        ui.add(version_label(version.mk_string(local_versions), selected))
+                    }
+                })
+                .inner;
+            if row.clicked() {
+                *selected_version = Some(version.id().clone());
+            }
+            if row.double_clicked() {
+                let installed = local_versions.get(version.id());
+                action = match double_click_action {
+                    // copying is the only thing left to do without write access
+                    _ if read_only => {
+                        ui.ctx().output().copied_text = version.id().to_string();
+                        None
+                    }
+                    DoubleClickAction::Smart => match installed {
+                        None => Some(Action::Install),
+                        Some(false) => Some(Action::SetCurrent),
+                        Some(true) => None,
+                    },
+                    DoubleClickAction::Install if installed.is_none() => Some(Action::Install),
+                    DoubleClickAction::SetCurrent if installed == Some(&false) => {
+                        Some(Action::SetCurrent)
+                    }
+                    DoubleClickAction::CopyIdentifier => {
+                        ui.ctx().output().copied_text = version.id().to_string();
+                        None
+                    }
+                    _ => None,
+                };
+            }
+            if local_versions.contains_key(version.id()) {
+                context_menu(
+                    ui,
+                    &row,
+                    &format!("{} {}", binary_name, version.id()),
+                    |ui| {
+                        if ui.button("Run…").clicked() {
+                            *run_window = Some(RunWindow::new(binary_name, version.id()));
+                        }
+                        if binary_name == "java" {
+                            SdkmanApp::render_jshell_menu(
+                                ui,
+                                version.id(),
+                                local_versions,
+                                terminal_command,
+                                error_message,
+                            );
+                        }
+                        SdkmanApp::render_shim_menu(ui, binary_name, version.id(), error_message);
+                        SdkmanApp::render_protection_menu(
+                            ui,
+                            binary_name,
+                            version.id(),
+                            protected,
+                            error_message,
+                        );
+                    },
+                );
+            } else {
+                context_menu(
+                    ui,
+                    &row,
+                    &format!("{} {}", binary_name, version.id()),
+                    |ui| {
+                        if ui.button("Show where it downloads from").clicked() {
+                            *download_preview =
+                                Some(DownloadPreviewWindow::new(binary_name, version.id()));
+                        }
+                    },
+                );
+            }
+            if let Some(reason) = category::deprecation(binary_name, version.id(), version.dist()) {
+                ui.add(
+                    Label::new(" deprecated ")
+                        .small()
+                        .text_color(WHITE)
+                        .background_color(DEPRECATED_BADGE),
+                )
+                .on_hover_text(reason);
+            }
+            if let Some(mismatch) = mismatch {
+                ui.add(Label::new("⚠").text_color(Color32::YELLOW))
+                    .on_hover_text(format!(
+                        "{} is built for {}, but this machine is {}.\n{}",
+                        mismatch.binary.display(),
+                        mismatch.found(),
+                        mismatch.host,
+                        mismatch.suggestion()
+                    ));
+            }
+            if read_only {
+                ui.with_layout(Layout::right_to_left(), |ui| {
+                    let step = match local_versions.get(version.id()) {
+                        None => Step::new(binary_name, version.id(), Action::Install),
+                        Some(false) => Step::new(binary_name, version.id(), Action::SetCurrent),
+                        Some(true) => return,
+                    };
+                    // it resolves the installation, so it's only built when it's looked at
+                    if ui
+                        .add(Button::new("sudo").text_style(eframe::egui::TextStyle::Body))
+                        .on_hover_ui(|ui| {
+                            ui.label(match elevation::sudo_command(&step) {
+                                Ok(command) => {
+                                    format!("Copy the command to {}:\n{}", step, command)
+                                }
+                                Err(e) => e,
+                            });
+                        })
+                        .clicked()
+                    {
+                        if let Ok(command) = elevation::sudo_command(&step) {
+                            ui.ctx().output().copied_text = command;
+                        }
+                    }
+                });
+                return;
+            }
             ui.with_layout(Layout::right_to_left(), |ui| {
+                let is_protected = protected.contains(binary_name, version.id());
                 if ui
                     .add_enabled(
-                        local_versions.contains_key(version.id()),
+                        local_versions.contains_key(version.id()) && !is_protected,
                         Button::new("delete").text_style(eframe::egui::TextStyle::Body),
                     )
                     .on_hover_ui(|ui| {
                         show_tooltip_text(ui.ctx(), Id::new(version.id()), "Delete version");
                     })
+                    .on_disabled_hover_text(if is_protected {
+                        "Protected, lift the protection in the context menu to delete it"
+                    } else {
+                        "Not installed"
+                    })
                     .clicked()
                 {
-                    println!("Deleting candidate version...");
+                    action = Some(Action::Delete);
                 }
                 if ui
                     .add_enabled(
@@ -471,7 +3689,7 @@ impl SdkmanApp {
                     })
                     .clicked()
                 {
-                    println!("Installing candidate version...");
+                    action = Some(Action::Install);
                 }
                 if ui
                     .add_enabled(
@@ -484,10 +3702,112 @@ impl SdkmanApp {
                     })
                     .clicked()
                 {
-                    println!("Setting current candidate version...");
+                    action = Some(Action::SetCurrent);
                 }
             });
         });
+        action
+    }
+
+    // jshell of exactly this JDK, to compare how versions behave
+    fn render_jshell_menu(
+        ui: &mut Ui,
+        version: &str,
+        local_versions: &HashMap<String, bool>,
+        terminal_command: &str,
+        error_message: &mut Option<String>,
+    ) {
+        let name = if cfg!(windows) {
+            "jshell.exe"
+        } else {
+            "jshell"
+        };
+        let jshell = home_path("java", version)
+            .map(|home| home.join("bin").join(name))
+            .and_then(|jshell| {
+                if jshell.is_file() {
+                    Ok(jshell)
+                } else {
+                    Err(std::io::Error::from(std::io::ErrorKind::NotFound))
+                }
+            });
+        if ui
+            .add_enabled(jshell.is_ok(), Button::new("Open JShell"))
+            .on_disabled_hover_text("JShell comes with JDK 9 and later")
+            .clicked()
+        {
+            let local_candidate = LocalCandidate::new("java".to_string(), local_versions.clone());
+            if let Err(e) = jshell.and_then(|jshell| {
+                projects::launch_in_terminal(
+                    Some(&jshell),
+                    &[("java".to_string(), version.to_string())],
+                    terminal_command,
+                    &[local_candidate],
+                )
+            }) {
+                *error_message = Some(format!("Launching JShell failed with:\n{}", e));
+            }
+        }
+    }
+
+    // shims run a version no matter which one is current
+    fn render_shim_menu(
+        ui: &mut Ui,
+        binary_name: &str,
+        version: &str,
+        error_message: &mut Option<String>,
+    ) {
+        let name = shims::shim_name(binary_name, version);
+        let owner = shims::owner(binary_name, version).ok().flatten();
+        if let Some((owner, owned)) = owner
+            .as_ref()
+            .filter(|(owner, owned)| owner != binary_name || owned != version)
+        {
+            ui.add_enabled(false, Button::new(format!("Create shim {}", name)))
+                .on_disabled_hover_text(format!("{} already runs {} {}", name, owner, owned));
+        } else if owner.is_some() {
+            if ui.button(format!("Copy the path of {}", name)).clicked() {
+                if let Ok(shim) = shims::shim_path(binary_name, version) {
+                    ui.ctx().output().copied_text = shim.display().to_string();
+                }
+            }
+            if ui.button(format!("Remove shim {}", name)).clicked() {
+                if let Err(e) = shims::remove(binary_name, version) {
+                    *error_message = Some(format!("Removing the shim failed with:\n{}", e));
+                }
+            }
+        } else if ui
+            .button(format!("Create shim {}", name))
+            .on_hover_text("A script that always runs this version")
+            .clicked()
+        {
+            match shims::create(binary_name, version) {
+                Ok(shim) => ui.ctx().output().copied_text = shim.display().to_string(),
+                Err(e) => {
+                    *error_message = Some(format!("Creating the shim failed with:\n{}", e));
+                }
+            }
+        }
+    }
+
+    // protected versions can't be deleted, neither one by one nor in bulk
+    fn render_protection_menu(
+        ui: &mut Ui,
+        binary_name: &str,
+        version: &str,
+        protected: &mut Protected,
+        error_message: &mut Option<String>,
+    ) {
+        let label = if protected.contains(binary_name, version) {
+            "🔓 Lift the protection"
+        } else {
+            "🔒 Protect from deletion"
+        };
+        if ui.button(label).clicked() {
+            if let Err(e) = protected.toggle(binary_name, version) {
+                *error_message = Some(format!("Saving the protected versions failed with:\n{}", e));
+            }
+        }
     }
 
     fn render_search_dialog(
@@ -500,47 +3820,119 @@ impl SdkmanApp {
     ) {
         Window::new("Search").show(ctx, |ui| {
             ui.add_space(PADDING);
+            let mut picked = None;
+            let results = search::search(candidates, candidate_search_term);
             ui.horizontal(|ui| {
                 ui.label("Candidate:");
                 ui.with_layout(Layout::left_to_right(), |ui| {
                     let text_input = ui.text_edit_singleline(candidate_search_term);
                     if text_input.lost_focus() && ui.input().key_pressed(Key::Enter) {
-                        match candidates.iter().find(|candidate| {
-                            candidate.name == *candidate_search_term
-                                || candidate
-                                    .installation_instruction
-                                    .ends_with(candidate_search_term.as_str())
-                        }) {
-                            None => {}
-                            Some(found) => {
-                                match fetch_candidate_versions(&mut found.to_model()) {
-                                    Ok(candidate_with_versions) => {
-                                        *selected_candidate = Some(Candidate::from_model(candidate_with_versions));
-                                    }
-                                    Err(e) => {
-                                        *selected_candidate = None;
-                                        *error_message = Some(format!(
-                                            "Loading all versions for candidate '{}' failed with {}",
-                                            candidate_search_term, e
-                                        ));
-                                    }
-                                }
-                                *candidate_search_dialog = false;
-                                *candidate_search_term = String::default();
-                            }
-                        }
+                        picked = results.first().copied();
                     }
                 });
             });
+            if !candidate_search_term.trim().is_empty() {
+                ui.add_space(PADDING);
+                if results.is_empty() {
+                    ui.label("No candidate matches the name or description.");
+                }
+                for candidate in &results {
+                    if ui
+                        .selectable_label(false, candidate.name())
+                        .on_hover_text(candidate.description())
+                        .clicked()
+                    {
+                        picked = Some(*candidate);
+                    }
+                }
+            }
+            if let Some(found) = picked {
+                match found.load_versions() {
+                    Ok(candidate_with_versions) => {
+                        *selected_candidate = Some(candidate_with_versions);
+                    }
+                    Err(e) => {
+                        *selected_candidate = None;
+                        *error_message = Some(format!(
+                            "Loading all versions for candidate '{}' failed with {}",
+                            found.name(),
+                            e
+                        ));
+                    }
+                }
+                *candidate_search_dialog = false;
+                *candidate_search_term = String::default();
+            }
             ui.add_space(PADDING);
         });
     }
 
-    pub fn render_footer(&self, ctx: &CtxRef) {
+    pub fn render_footer(&mut self, ctx: &CtxRef) {
+        let mut clean_up = false;
         TopBottomPanel::bottom("footer").show(ctx, |ui| {
             ui.vertical_centered(|ui| {
                 ui.add_space(10.);
-                ui.add(Label::new("API: https://api.sdkman.io/2").monospace());
+                // turns while requests or downloads are running
+                let in_flight = trace::in_flight();
+                if in_flight > 0 {
+                    let frame = (ui.input().time * 8.) as usize % NETWORK_FRAMES.len();
+                    ui.add(
+                        Label::new(format!("{} {} in flight", NETWORK_FRAMES[frame], in_flight))
+                            .monospace(),
+                    )
+                    .on_hover_text("Requests and downloads running");
+                    ctx.request_repaint();
+                }
+                if let Some(scan) = self.state.scan_progress() {
+                    ui.horizontal(|ui| {
+                        ui.label(if scan.current.is_empty() {
+                            "Scanning the installed versions…".to_string()
+                        } else {
+                            format!(
+                                "Scanning the installed versions {}/{}: {}",
+                                scan.scanned + 1,
+                                scan.total,
+                                scan.current
+                            )
+                        });
+                        if ui.small_button("Cancel").clicked() {
+                            self.state.cancel_scan();
+                        }
+                    });
+                }
+                if let Some(free) = self.jobs.low_disk_space() {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(
+                            Color32::YELLOW,
+                            format!(
+                                "Low disk space: {} free on the SDKMAN volume",
+                                util::format_size(free)
+                            ),
+                        );
+                        if ui.small_button("Free up space").clicked() {
+                            clean_up = true;
+                        }
+                    });
+                }
+                if let (Some(_), Some(until)) = (&self.scheduled_retry, rate_limited_until()) {
+                    let seconds = until
+                        .duration_since(SystemTime::now())
+                        .unwrap_or_default()
+                        .as_secs()
+                        + 1;
+                    ui.colored_label(
+                        Color32::YELLOW,
+                        format!("Rate limited by the API, retrying in {}s", seconds),
+                    );
+                }
+                ui.add(
+                    Label::new(format!(
+                        "API: {} ({} channel)",
+                        api_url().unwrap_or_else(|_| "unknown".to_string()),
+                        self.config.channel
+                    ))
+                    .monospace(),
+                );
                 ui.add(
                     Hyperlink::new("https://github.com/emilk/egui")
                         .text("Made with egui")
@@ -554,5 +3946,8 @@ impl SdkmanApp {
                 ui.add_space(10.);
             })
         });
+        if clean_up {
+            self.open_cleanup();
+        }
     }
 }
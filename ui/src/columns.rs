@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use eframe::egui::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use api::remote::RemoteVersion;
+
+use crate::util;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Column {
+    Vendor,
+    Version,
+    Dist,
+    Status,
+    Identifier,
+    Size,
+    Released,
+}
+
+impl Column {
+    fn title(&self) -> &'static str {
+        match self {
+            Column::Vendor => "Vendor",
+            Column::Version => "Version",
+            Column::Dist => "Dist",
+            Column::Status => "Status",
+            Column::Identifier => "Identifier",
+            Column::Size => "Size",
+            Column::Released => "Released",
+        }
+    }
+    fn width(&self) -> usize {
+        match self {
+            Column::Vendor => 13,
+            Column::Version => 20,
+            Column::Dist => 10,
+            Column::Status => 18,
+            Column::Identifier => 24,
+            Column::Size => 10,
+            Column::Released => 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnSetting {
+    pub column: Column,
+    pub visible: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnLayout {
+    pub columns: Vec<ColumnSetting>,
+}
+
+impl Default for ColumnLayout {
+    // mirrors the columns shown before the layout became configurable
+    fn default() -> Self {
+        let column = |column, visible| ColumnSetting { column, visible };
+        Self {
+            columns: vec![
+                column(Column::Vendor, true),
+                column(Column::Version, true),
+                column(Column::Status, true),
+                column(Column::Dist, false),
+                column(Column::Identifier, false),
+                column(Column::Size, false),
+                column(Column::Released, false),
+            ],
+        }
+    }
+}
+
+impl ColumnLayout {
+    pub fn is_visible(&self, column: Column) -> bool {
+        self.columns
+            .iter()
+            .any(|setting| setting.column == column && setting.visible)
+    }
+
+    pub fn header(&self) -> String {
+        self.visible_columns()
+            .map(|column| format!("{: <width$}", column.title(), width = column.width()))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    pub fn row(
+        &self,
+        version: &RemoteVersion,
+        local_versions: &HashMap<String, bool>,
+        size: Option<u64>,
+    ) -> String {
+        self.visible_columns()
+            .map(|column| {
+                let cell = cell(column, version, local_versions, size);
+                format!("{: <width$}", cell, width = column.width())
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    // renders the column toggles and reorder buttons; returns true if the layout changed
+    pub fn render_editor(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = false;
+        let mut move_up = None;
+        let count = self.columns.len();
+        for (index, setting) in self.columns.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                if ui
+                    .checkbox(&mut setting.visible, setting.column.title())
+                    .changed()
+                {
+                    changed = true;
+                }
+                ui.with_layout(Layout::right_to_left(), |ui| {
+                    if ui
+                        .add_enabled(index + 1 < count, Button::new("⬇"))
+                        .clicked()
+                    {
+                        move_up = Some(index + 1);
+                    }
+                    if ui.add_enabled(index > 0, Button::new("⬆")).clicked() {
+                        move_up = Some(index);
+                    }
+                });
+            });
+        }
+        if let Some(index) = move_up {
+            self.columns.swap(index - 1, index);
+            changed = true;
+        }
+        changed
+    }
+
+    fn visible_columns(&self) -> impl Iterator<Item = Column> + '_ {
+        self.columns
+            .iter()
+            .filter(|setting| setting.visible)
+            .map(|setting| setting.column)
+    }
+}
+
+fn cell(
+    column: Column,
+    version: &RemoteVersion,
+    local_versions: &HashMap<String, bool>,
+    size: Option<u64>,
+) -> String {
+    match column {
+        Column::Vendor => version.vendor().to_string(),
+        Column::Version => version.version().clone(),
+        Column::Dist => version.dist().to_string(),
+        Column::Status => match local_versions.get(version.id()) {
+            Some(true) => "installed, current".to_string(),
+            Some(false) => "installed".to_string(),
+            None => String::new(),
+        },
+        Column::Identifier => version.id().clone(),
+        Column::Size => size.map(util::format_size).unwrap_or_default(),
+        Column::Released => version
+            .released()
+            .map(|released| released.to_string())
+            .unwrap_or_default(),
+    }
+}
@@ -0,0 +1,107 @@
+use eframe::egui::*;
+
+use api::local::LocalCandidate;
+
+use crate::candidates::Candidate;
+use crate::util;
+
+const PADDING: f32 = 8.0;
+const VERSIONS_HEIGHT: f32 = 300.;
+
+// two candidates side by side, picked one after the other from the list
+#[derive(Default)]
+pub struct Comparison {
+    left: Option<Candidate>,
+    right: Option<Candidate>,
+}
+
+impl Comparison {
+    // the first pick waits for the second one, later picks replace the right side
+    pub fn add(&mut self, candidate: Candidate) {
+        if self.left.is_none() {
+            self.left = Some(candidate);
+        } else {
+            self.right = Some(candidate);
+        }
+    }
+
+    pub fn waiting_for(&self) -> Option<&Candidate> {
+        self.left.as_ref().filter(|_| self.right.is_none())
+    }
+
+    pub fn clear(&mut self) {
+        self.left = None;
+        self.right = None;
+    }
+
+    pub fn render(&mut self, ctx: &CtxRef, local_candidates: &[LocalCandidate]) {
+        let (left, right) = match (&self.left, &self.right) {
+            (Some(left), Some(right)) => (left, right),
+            _ => return,
+        };
+        let mut open = true;
+        Window::new(format!("{} vs {}", left.name(), right.name()))
+            .id(Id::new("comparison"))
+            .open(&mut open)
+            .default_width(800.)
+            .show(ctx, |ui| {
+                ui.columns(2, |columns| {
+                    for (ui, candidate) in columns.iter_mut().zip([left, right]) {
+                        let local_candidate = local_candidates
+                            .iter()
+                            .find(|local| local.binary_name() == candidate.binary_name());
+                        render_side(ui, candidate, local_candidate);
+                    }
+                });
+            });
+        if !open {
+            self.clear();
+        }
+    }
+}
+
+fn render_side(ui: &mut Ui, candidate: &Candidate, local_candidate: Option<&LocalCandidate>) {
+    ui.heading(candidate.name());
+    ui.add_space(PADDING);
+    Grid::new(("comparison_facts", candidate.binary_name()))
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Default:");
+            ui.label(util::strip_parens(candidate.default_version()));
+            ui.end_row();
+            ui.label("Category:");
+            ui.label(candidate.category().to_string());
+            ui.end_row();
+            ui.label("Installed:");
+            ui.label(match local_candidate {
+                Some(local) => format!(
+                    "{} (current: {})",
+                    local.versions().len(),
+                    local.current_version().map_or("none", |v| v.as_str())
+                ),
+                None => "no".to_string(),
+            });
+            ui.end_row();
+            ui.label("Homepage:");
+            ui.hyperlink(candidate.homepage());
+            ui.end_row();
+        });
+    ui.add_space(PADDING);
+    ui.add(Label::new(candidate.description()).wrap(true));
+    ui.add_space(PADDING);
+    ui.label(format!("{} versions", candidate.versions().len()));
+    ScrollArea::vertical()
+        .id_source(("comparison_versions", candidate.binary_name()))
+        .max_height(VERSIONS_HEIGHT)
+        .show(ui, |ui| {
+            for version in candidate.versions() {
+                let marker =
+                    match local_candidate.and_then(|local| local.versions().get(version.id())) {
+                        Some(true) => "➤",
+                        Some(false) => "✔",
+                        None => " ",
+                    };
+                ui.monospace(format!("{} {}", marker, version.id()));
+            }
+        });
+}
@@ -0,0 +1,312 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::Error;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use eframe::egui::CtxRef;
+use eframe::egui::Visuals;
+use serde::Deserialize;
+use serde::Serialize;
+
+use api::cache;
+use api::cache::CacheSettings;
+use api::dirs;
+use api::dirs::SdkmanDirs;
+use api::download;
+use api::download::Mirror;
+use api::install::quirks;
+use api::local;
+use api::manifest;
+use api::manifest::Kind;
+use api::manifest::ManifestError;
+use api::remote;
+use api::remote::Channel;
+use api::security;
+use api::store;
+use api::store::CurrentDetection;
+
+use crate::columns::ColumnLayout;
+use crate::filters::SmartFilter;
+use crate::profiles::Profile;
+use crate::util;
+
+const APP_DIR: &str = ".sdkman-ui";
+const CONFIG_FILE: &str = "config.toml";
+const CACHE_DIR: &str = "cache";
+const SETTINGS_FILE: &str = "sdkman-ui-settings.toml";
+const KEYRING_DIR: &str = "keyring";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl Theme {
+    pub fn apply(&self, ctx: &CtxRef) {
+        match self {
+            Theme::Dark => ctx.set_visuals(Visuals::dark()),
+            Theme::Light => ctx.set_visuals(Visuals::light()),
+        }
+    }
+}
+
+// what double-clicking a version row does
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DoubleClickAction {
+    // install the version if it's missing, make it the current one otherwise
+    #[default]
+    Smart,
+    Install,
+    SetCurrent,
+    CopyIdentifier,
+}
+
+// how the candidates tab is laid out
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaneLayout {
+    // the list, replaced by the versions of a candidate once one is selected
+    #[default]
+    Single,
+    // the list, the versions and the jobs with the log side by side, for wide screens
+    ThreePanes,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub onboarded: bool,
+    pub theme: Theme,
+    pub pinned_candidates: Vec<String>,
+    pub background_refresh: bool,
+    pub refresh_interval_minutes: u64,
+    pub analytics_enabled: bool,
+    pub analytics_url: String,
+    pub show_release_dates: bool,
+    pub catalog_cache_hours: u64,
+    pub versions_cache_minutes: u64,
+    pub max_parallel_downloads: usize,
+    // installs ask first when less than this is free on the SDKMAN volume; 0 never asks
+    pub low_disk_space_mb: u64,
+    pub verify_installs: bool,
+    // checks the signatures of vendors that publish them, see api::security
+    pub verify_signatures: bool,
+    // removes the quarantine attribute from extracted files on macOS
+    pub clear_quarantine: bool,
+    pub channel: Channel,
+    // empty for the platform SDKMAN detected
+    pub platform_override: String,
+    pub double_click_action: DoubleClickAction,
+    // what switching the current version changes, shown before switching
+    pub preview_switches: bool,
+    // directories shown in the projects tab
+    pub projects: Vec<PathBuf>,
+    // started in a project's directory; empty for the system terminal
+    pub terminal_command: String,
+    // the SDKMAN installation to manage; unset for the one the shell points to
+    pub sdkman_dir: Option<PathBuf>,
+    // homepages open in a side panel as text
+    pub homepage_panel: bool,
+    // candidates whose new versions show up in the inbox
+    pub subscriptions: Vec<String>,
+    // what changed in the toolchain, shown on the first launch of a week
+    pub weekly_summary: bool,
+    pub pane_layout: PaneLayout,
+    // a metadata file with the categories and the suggested candidates; empty for the bundled one
+    pub categories_url: String,
+    // the profile switched to last
+    pub active_profile: Option<String>,
+    // how the scan tells the current versions apart; see api::store::CurrentDetection
+    pub current_detection: CurrentDetection,
+    pub scan_timeout_seconds: u64,
+    // tables have to come last for the TOML serialization
+    pub column_layouts: BTreeMap<String, ColumnLayout>,
+    pub download_mirrors: Vec<Mirror>,
+    pub smart_filters: Vec<SmartFilter>,
+    pub profiles: Vec<Profile>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            onboarded: false,
+            theme: Theme::default(),
+            pinned_candidates: Vec::new(),
+            background_refresh: false,
+            refresh_interval_minutes: 60,
+            analytics_enabled: false,
+            analytics_url: String::new(),
+            show_release_dates: false,
+            catalog_cache_hours: 24,
+            versions_cache_minutes: 60,
+            max_parallel_downloads: 2,
+            low_disk_space_mb: 2048,
+            verify_installs: false,
+            verify_signatures: false,
+            clear_quarantine: true,
+            channel: Channel::default(),
+            platform_override: String::new(),
+            double_click_action: DoubleClickAction::default(),
+            preview_switches: true,
+            projects: Vec::new(),
+            terminal_command: String::new(),
+            sdkman_dir: None,
+            homepage_panel: false,
+            subscriptions: Vec::new(),
+            weekly_summary: false,
+            pane_layout: PaneLayout::default(),
+            categories_url: String::new(),
+            active_profile: None,
+            current_detection: CurrentDetection::default(),
+            scan_timeout_seconds: 30,
+            column_layouts: BTreeMap::new(),
+            download_mirrors: Vec::new(),
+            smart_filters: SmartFilter::presets(),
+            profiles: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> Config {
+        util::load_toml(config_path(), "the configuration")
+    }
+
+    // unlike load(), a file that doesn't parse is an error, not the defaults
+    pub fn read() -> std::io::Result<Config> {
+        let content = fs::read_to_string(config_path()?)?;
+        toml::from_str(&content).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        util::save_toml(&config_path()?, self)
+    }
+
+    pub fn configure_installation(&self) {
+        dirs::select(self.sdkman_dir.clone());
+    }
+
+    pub fn configure_scan(&self) {
+        store::set_current_detection(self.current_detection);
+        local::set_scan_timeout(Duration::from_secs(self.scan_timeout_seconds.max(1)));
+    }
+
+    // every installation has a cache of its own, as their platforms may differ
+    pub fn configure_cache(&self) {
+        let installation = SdkmanDirs::resolve()
+            .map(|dirs| dirs.key())
+            .unwrap_or_default();
+        match app_dir() {
+            Ok(dir) => cache::configure(CacheSettings {
+                dir: dir.join(CACHE_DIR).join(installation),
+                catalog_ttl: Duration::from_secs(self.catalog_cache_hours * 3600),
+                versions_ttl: Duration::from_secs(self.versions_cache_minutes * 60),
+            }),
+            Err(e) => log!("Caching is disabled: {}", e),
+        }
+    }
+
+    pub fn configure_downloads(&self) {
+        download::set_max_parallel(self.max_parallel_downloads);
+        download::set_mirrors(self.download_mirrors.clone());
+        security::set_verify_signatures(self.verify_signatures);
+        quirks::set_clear_quarantine(self.clear_quarantine);
+        security::set_keyring_dir(app_dir().map(|dir| dir.join(KEYRING_DIR)).ok());
+    }
+
+    pub fn configure_api(&self) {
+        remote::set_channel(self.channel);
+        remote::set_platform_override(Some(self.platform_override.clone()));
+    }
+
+    // a copy that is safe to share, e.g. in crash reports
+    pub fn redacted(&self) -> Config {
+        let mut config = self.clone();
+        if !config.analytics_url.is_empty() {
+            config.analytics_url = "<redacted>".to_string();
+        }
+        config
+    }
+
+    // the settings worth syncing between machines, e.g. with the dotfiles: no paths of this
+    // machine and no credentials
+    fn portable(&self) -> Config {
+        let mut config = self.clone();
+        config.onboarded = false;
+        config.sdkman_dir = None;
+        config.projects.clear();
+        config.analytics_url.clear();
+        config.download_mirrors = config
+            .download_mirrors
+            .iter()
+            .map(Mirror::without_credentials)
+            .collect();
+        config
+    }
+
+    pub fn export_settings(&self, path: &Path) -> std::io::Result<()> {
+        let content = toml::to_string_pretty(&self.portable())
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        fs::write(path, manifest::header(Kind::Settings) + &content)
+    }
+
+    // the imported settings, with what belongs to this machine kept as it is
+    pub fn import_settings(&self, path: &Path) -> std::io::Result<Config> {
+        let content = fs::read_to_string(path)?;
+        let mut config: Config = manifest::parse_toml(Kind::Settings, &content)
+            .map_err(manifest_error)?
+            .try_into()
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        config.onboarded = self.onboarded;
+        config.sdkman_dir = self.sdkman_dir.clone();
+        config.projects = self.projects.clone();
+        if config.analytics_url.is_empty() {
+            config.analytics_url = self.analytics_url.clone();
+        }
+        Ok(config)
+    }
+
+    pub fn column_layout(&self, binary_name: &str) -> ColumnLayout {
+        self.column_layouts
+            .get(binary_name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn is_pinned(&self, binary_name: &str) -> bool {
+        self.pinned_candidates
+            .iter()
+            .any(|pinned| pinned == binary_name)
+    }
+}
+
+pub fn app_dir() -> std::io::Result<PathBuf> {
+    env::var("HOME")
+        .map(|home| PathBuf::from(home).join(APP_DIR))
+        .map_err(|e| Error::new(ErrorKind::NotFound, e))
+}
+
+// where settings are exported to unless told otherwise, next to the other dotfiles
+pub fn settings_file() -> PathBuf {
+    env::var("HOME")
+        .map(|home| PathBuf::from(home).join(SETTINGS_FILE))
+        .unwrap_or_else(|_| PathBuf::from(SETTINGS_FILE))
+}
+
+// all the validation errors of an imported file in one
+pub fn manifest_error(errors: Vec<ManifestError>) -> Error {
+    let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+    Error::new(ErrorKind::InvalidData, messages.join("\n"))
+}
+
+pub fn config_path() -> std::io::Result<PathBuf> {
+    app_dir().map(|dir| dir.join(CONFIG_FILE))
+}
@@ -0,0 +1,65 @@
+use std::fs;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use crate::config::config_path;
+use crate::config::Config;
+use crate::repaint::RepaintNotifier;
+use crate::tasks;
+
+// there's no file notification that works everywhere, so the modification time is polled
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+// reads the config file again whenever it changes, until dropped
+pub struct ConfigWatch {
+    _stop: Sender<()>,
+    reloaded: Receiver<Config>,
+}
+
+impl ConfigWatch {
+    pub fn spawn(repaint: &RepaintNotifier) -> ConfigWatch {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let (sender, reloaded) = mpsc::channel();
+        let repaint = repaint.clone();
+        tasks::spawn(move || {
+            let mut last = modified();
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(POLL_INTERVAL) {
+                let current = modified();
+                if current == last {
+                    continue;
+                }
+                last = current;
+                // a half-written or broken file is skipped, the next save fixes it
+                match Config::read() {
+                    Ok(config) => {
+                        if sender.send(config).is_err() {
+                            break;
+                        }
+                        repaint.notify();
+                    }
+                    Err(e) => log!("Ignoring the changed configuration: {}", e),
+                }
+            }
+        });
+        ConfigWatch {
+            _stop: stop,
+            reloaded,
+        }
+    }
+
+    // the latest configuration read since the last call
+    pub fn poll(&self) -> Option<Config> {
+        self.reloaded.try_iter().last()
+    }
+}
+
+fn modified() -> Option<SystemTime> {
+    config_path()
+        .and_then(fs::metadata)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
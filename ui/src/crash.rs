@@ -0,0 +1,131 @@
+use std::backtrace::Backtrace;
+use std::env;
+use std::fs;
+use std::panic;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use eframe::egui::*;
+
+use api::dirs::SdkmanDirs;
+use api::remote;
+
+use crate::config::app_dir;
+use crate::config::Config;
+use crate::logs;
+use crate::util;
+
+const CRASH_REPORTS_DIR: &str = "crash-reports";
+const SEEN_MARKER: &str = ".seen";
+const ISSUES_URL: &str = "https://github.com/gerdreiss/sdkman-ui/issues/new";
+const PADDING: f32 = 8.0;
+
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let backtrace = Backtrace::force_capture();
+        match write_report(&info.to_string(), &backtrace.to_string()) {
+            Ok(path) => eprintln!("A crash report has been written to {}", path.display()),
+            Err(e) => eprintln!("Failed to write the crash report: {}", e),
+        }
+        default_hook(info);
+    }));
+}
+
+fn write_report(panic_message: &str, backtrace: &str) -> std::io::Result<PathBuf> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let report_dir = reports_dir()?.join(format!("crash-{}", timestamp));
+    fs::create_dir_all(&report_dir)?;
+
+    fs::write(
+        report_dir.join("panic.txt"),
+        format!("{}\n\n{}", panic_message, backtrace),
+    )?;
+    fs::write(report_dir.join("log.txt"), logs::snapshot().join("\n"))?;
+    fs::write(report_dir.join("platform.txt"), platform_info())?;
+    let config = toml::to_string_pretty(&Config::load().redacted()).unwrap_or_default();
+    fs::write(report_dir.join("config.toml"), config)?;
+
+    Ok(report_dir)
+}
+
+pub fn platform_info() -> String {
+    format!(
+        "sdkman-ui: {} ({})\nos: {}\narch: {}\nSDKMAN_PLATFORM: {}\nSDKMAN_VERSION: {}\n",
+        env!("CARGO_PKG_VERSION"),
+        env!("GIT_HASH"),
+        env::consts::OS,
+        env::consts::ARCH,
+        remote::platform(),
+        sdkman_version().unwrap_or_default(),
+    )
+}
+
+pub fn sdkman_version() -> Option<String> {
+    env::var("SDKMAN_VERSION")
+        .ok()
+        .or_else(|| SdkmanDirs::resolve().ok().and_then(|dirs| dirs.version()))
+}
+
+fn reports_dir() -> std::io::Result<PathBuf> {
+    app_dir().map(|dir| dir.join(CRASH_REPORTS_DIR))
+}
+
+// the most recent crash report the user hasn't been told about yet
+pub fn pending_report() -> Option<PathBuf> {
+    fs::read_dir(reports_dir().ok()?)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && !path.join(SEEN_MARKER).exists())
+        .max()
+}
+
+fn dismiss(report_dir: &Path) {
+    if let Err(e) = fs::write(report_dir.join(SEEN_MARKER), "") {
+        log!("Failed to dismiss the crash report: {}", e);
+    }
+}
+
+// renders the crash dialog; returns false once the dialog should be closed
+pub fn render_dialog(ctx: &CtxRef, report_dir: &Path) -> bool {
+    let mut open = true;
+    Window::new("sdkman-ui crashed")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(Align2::CENTER_CENTER, [0., 0.])
+        .show(ctx, |ui| {
+            ui.add_space(PADDING);
+            ui.label("sdkman-ui crashed the last time it ran. A report has been saved to:");
+            ui.monospace(report_dir.to_string_lossy());
+            ui.add_space(PADDING);
+            ui.label("Please open an issue and attach the files of the report.");
+            ui.label("Check them first, they contain the recent log and your redacted settings.");
+            ui.add_space(PADDING);
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Open issue page").clicked() {
+                    ui.ctx().output().open_url = Some(output::OpenUrl {
+                        url: ISSUES_URL.to_owned(),
+                        new_tab: true,
+                    });
+                }
+                if ui.button("Show report").clicked() {
+                    if let Err(e) = util::open_path(report_dir) {
+                        log!("Failed to open the crash report: {}", e);
+                    }
+                }
+                if ui.button("Dismiss").clicked() {
+                    dismiss(report_dir);
+                    open = false;
+                }
+            });
+            ui.add_space(PADDING);
+        });
+    open
+}
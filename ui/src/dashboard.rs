@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use eframe::egui::*;
+
+use api::archives;
+use api::archives::DeduplicationReport;
+use api::local::*;
+
+use crate::candidates::Candidate;
+use crate::last_used;
+use crate::protected::Protected;
+use crate::repaint::RepaintNotifier;
+use crate::tasks;
+use crate::util;
+
+const PADDING: f32 = 8.0;
+const YELLOW: Color32 = Color32::from_rgb(255, 255, 0);
+// versions nobody ran for this long are suggested for removal
+const UNUSED_AFTER: Duration = Duration::from_secs(90 * 86400);
+
+#[derive(Default)]
+pub struct Dashboard {
+    usages: HashMap<String, LocalCandidateUsage>,
+    receiver: Option<Receiver<Vec<LocalCandidateUsage>>>,
+    loaded: bool,
+    deduplication: Option<Result<DeduplicationReport, String>>,
+    deduplicating: Option<Receiver<Result<DeduplicationReport, String>>>,
+}
+
+impl Dashboard {
+    // computing the disk usage walks every installed version, so it's done once in the background
+    pub fn load(&mut self, local_candidates: &[LocalCandidate], repaint: &RepaintNotifier) {
+        if self.loaded {
+            return;
+        }
+        let binary_names: Vec<String> = local_candidates
+            .iter()
+            .map(|local_candidate| local_candidate.binary_name().clone())
+            .collect();
+        let (sender, receiver) = mpsc::channel();
+        let repaint = repaint.clone();
+        tasks::spawn(move || {
+            let usages = binary_names
+                .iter()
+                .filter_map(|binary_name| match retrieve_candidate_usage(binary_name) {
+                    Ok(mut usage) => {
+                        usage.merge_last_used(|version| last_used::recorded(binary_name, version));
+                        Some(usage)
+                    }
+                    Err(e) => {
+                        log!("Failed to retrieve disk usage of {}: {}", binary_name, e);
+                        None
+                    }
+                })
+                .collect();
+            let _ = sender.send(usages);
+            repaint.notify();
+        });
+        self.receiver = Some(receiver);
+        self.loaded = true;
+    }
+
+    pub fn reload(&mut self, local_candidates: &[LocalCandidate], repaint: &RepaintNotifier) {
+        self.loaded = false;
+        self.load(local_candidates, repaint);
+    }
+
+    // renders the dashboard and returns the binary name of the candidate the user clicked on
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        candidates: &[Candidate],
+        local_candidates: &[LocalCandidate],
+        protected: &Protected,
+        repaint: &RepaintNotifier,
+    ) -> Option<String> {
+        let received = self.receiver.as_ref().and_then(|r| r.try_recv().ok());
+        if let Some(usages) = received {
+            self.usages = usages
+                .into_iter()
+                .map(|usage| (usage.binary_name().clone(), usage))
+                .collect();
+            self.receiver = None;
+        }
+
+        let mut clicked = None;
+
+        ui.add_space(PADDING);
+        ui.horizontal(|ui| {
+            ui.heading("Installed candidates");
+            ui.with_layout(Layout::right_to_left(), |ui| {
+                if ui
+                    .add(Button::new("🔄").text_style(TextStyle::Body))
+                    .on_hover_text("Recalculate disk usage")
+                    .clicked()
+                {
+                    self.reload(local_candidates, repaint);
+                }
+            });
+        });
+        ui.add_space(PADDING);
+
+        if local_candidates.is_empty() {
+            ui.label("No candidates installed yet.");
+            return None;
+        }
+
+        Grid::new("dashboard")
+            .striped(true)
+            .num_columns(5)
+            .spacing([4. * PADDING, PADDING])
+            .show(ui, |ui| {
+                ui.add(Label::new("Candidate").strong());
+                ui.add(Label::new("Current").strong());
+                ui.add(Label::new("Latest").strong());
+                ui.add(Label::new("Disk usage").strong());
+                ui.add(Label::new("Last used").strong());
+                ui.end_row();
+
+                for local_candidate in local_candidates {
+                    let candidate = candidates
+                        .iter()
+                        .find(|c| c.binary_name() == local_candidate.binary_name());
+                    let name = candidate
+                        .map(|c| c.name().as_str())
+                        .unwrap_or_else(|| local_candidate.binary_name().as_str());
+                    let current = local_candidate
+                        .versions()
+                        .iter()
+                        .find(|(_, current)| **current)
+                        .map(|(version, _)| version.as_str());
+                    let latest = candidate.map(|c| util::strip_parens(c.default_version()));
+
+                    if ui
+                        .add(Button::new(name).frame(false))
+                        .on_hover_text("Show all versions")
+                        .clicked()
+                    {
+                        clicked = Some(local_candidate.binary_name().clone());
+                    }
+                    ui.label(current.unwrap_or("-"));
+                    match latest {
+                        _ if candidate.is_some_and(|c| c.is_archived()) => {
+                            ui.label("archived upstream")
+                                .on_hover_text("No longer in the SDKMAN catalog");
+                        }
+                        Some(latest) if current.is_some_and(|c| !c.starts_with(latest)) => {
+                            ui.colored_label(YELLOW, latest)
+                                .on_hover_text("A newer default version is available");
+                        }
+                        Some(latest) => {
+                            ui.label(latest);
+                        }
+                        None => {
+                            ui.label("-");
+                        }
+                    }
+                    match self.usages.get(local_candidate.binary_name()) {
+                        Some(usage) => {
+                            ui.label(util::format_size(usage.disk_usage()));
+                            ui.label(
+                                usage
+                                    .last_used()
+                                    .map(util::format_elapsed)
+                                    .unwrap_or_else(|| "-".to_string()),
+                            );
+                        }
+                        None => {
+                            ui.label("…");
+                            ui.label("…");
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if let Some(binary_name) = self.render_cleanup(ui, protected) {
+            clicked = Some(binary_name);
+        }
+        self.render_archives(ui, repaint);
+        clicked
+    }
+
+    // checksumming the archives takes a while, so it runs in the background
+    fn deduplicate(&mut self, repaint: &RepaintNotifier) {
+        let (sender, receiver) = mpsc::channel();
+        let repaint = repaint.clone();
+        tasks::spawn(move || {
+            let _ = sender.send(archives::deduplicate().map_err(|e| e.to_string()));
+            repaint.notify();
+        });
+        self.deduplication = None;
+        self.deduplicating = Some(receiver);
+    }
+
+    fn render_archives(&mut self, ui: &mut Ui, repaint: &RepaintNotifier) {
+        if let Some(result) = self.deduplicating.as_ref().and_then(|r| r.try_recv().ok()) {
+            if let Ok(report) = &result {
+                log!(
+                    "Deduplicated the downloaded archives, saved {}",
+                    util::format_size(report.saved)
+                );
+            }
+            self.deduplication = Some(result);
+            self.deduplicating = None;
+        }
+
+        ui.add_space(2. * PADDING);
+        ui.heading("Downloaded archives");
+        ui.horizontal(|ui| {
+            ui.label("Archives with the same content can share their disk space.");
+            if ui
+                .add_enabled(self.deduplicating.is_none(), Button::new("Deduplicate"))
+                .on_hover_text("Replace identical archives by links to one of them")
+                .clicked()
+            {
+                self.deduplicate(repaint);
+            }
+        });
+        match &self.deduplication {
+            _ if self.deduplicating.is_some() => {
+                ui.label("Comparing the archives…");
+            }
+            None => {}
+            Some(Err(e)) => {
+                ui.colored_label(YELLOW, format!("Deduplicating failed with:\n{}", e));
+            }
+            Some(Ok(report)) if report.duplicates.is_empty() => {
+                ui.label("No duplicate archives found.");
+            }
+            Some(Ok(report)) => {
+                ui.label(format!(
+                    "Linked {} archives, saved {}:",
+                    report
+                        .duplicates
+                        .iter()
+                        .map(|duplicate| duplicate.linked.len())
+                        .sum::<usize>(),
+                    util::format_size(report.saved)
+                ));
+                for duplicate in &report.duplicates {
+                    for linked in &duplicate.linked {
+                        ui.monospace(format!(
+                            "{} = {}",
+                            file_name(linked),
+                            file_name(&duplicate.kept)
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    // the versions that aren't current or protected and haven't run for a while, the biggest first
+    fn render_cleanup(&self, ui: &mut Ui, protected: &Protected) -> Option<String> {
+        let unused_since = SystemTime::now() - UNUSED_AFTER;
+        let mut unused: Vec<(&String, &LocalVersionUsage)> = self
+            .usages
+            .values()
+            .flat_map(|usage| {
+                usage
+                    .versions()
+                    .iter()
+                    .map(move |version| (usage.binary_name(), version))
+            })
+            .filter(|(binary_name, version)| {
+                !version.current()
+                    && !protected.contains(binary_name, version.version())
+                    && version.last_used().is_none_or(|used| used < unused_since)
+            })
+            .collect();
+        if unused.is_empty() {
+            return None;
+        }
+        unused.sort_by_key(|(_, version)| std::cmp::Reverse(version.disk_usage()));
+
+        let mut clicked = None;
+        ui.add_space(2. * PADDING);
+        ui.heading("Cleanup");
+        ui.label(format!(
+            "{} versions haven't been used for three months, together {}.",
+            unused.len(),
+            util::format_size(unused.iter().map(|(_, version)| version.disk_usage()).sum())
+        ));
+        ui.add_space(PADDING);
+        Grid::new("cleanup")
+            .striped(true)
+            .num_columns(3)
+            .spacing([4. * PADDING, PADDING])
+            .show(ui, |ui| {
+                for (binary_name, version) in unused {
+                    if ui
+                        .add(
+                            Button::new(format!("{} {}", binary_name, version.version()))
+                                .frame(false),
+                        )
+                        .on_hover_text("Show all versions")
+                        .clicked()
+                    {
+                        clicked = Some(binary_name.clone());
+                    }
+                    ui.label(util::format_size(version.disk_usage()));
+                    ui.label(match version.last_used() {
+                        Some(used) => format!("last used {}", util::format_elapsed(used)),
+                        None => "never used".to_string(),
+                    });
+                    ui.end_row();
+                }
+            });
+        clicked
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
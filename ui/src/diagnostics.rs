@@ -0,0 +1,108 @@
+use std::fs;
+use std::io::Error;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use api::cache;
+use api::local::LocalCandidate;
+
+use crate::config::app_dir;
+use crate::config::Config;
+use crate::crash;
+use crate::logs;
+
+const DIAGNOSTICS_DIR: &str = "diagnostics";
+
+// bundles what's needed to reproduce a parsing or merging problem into a zip file
+// next to the crash reports and returns its path
+pub fn export(config: &Config, local_candidates: &[LocalCandidate]) -> std::io::Result<PathBuf> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let dir = app_dir()?.join(DIAGNOSTICS_DIR);
+    let name = format!("diagnostics-{}", timestamp);
+    let staging = dir.join(&name);
+    fs::create_dir_all(&staging)?;
+
+    let config = toml::to_string_pretty(&config.redacted()).unwrap_or_default();
+    fs::write(staging.join("config.toml"), config)?;
+    fs::write(staging.join("platform.txt"), crash::platform_info())?;
+    fs::write(staging.join("log.txt"), logs::snapshot().join("\n"))?;
+    fs::write(
+        staging.join("local-candidates.txt"),
+        describe_local_candidates(local_candidates),
+    )?;
+    if let Some(cache_dir) = cache::dir().filter(|dir| dir.is_dir()) {
+        copy_dir(&cache_dir, &staging.join("cache"))?;
+    }
+
+    let archive = dir.join(format!("{}.zip", name));
+    zip(&dir, &name, &archive)?;
+    fs::remove_dir_all(&staging)?;
+    Ok(archive)
+}
+
+// what the scan of the candidates directory found, as "candidate: version, *current"
+fn describe_local_candidates(local_candidates: &[LocalCandidate]) -> String {
+    let mut lines: Vec<String> = local_candidates
+        .iter()
+        .map(|local| {
+            let mut versions: Vec<String> = local
+                .versions()
+                .iter()
+                .map(|(version, current)| {
+                    if *current {
+                        format!("*{}", version)
+                    } else {
+                        version.clone()
+                    }
+                })
+                .collect();
+            versions.sort();
+            format!("{}: {}", local.binary_name(), versions.join(", "))
+        })
+        .collect();
+    lines.sort();
+    lines.join("\n")
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+// the zip tool isn't installed on Windows, but its bsdtar writes zip files as well
+fn zip(dir: &Path, name: &str, archive: &Path) -> std::io::Result<()> {
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("tar");
+        command.arg("-a").arg("-cf").arg(archive).arg(name);
+        command
+    } else {
+        let mut command = Command::new("zip");
+        command.arg("-qr").arg(archive).arg(name);
+        command
+    };
+    let status = command.current_dir(dir).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::other(format!(
+            "creating {} exited with {}",
+            archive.display(),
+            status
+        )))
+    }
+}
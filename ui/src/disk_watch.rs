@@ -0,0 +1,55 @@
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+
+use api::dirs::SdkmanDirs;
+use api::disk;
+
+use crate::repaint::RepaintNotifier;
+use crate::tasks;
+
+// downloads fill the volume in minutes, not seconds
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+// measures the free space on the volume of the SDKMAN installation, until dropped
+pub struct DiskWatch {
+    _stop: Sender<()>,
+    measured: Receiver<u64>,
+}
+
+impl DiskWatch {
+    pub fn spawn(repaint: &RepaintNotifier) -> DiskWatch {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let (sender, measured) = mpsc::channel();
+        let repaint = repaint.clone();
+        tasks::spawn(move || loop {
+            // resolved every time, the installation may have been switched in the meantime
+            match SdkmanDirs::resolve().and_then(|dirs| disk::free_space(&dirs.root)) {
+                Ok(free) => {
+                    if sender.send(free).is_err() {
+                        break;
+                    }
+                    repaint.notify();
+                }
+                Err(e) => {
+                    log!("Failed to measure the free disk space: {}", e);
+                    break;
+                }
+            }
+            if let Err(RecvTimeoutError::Disconnected) = stopped.recv_timeout(POLL_INTERVAL) {
+                break;
+            }
+        });
+        DiskWatch {
+            _stop: stop,
+            measured,
+        }
+    }
+
+    // the latest free space measured since the last call
+    pub fn poll(&self) -> Option<u64> {
+        self.measured.try_iter().last()
+    }
+}
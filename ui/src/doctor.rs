@@ -0,0 +1,336 @@
+use std::env;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+
+use eframe::egui::*;
+
+use api::local;
+use api::local::LocalCandidate;
+use api::names;
+use api::names::NameProblem;
+
+use crate::repaint::RepaintNotifier;
+use crate::shims;
+use crate::tasks;
+
+const PADDING: f32 = 8.0;
+const YELLOW: Color32 = Color32::from_rgb(255, 255, 0);
+
+// an executable of a current version that another installation comes before on the PATH
+pub struct Shadowed {
+    binary_name: String,
+    executable: String,
+    found: PathBuf,
+    managed: PathBuf,
+}
+
+impl Shadowed {
+    fn source(&self) -> &'static str {
+        let found = self.found.to_string_lossy();
+        if found.contains("/homebrew/") || found.contains("/Cellar/") || found.contains("linuxbrew")
+        {
+            "Homebrew"
+        } else if found.starts_with("/usr/") || found.starts_with("/bin/") {
+            "the system"
+        } else {
+            "another installation"
+        }
+    }
+
+    // the line to add to the shell profile, after the one initializing SDKMAN
+    fn fix(&self) -> String {
+        format!("export PATH=\"{}:$PATH\"", self.managed.display())
+    }
+}
+
+// an installed version whose directory name is likely to cause trouble
+pub struct OddName {
+    binary_name: String,
+    version: String,
+    problem: NameProblem,
+    // the name to rename it to, unless another version has it already
+    rename_to: Option<String>,
+}
+
+pub enum DoctorOutcome {
+    Rename {
+        binary_name: String,
+        version: String,
+        to: String,
+    },
+}
+
+pub struct Diagnosis {
+    path: Vec<PathBuf>,
+    // whether the PATH came from a login shell or only from the environment the app started in
+    from_shell: bool,
+    shadowed: Vec<Shadowed>,
+    // current versions whose bin directory isn't on the PATH at all
+    missing: Vec<(String, PathBuf)>,
+    odd_names: Vec<OddName>,
+}
+
+// checks whether the versions SDKMAN makes current are the ones a shell actually runs
+#[derive(Default)]
+pub struct Doctor {
+    diagnosis: Option<Diagnosis>,
+    receiver: Option<Receiver<Diagnosis>>,
+}
+
+impl Doctor {
+    // starting a shell takes a moment, so the diagnosis runs in the background
+    pub fn load(&mut self, local_candidates: &[LocalCandidate], repaint: &RepaintNotifier) {
+        if self.receiver.is_some() {
+            return;
+        }
+        let current: Vec<(String, String)> = local_candidates
+            .iter()
+            .filter_map(|local_candidate| {
+                local_candidate
+                    .versions()
+                    .iter()
+                    .find(|(_, current)| **current)
+                    .map(|(version, _)| (local_candidate.binary_name().clone(), version.clone()))
+            })
+            .collect();
+        let odd_names = odd_names(local_candidates);
+        let (sender, receiver) = mpsc::channel();
+        let repaint = repaint.clone();
+        tasks::spawn(move || {
+            let _ = sender.send(Diagnosis {
+                odd_names,
+                ..diagnose(&current)
+            });
+            repaint.notify();
+        });
+        self.receiver = Some(receiver);
+    }
+
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        local_candidates: &[LocalCandidate],
+        repaint: &RepaintNotifier,
+    ) -> Option<DoctorOutcome> {
+        if let Some(diagnosis) = self.receiver.as_ref().and_then(|r| r.try_recv().ok()) {
+            self.diagnosis = Some(diagnosis);
+            self.receiver = None;
+        }
+
+        ui.add_space(PADDING);
+        ui.horizontal(|ui| {
+            ui.heading("Doctor");
+            if ui
+                .add_enabled(self.receiver.is_none(), Button::new("⟳"))
+                .on_hover_text("Check again")
+                .clicked()
+            {
+                self.load(local_candidates, repaint);
+            }
+        });
+        ui.add_space(PADDING);
+
+        let diagnosis = match &self.diagnosis {
+            Some(diagnosis) => diagnosis,
+            None => {
+                ui.label("Checking the PATH…");
+                return None;
+            }
+        };
+        if !diagnosis.from_shell {
+            ui.label("Your login shell couldn't be asked for its PATH, so the PATH sdkman-ui was started with is checked instead.");
+            ui.add_space(PADDING);
+        }
+        if diagnosis.shadowed.is_empty() && diagnosis.missing.is_empty() {
+            ui.label("✔ Your shell runs the current versions SDKMAN manages.");
+        }
+        for shadowed in &diagnosis.shadowed {
+            ui.separator();
+            ui.colored_label(
+                YELLOW,
+                format!(
+                    "{} of {} from {} comes first on the PATH",
+                    shadowed.executable,
+                    shadowed.binary_name,
+                    shadowed.source()
+                ),
+            );
+            ui.monospace(shadowed.found.display().to_string());
+            ui.label("Add this line to your shell profile after the one initializing SDKMAN:");
+            ui.horizontal(|ui| {
+                ui.monospace(shadowed.fix());
+                if ui.button("📋").on_hover_text("Copy").clicked() {
+                    ui.ctx().output().copied_text = shadowed.fix();
+                }
+            });
+        }
+        for (binary_name, bin) in &diagnosis.missing {
+            ui.separator();
+            ui.colored_label(
+                YELLOW,
+                format!("The current {} isn't on the PATH", binary_name),
+            );
+            ui.monospace(bin.display().to_string());
+            ui.label("Check that your shell profile initializes SDKMAN.");
+        }
+        let mut outcome = None;
+        for odd_name in &diagnosis.odd_names {
+            ui.separator();
+            ui.colored_label(
+                YELLOW,
+                format!(
+                    "{} {} {}",
+                    odd_name.binary_name, odd_name.version, odd_name.problem
+                ),
+            );
+            match &odd_name.rename_to {
+                Some(to) => {
+                    if ui.button(format!("Rename to {}", to)).clicked() {
+                        outcome = Some(DoctorOutcome::Rename {
+                            binary_name: odd_name.binary_name.clone(),
+                            version: odd_name.version.clone(),
+                            to: to.clone(),
+                        });
+                    }
+                }
+                None if matches!(odd_name.problem, NameProblem::CaseDuplicate(_)) => {
+                    ui.label("Remove one of them, reinstalling a version gives it the usual name.");
+                }
+                None => {
+                    ui.label("It can't be renamed automatically, rename or reinstall it yourself.");
+                }
+            }
+        }
+        ui.add_space(2. * PADDING);
+        CollapsingHeader::new("PATH").show(ui, |ui| {
+            for dir in &diagnosis.path {
+                ui.monospace(dir.display().to_string());
+            }
+        });
+        // the next check finds it again if renaming fails
+        if let (
+            Some(DoctorOutcome::Rename {
+                binary_name,
+                version,
+                ..
+            }),
+            Some(diagnosis),
+        ) = (&outcome, &mut self.diagnosis)
+        {
+            diagnosis.odd_names.retain(|odd_name| {
+                odd_name.binary_name != *binary_name || odd_name.version != *version
+            });
+        }
+        outcome
+    }
+}
+
+// version directories differing only by case or with unusual characters, mostly copied by hand
+fn odd_names(local_candidates: &[LocalCandidate]) -> Vec<OddName> {
+    let mut odd_names = Vec::new();
+    for local_candidate in local_candidates {
+        let versions = local_candidate.versions();
+        for (version, problem) in names::check_versions(versions.keys()) {
+            let to = match &problem {
+                NameProblem::CaseDuplicate(_) => version.to_lowercase(),
+                NameProblem::Unicode(_) => names::normalize(&version),
+            };
+            let unchanged = to == version;
+            let rename_to = if unchanged || to.is_empty() || versions.contains_key(&to) {
+                None
+            } else {
+                Some(to)
+            };
+            // of two names differing by case, only the one that isn't lowercase needs a hint
+            if rename_to.is_none() && matches!(problem, NameProblem::CaseDuplicate(_)) && unchanged
+            {
+                continue;
+            }
+            odd_names.push(OddName {
+                binary_name: local_candidate.binary_name().clone(),
+                version,
+                problem,
+                rename_to,
+            });
+        }
+    }
+    odd_names
+}
+
+fn diagnose(current: &[(String, String)]) -> Diagnosis {
+    let (path, from_shell) = match shell_path() {
+        Some(path) => (path, true),
+        None => (
+            env::var_os("PATH")
+                .map(|path| env::split_paths(&path).collect())
+                .unwrap_or_default(),
+            false,
+        ),
+    };
+    let mut shadowed = Vec::new();
+    let mut missing = Vec::new();
+    for (binary_name, version) in current {
+        let managed = match local::candidate_path(binary_name) {
+            Ok(candidate) => candidate.join("current").join("bin"),
+            Err(_) => continue,
+        };
+        let executable = match local::home_path(binary_name, version)
+            .and_then(|home| shims::executable(&home.join("bin"), binary_name))
+        {
+            Ok(executable) => executable,
+            Err(_) => continue,
+        };
+        let name = executable.file_name().unwrap_or_default();
+        match path
+            .iter()
+            .map(|dir| dir.join(name))
+            .find(|candidate| candidate.is_file())
+        {
+            Some(found) if !is_managed(&found, &managed, &executable) => {
+                shadowed.push(Shadowed {
+                    binary_name: binary_name.clone(),
+                    executable: name.to_string_lossy().to_string(),
+                    found,
+                    managed,
+                });
+            }
+            Some(_) => {}
+            None => missing.push((binary_name.clone(), managed)),
+        }
+    }
+    Diagnosis {
+        path,
+        from_shell,
+        shadowed,
+        missing,
+        odd_names: Vec::new(),
+    }
+}
+
+// the current link, the version it points to, or a symlink to either
+fn is_managed(found: &Path, managed: &Path, executable: &Path) -> bool {
+    found.starts_with(managed) || found.canonicalize().ok() == executable.canonicalize().ok()
+}
+
+// the PATH of an interactive login shell, which is where the profile initializes SDKMAN
+fn shell_path() -> Option<Vec<PathBuf>> {
+    let shell = env::var("SHELL").ok()?;
+    let output = Command::new(shell)
+        .args(["-lic", "printf '%s' \"$PATH\""])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // the profile may print something itself, the PATH comes last
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let path = stdout.lines().last()?.trim();
+    if path.is_empty() {
+        None
+    } else {
+        Some(env::split_paths(path).collect())
+    }
+}
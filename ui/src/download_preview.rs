@@ -0,0 +1,121 @@
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+
+use eframe::egui::*;
+
+use api::download;
+use api::download::DownloadPreview;
+
+use crate::repaint::RepaintNotifier;
+use crate::tasks;
+use crate::util;
+
+const PADDING: f32 = 8.0;
+
+pub enum DownloadPreviewOutcome {
+    Open,
+    Install,
+    Close,
+}
+
+// where a version would be downloaded from, resolved the way an installation resolves it
+pub struct DownloadPreviewWindow {
+    pub binary_name: String,
+    pub version: String,
+    preview: Option<Result<DownloadPreview, String>>,
+    receiver: Option<Receiver<Result<DownloadPreview, String>>>,
+}
+
+impl DownloadPreviewWindow {
+    pub fn new(binary_name: &str, version: &str) -> DownloadPreviewWindow {
+        DownloadPreviewWindow {
+            binary_name: binary_name.to_string(),
+            version: version.to_string(),
+            preview: None,
+            receiver: None,
+        }
+    }
+
+    fn resolve(&mut self, repaint: &RepaintNotifier) {
+        let (sender, receiver) = mpsc::channel();
+        let binary_name = self.binary_name.clone();
+        let version = self.version.clone();
+        let repaint = repaint.clone();
+        tasks::spawn(move || {
+            let _ =
+                sender.send(download::preview(&binary_name, &version).map_err(|e| e.to_string()));
+            repaint.notify();
+        });
+        self.receiver = Some(receiver);
+    }
+
+    pub fn render(&mut self, ctx: &CtxRef, repaint: &RepaintNotifier) -> DownloadPreviewOutcome {
+        if self.preview.is_none() && self.receiver.is_none() {
+            self.resolve(repaint);
+        }
+        if let Some(preview) = self.receiver.as_ref().and_then(|r| r.try_recv().ok()) {
+            self.preview = Some(preview);
+            self.receiver = None;
+        }
+        let mut outcome = DownloadPreviewOutcome::Open;
+        let mut open = true;
+        Window::new(format!("Download of {} {}", self.binary_name, self.version))
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                match &self.preview {
+                    None => {
+                        ui.label("Following the redirect of the SDKMAN broker…");
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(
+                            Color32::YELLOW,
+                            format!("Resolving the download failed with:\n{}", e),
+                        );
+                    }
+                    Some(Ok(preview)) => {
+                        Grid::new("download_preview")
+                            .num_columns(2)
+                            .spacing([4. * PADDING, PADDING])
+                            .show(ui, |ui| {
+                                ui.label("Host:");
+                                ui.strong(&preview.host);
+                                ui.end_row();
+                                ui.label("URL:");
+                                ui.monospace(&preview.url);
+                                ui.end_row();
+                                ui.label("Size:");
+                                ui.label(
+                                    preview
+                                        .size
+                                        .map_or_else(|| "unknown".to_string(), util::format_size),
+                                );
+                                ui.end_row();
+                                if !preview.mirrors.is_empty() {
+                                    ui.label("Mirrors:");
+                                    ui.monospace(preview.mirrors.join("\n"));
+                                    ui.end_row();
+                                }
+                            });
+                        if ui.button("Copy the URL").clicked() {
+                            ui.ctx().output().copied_text = preview.url.clone();
+                        }
+                    }
+                }
+                ui.add_space(PADDING);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Install").clicked() {
+                        outcome = DownloadPreviewOutcome::Install;
+                    }
+                    if ui.button("Close").clicked() {
+                        outcome = DownloadPreviewOutcome::Close;
+                    }
+                });
+            });
+        if !open {
+            outcome = DownloadPreviewOutcome::Close;
+        }
+        outcome
+    }
+}
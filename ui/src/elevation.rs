@@ -0,0 +1,121 @@
+use std::env;
+use std::process::Command;
+
+use api::dirs::SdkmanDirs;
+
+use crate::history::Action;
+use crate::jobs::Step;
+
+// pkexec's exit codes for a dismissed and a refused authentication
+const PKEXEC_DISMISSED: i32 = 126;
+const PKEXEC_NOT_AUTHORIZED: i32 = 127;
+const INIT_SCRIPT: &str = "bin/sdkman-init.sh";
+
+// the sdk command doing the same as the step, for a terminal
+pub fn sudo_command(step: &Step) -> Result<String, String> {
+    Ok(format!(
+        "sudo bash -c {}",
+        quote(&sdk_script(step, false)?)?
+    ))
+}
+
+// macOS always comes with its authorization dialog, Linux needs polkit's pkexec
+pub fn available() -> bool {
+    cfg!(target_os = "macos") || on_path("pkexec")
+}
+
+// runs the step as root through the sdk command of the installation, as the app's own code
+// can't be elevated; the system asks for the password
+pub fn run(step: &Step) -> Result<(), String> {
+    if step.archive.is_some() {
+        return Err("archives can't be installed with administrator rights".to_string());
+    }
+    let script = sdk_script(step, true)?;
+    let output = if cfg!(target_os = "macos") {
+        Command::new("osascript")
+            .arg("-e")
+            .arg(format!(
+                "do shell script \"{}\" with prompt \"sdkman-ui wants to {}.\" with administrator privileges",
+                script.replace('\\', "\\\\").replace('"', "\\\""),
+                step
+            ))
+            .output()
+    } else {
+        Command::new("pkexec").args(["bash", "-c", &script]).output()
+    }
+    .map_err(|e| e.to_string())?;
+
+    match output.status.code() {
+        Some(0) => Ok(()),
+        Some(PKEXEC_DISMISSED) if !cfg!(target_os = "macos") => {
+            Err("the authentication was cancelled".to_string())
+        }
+        Some(PKEXEC_NOT_AUTHORIZED) if !cfg!(target_os = "macos") => {
+            Err("not authorized to change the installation".to_string())
+        }
+        _ => Err(format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        )
+        .trim()
+        .to_string()),
+    }
+}
+
+// sdk is a shell function, so a root shell has to load SDKMAN first;
+// nobody is there to answer its questions when it runs in the background
+fn sdk_script(step: &Step, unattended: bool) -> Result<String, String> {
+    // the names come from files like a project's .sdkmanrc and end up in a root shell
+    for value in [&step.binary_name, &step.version] {
+        if !is_plain(value) {
+            return Err(format!("'{}' can't be passed to sdk as root", value));
+        }
+    }
+    let (dir, init) = match SdkmanDirs::resolve() {
+        Ok(dirs) => (
+            quote(&dirs.root.to_string_lossy())?,
+            quote(&dirs.root.join(INIT_SCRIPT).to_string_lossy())?,
+        ),
+        Err(_) => (
+            "\"$SDKMAN_DIR\"".to_string(),
+            format!("\"$SDKMAN_DIR/{}\"", INIT_SCRIPT),
+        ),
+    };
+    let sdk = match step.action {
+        Action::Install => "install",
+        Action::Delete => "uninstall",
+        Action::SetCurrent => "default",
+    };
+    Ok(format!(
+        "export SDKMAN_DIR={} && source {} && {}sdk {} {} {}",
+        dir,
+        init,
+        if unattended {
+            "sdkman_auto_answer=true && "
+        } else {
+            ""
+        },
+        sdk,
+        quote(&step.binary_name)?,
+        quote(&step.version)?
+    ))
+}
+
+fn is_plain(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '+' | '-'))
+}
+
+fn quote(value: &str) -> Result<String, String> {
+    shlex::try_quote(value)
+        .map(|quoted| quoted.to_string())
+        .map_err(|e| e.to_string())
+}
+
+fn on_path(program: &str) -> bool {
+    env::var_os("PATH")
+        .is_some_and(|path| env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+}
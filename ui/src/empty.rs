@@ -0,0 +1,59 @@
+use eframe::egui::*;
+
+const TROUBLESHOOTING_URL: &str = "https://github.com/gerdreiss/sdkman-ui#troubleshooting";
+const PADDING: f32 = 8.0;
+
+pub enum EmptyState<'a> {
+    FetchFailed(&'a str),
+    NothingInstalled,
+    NoCandidates,
+    // the platform the versions were asked for
+    NoVersions(&'a str),
+}
+
+impl EmptyState<'_> {
+    fn title(&self) -> &'static str {
+        match self {
+            EmptyState::FetchFailed(_) => "The candidates could not be loaded",
+            EmptyState::NothingInstalled => "No installed candidates",
+            EmptyState::NoCandidates => "No candidates to show",
+            EmptyState::NoVersions(_) => "No versions available",
+        }
+    }
+    fn reason(&self) -> String {
+        match self {
+            EmptyState::FetchFailed(error) => error.to_string(),
+            EmptyState::NothingInstalled => {
+                "None of the candidates is installed locally yet.".to_string()
+            }
+            EmptyState::NoCandidates => "The candidate list came back empty.".to_string(),
+            EmptyState::NoVersions(platform) => format!(
+                "This candidate has no versions for the platform '{}'. \
+                 Some candidates are only built for a few platforms; \
+                 set a platform override in the settings to browse the versions of another one, \
+                 e.g. darwinx64 on Apple silicon, whose builds run under Rosetta.",
+                platform
+            ),
+        }
+    }
+}
+
+// renders the panel shown instead of an empty list; returns true if a retry was requested
+pub fn render(ui: &mut Ui, state: &EmptyState) -> bool {
+    let mut retry = false;
+    ui.vertical_centered(|ui| {
+        ui.add_space(PADDING * 4.);
+        ui.heading(state.title());
+        ui.add_space(PADDING);
+        ui.add(Label::new(state.reason()).wrap(true));
+        ui.add_space(PADDING * 2.);
+        let label = match state {
+            EmptyState::NothingInstalled => "Show all candidates",
+            _ => "Retry",
+        };
+        retry = ui.button(label).clicked();
+        ui.add_space(PADDING);
+        ui.add(Hyperlink::new(TROUBLESHOOTING_URL).text("Troubleshooting"));
+    });
+    retry
+}
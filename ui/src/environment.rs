@@ -0,0 +1,149 @@
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use eframe::egui::*;
+
+use api::local::LocalCandidate;
+use api::manifest;
+use api::sdkman;
+
+use crate::history::Action;
+use crate::jobs::Jobs;
+use crate::jobs::Step;
+
+pub const SDKMANRC: &str = ".sdkmanrc";
+const PADDING: f32 = 8.0;
+
+struct EnvironmentEntry {
+    binary_name: String,
+    version: String,
+    // missing versions are selected for installation
+    selected: bool,
+}
+
+pub enum EnvironmentOutcome {
+    Open,
+    Install(Vec<Step>),
+    Close,
+}
+
+// the versions a project's .sdkmanrc asks for, compared with the installed ones
+pub struct Environment {
+    path: PathBuf,
+    entries: Vec<EnvironmentEntry>,
+    // the lines that were skipped and why
+    problems: Vec<String>,
+}
+
+impl Environment {
+    pub fn load(path: &Path, local_candidates: &[LocalCandidate]) -> std::io::Result<Environment> {
+        let content = fs::read_to_string(path)?;
+        let (entries, problems) = manifest::validate_sdkmanrc(&content);
+        let entries = entries
+            .into_iter()
+            .map(|(binary_name, version)| EnvironmentEntry {
+                selected: !is_installed(local_candidates, &binary_name, &version),
+                binary_name,
+                version,
+            })
+            .collect();
+        Ok(Environment {
+            path: path.to_path_buf(),
+            entries,
+            problems: problems.iter().map(ToString::to_string).collect(),
+        })
+    }
+
+    pub fn render(
+        &mut self,
+        ctx: &CtxRef,
+        local_candidates: &[LocalCandidate],
+        jobs: &Jobs,
+    ) -> EnvironmentOutcome {
+        let mut outcome = EnvironmentOutcome::Open;
+        let mut open = true;
+        Window::new("Environment")
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.add_space(PADDING);
+                ui.monospace(self.path.display().to_string());
+                ui.add_space(PADDING);
+                if self.entries.is_empty() {
+                    ui.label("The file doesn't ask for any versions.");
+                }
+                for problem in &self.problems {
+                    ui.colored_label(Color32::YELLOW, format!("Skipped: {}", problem));
+                }
+                Grid::new("environment")
+                    .striped(true)
+                    .spacing([2. * PADDING, PADDING])
+                    .show(ui, |ui| {
+                        for entry in &mut self.entries {
+                            let step =
+                                Step::new(&entry.binary_name, &entry.version, Action::Install);
+                            if is_installed(local_candidates, &entry.binary_name, &entry.version) {
+                                ui.label("✔");
+                                ui.label(format!("{} {}", entry.binary_name, entry.version));
+                                ui.label("installed");
+                            } else if jobs.is_pending(&step) {
+                                ui.label("…");
+                                ui.label(format!("{} {}", entry.binary_name, entry.version));
+                                ui.label("installing");
+                            } else {
+                                ui.checkbox(&mut entry.selected, "");
+                                ui.colored_label(
+                                    Color32::YELLOW,
+                                    format!("{} {}", entry.binary_name, entry.version),
+                                );
+                                ui.label("missing");
+                            }
+                            ui.end_row();
+                        }
+                    });
+                ui.add_space(PADDING);
+                ui.separator();
+                let steps: Vec<Step> = self
+                    .entries
+                    .iter()
+                    .filter(|entry| {
+                        entry.selected
+                            && !is_installed(local_candidates, &entry.binary_name, &entry.version)
+                    })
+                    .map(|entry| Step::new(&entry.binary_name, &entry.version, Action::Install))
+                    // clicking again while they are installed would install them twice
+                    .filter(|step| !jobs.is_pending(step))
+                    .collect();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            !steps.is_empty(),
+                            Button::new(format!("Install {} missing", steps.len())),
+                        )
+                        .clicked()
+                    {
+                        outcome = EnvironmentOutcome::Install(steps.clone());
+                    }
+                    if ui.button("Close").clicked() {
+                        outcome = EnvironmentOutcome::Close;
+                    }
+                });
+            });
+        if !open {
+            outcome = EnvironmentOutcome::Close;
+        }
+        outcome
+    }
+}
+
+pub fn parse(content: &str) -> Vec<(String, String)> {
+    sdkman::parse_sdkmanrc(content)
+}
+
+pub fn is_installed(local_candidates: &[LocalCandidate], binary_name: &str, version: &str) -> bool {
+    local_candidates
+        .iter()
+        .find(|local| local.binary_name() == binary_name)
+        .is_some_and(|local| local.versions().contains_key(version))
+}
@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+
+use api::local::sdk_home;
+use api::local::LocalCandidate;
+use api::remote::RemoteCandidate;
+use api::remote::RemoteVersion;
+
+use crate::util;
+
+const CSV_HEADER: &str = "candidate,identifier,vendor,version,dist,installed,current,released,home";
+const CATALOG_HEADER: [&str; 6] = [
+    "name",
+    "binary",
+    "default version",
+    "homepage",
+    "installed",
+    "current",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CatalogFormat {
+    Csv,
+    Markdown,
+}
+
+impl CatalogFormat {
+    pub fn parse(name: &str) -> Option<CatalogFormat> {
+        match name {
+            "csv" => Some(CatalogFormat::Csv),
+            "markdown" | "md" => Some(CatalogFormat::Markdown),
+            _ => None,
+        }
+    }
+
+    pub fn render(self, candidates: &[RemoteCandidate], local: &[LocalCandidate]) -> String {
+        let rows = catalog_rows(candidates, local);
+        match self {
+            CatalogFormat::Csv => catalog_to_csv(&rows),
+            CatalogFormat::Markdown => catalog_to_markdown(&rows),
+        }
+    }
+}
+
+// one line per candidate of the catalog, with the versions installed of it
+fn catalog_rows(candidates: &[RemoteCandidate], local: &[LocalCandidate]) -> Vec<[String; 6]> {
+    candidates
+        .iter()
+        .map(|candidate| {
+            let local_candidate = local
+                .iter()
+                .find(|local| local.binary_name() == candidate.binary_name());
+            let mut installed: Vec<&String> = local_candidate
+                .map(|local| local.versions().keys().collect())
+                .unwrap_or_default();
+            installed.sort_by(|v1, v2| alphanumeric_sort::compare_str(v1, v2));
+            [
+                candidate.name().clone(),
+                candidate.binary_name().clone(),
+                util::strip_parens(candidate.default_version()).to_string(),
+                candidate.homepage().clone(),
+                installed
+                    .iter()
+                    .map(|version| version.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(" "),
+                local_candidate
+                    .and_then(LocalCandidate::current_version)
+                    .cloned()
+                    .unwrap_or_default(),
+            ]
+        })
+        .collect()
+}
+
+fn catalog_to_csv(rows: &[[String; 6]]) -> String {
+    let mut lines = vec![CATALOG_HEADER.join(",")];
+    for row in rows {
+        lines.push(
+            row.iter()
+                .map(|field| csv_field(field))
+                .collect::<Vec<String>>()
+                .join(","),
+        );
+    }
+    lines.join("\n")
+}
+
+// a GitHub flavored table, e.g. for a team wiki
+fn catalog_to_markdown(rows: &[[String; 6]]) -> String {
+    let line = |fields: Vec<String>| format!("| {} |", fields.join(" | "));
+    let mut lines = vec![
+        line(
+            CATALOG_HEADER
+                .iter()
+                .map(|field| field.to_string())
+                .collect(),
+        ),
+        line(CATALOG_HEADER.iter().map(|_| "---".to_string()).collect()),
+    ];
+    for row in rows {
+        lines.push(line(
+            row.iter().map(|field| markdown_field(field)).collect(),
+        ));
+    }
+    lines.join("\n")
+}
+
+pub fn versions_to_csv(
+    binary_name: &str,
+    versions: &[RemoteVersion],
+    local_versions: &HashMap<String, bool>,
+) -> String {
+    let mut lines = vec![CSV_HEADER.to_string()];
+    for version in versions {
+        let fields = [
+            binary_name.to_string(),
+            version.id().clone(),
+            version.vendor().to_string(),
+            version.version().clone(),
+            version.dist().to_string(),
+            local_versions.contains_key(version.id()).to_string(),
+            (*local_versions.get(version.id()).unwrap_or(&false)).to_string(),
+            version
+                .released()
+                .map(|released| released.to_string())
+                .unwrap_or_default(),
+            home(binary_name, version, local_versions).unwrap_or_default(),
+        ];
+        lines.push(
+            fields
+                .iter()
+                .map(|field| csv_field(field))
+                .collect::<Vec<String>>()
+                .join(","),
+        );
+    }
+    lines.join("\n")
+}
+
+pub fn versions_to_json(
+    binary_name: &str,
+    versions: &[RemoteVersion],
+    local_versions: &HashMap<String, bool>,
+) -> String {
+    let versions: Vec<serde_json::Value> = versions
+        .iter()
+        .map(|version| {
+            serde_json::json!({
+                "identifier": version.id(),
+                "vendor": version.vendor(),
+                "version": version.version(),
+                "dist": version.dist(),
+                "installed": local_versions.contains_key(version.id()),
+                "current": *local_versions.get(version.id()).unwrap_or(&false),
+                "released": version.released().map(|released| released.to_string()),
+                "home": home(binary_name, version, local_versions),
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&serde_json::json!({
+        "candidate": binary_name,
+        "versions": versions,
+    }))
+    .unwrap_or_default()
+}
+
+// where an installed version lives, see sdk_home
+fn home(
+    binary_name: &str,
+    version: &RemoteVersion,
+    local_versions: &HashMap<String, bool>,
+) -> Option<String> {
+    if !local_versions.contains_key(version.id()) {
+        return None;
+    }
+    sdk_home(binary_name, version.id())
+        .map(|home| home.display().to_string())
+        .ok()
+}
+
+pub fn csv_field(field: &str) -> String {
+    if field.contains(',') || field.contains('"') || field.contains('\n') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn markdown_field(field: &str) -> String {
+    field.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog() -> (Vec<RemoteCandidate>, Vec<LocalCandidate>) {
+        let candidates = vec![
+            RemoteCandidate::new(
+                "Gradle".to_string(),
+                "gradle".to_string(),
+                "Build tool".to_string(),
+                "https://gradle.org".to_string(),
+                "(8.0.2)".to_string(),
+            ),
+            RemoteCandidate::new(
+                "Java | JDK".to_string(),
+                "java".to_string(),
+                "Java Platform".to_string(),
+                "https://openjdk.org".to_string(),
+                "(21.0.2-tem)".to_string(),
+            ),
+        ];
+        let local = vec![LocalCandidate::new(
+            "gradle".to_string(),
+            HashMap::from([("8.0.2".to_string(), true), ("7.6".to_string(), false)]),
+        )];
+        (candidates, local)
+    }
+
+    #[test]
+    fn renders_the_catalog_as_csv() {
+        let (candidates, local) = catalog();
+        assert_eq!(
+            CatalogFormat::Csv.render(&candidates, &local),
+            "name,binary,default version,homepage,installed,current\n\
+             Gradle,gradle,8.0.2,https://gradle.org,7.6 8.0.2,8.0.2\n\
+             Java | JDK,java,21.0.2-tem,https://openjdk.org,,"
+        );
+    }
+
+    #[test]
+    fn renders_the_catalog_as_markdown() {
+        let (candidates, local) = catalog();
+        assert_eq!(
+            CatalogFormat::Markdown.render(&candidates, &local),
+            "| name | binary | default version | homepage | installed | current |\n\
+             | --- | --- | --- | --- | --- | --- |\n\
+             | Gradle | gradle | 8.0.2 | https://gradle.org | 7.6 8.0.2 | 8.0.2 |\n\
+             | Java \\| JDK | java | 21.0.2-tem | https://openjdk.org |  |  |"
+        );
+    }
+
+    #[test]
+    fn parses_the_catalog_formats() {
+        assert_eq!(CatalogFormat::parse("csv"), Some(CatalogFormat::Csv));
+        assert_eq!(CatalogFormat::parse("md"), Some(CatalogFormat::Markdown));
+        assert_eq!(CatalogFormat::parse("json"), None);
+    }
+
+    #[test]
+    fn quotes_csv_fields() {
+        assert_eq!(csv_field("8.0.2"), "8.0.2");
+        assert_eq!(csv_field("1,2"), "\"1,2\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn escapes_markdown_fields() {
+        assert_eq!(markdown_field("a | b"), "a \\| b");
+        assert_eq!(markdown_field("two\nlines"), "two lines");
+    }
+}
@@ -0,0 +1,175 @@
+use eframe::egui::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use api::category::Category;
+use api::local::LocalCandidate;
+
+use crate::candidates::Candidate;
+use crate::search;
+use crate::util;
+
+const PADDING: f32 = 8.0;
+const CATEGORIES: [Category; 6] = [
+    Category::Jdk,
+    Category::Language,
+    Category::BuildTool,
+    Category::Framework,
+    Category::Platform,
+    Category::Tool,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Installed {
+    Any,
+    Yes,
+    No,
+}
+
+// a named combination of filters, saved in the config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmartFilter {
+    pub name: String,
+    pub installed: Installed,
+    // empty for all of them
+    pub categories: Vec<Category>,
+    // only candidates whose current version isn't the default one
+    pub with_updates: bool,
+    // matched against the name and description, like the search
+    pub text: String,
+}
+
+impl Default for SmartFilter {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            installed: Installed::Any,
+            categories: Vec::new(),
+            with_updates: false,
+            text: String::new(),
+        }
+    }
+}
+
+pub enum EditorOutcome {
+    Open,
+    Save(SmartFilter),
+    Delete(String),
+    Cancel,
+}
+
+impl SmartFilter {
+    pub fn presets() -> Vec<SmartFilter> {
+        vec![
+            SmartFilter {
+                name: "Installed JDKs with updates".to_string(),
+                installed: Installed::Yes,
+                categories: vec![Category::Jdk],
+                with_updates: true,
+                ..Default::default()
+            },
+            SmartFilter {
+                name: "JVM languages not installed".to_string(),
+                installed: Installed::No,
+                categories: vec![Category::Language],
+                ..Default::default()
+            },
+        ]
+    }
+
+    pub fn matches(&self, candidate: &Candidate, local_candidate: Option<&LocalCandidate>) -> bool {
+        let installed = local_candidate.is_some();
+        match self.installed {
+            Installed::Yes if !installed => return false,
+            Installed::No if installed => return false,
+            _ => {}
+        }
+        if !self.categories.is_empty() && !self.categories.contains(&candidate.category()) {
+            return false;
+        }
+        if self.with_updates && !has_update(candidate, local_candidate) {
+            return false;
+        }
+        self.text.trim().is_empty() || search::matches(candidate, &self.text)
+    }
+}
+
+fn has_update(candidate: &Candidate, local_candidate: Option<&LocalCandidate>) -> bool {
+    if candidate.is_archived() {
+        return false;
+    }
+    let latest = util::strip_parens(candidate.default_version());
+    local_candidate
+        .and_then(|local| local.current_version())
+        .is_some_and(|current| !current.starts_with(latest))
+}
+
+// edits a filter; saving one under an existing name replaces it
+pub fn render_editor(ctx: &CtxRef, draft: &mut SmartFilter, saved: bool) -> EditorOutcome {
+    let mut outcome = EditorOutcome::Open;
+    Window::new("Smart filter")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(Align2::CENTER_CENTER, [0., 0.])
+        .show(ctx, |ui| {
+            ui.add_space(PADDING);
+            Grid::new("smart_filter")
+                .spacing([2. * PADDING, PADDING])
+                .show(ui, |ui| {
+                    ui.label("Name:");
+                    ui.text_edit_singleline(&mut draft.name);
+                    ui.end_row();
+
+                    ui.label("Installed:");
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut draft.installed, Installed::Any, "Any");
+                        ui.radio_value(&mut draft.installed, Installed::Yes, "Yes");
+                        ui.radio_value(&mut draft.installed, Installed::No, "No");
+                    });
+                    ui.end_row();
+
+                    ui.label("Categories:");
+                    ui.vertical(|ui| {
+                        for category in CATEGORIES {
+                            let mut selected = draft.categories.contains(&category);
+                            if ui.checkbox(&mut selected, category.to_string()).changed() {
+                                if selected {
+                                    draft.categories.push(category);
+                                } else {
+                                    draft.categories.retain(|c| *c != category);
+                                }
+                            }
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Updates:");
+                    ui.checkbox(&mut draft.with_updates, "Only with a newer default version");
+                    ui.end_row();
+
+                    ui.label("Text:");
+                    ui.text_edit_singleline(&mut draft.text)
+                        .on_hover_text("Matched against the names and descriptions");
+                    ui.end_row();
+                });
+            ui.add_space(PADDING);
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(!draft.name.trim().is_empty(), Button::new("Save"))
+                    .clicked()
+                {
+                    outcome = EditorOutcome::Save(draft.clone());
+                }
+                if saved && ui.button("Delete").clicked() {
+                    outcome = EditorOutcome::Delete(draft.name.clone());
+                }
+                if ui.button("Cancel").clicked() {
+                    outcome = EditorOutcome::Cancel;
+                }
+            });
+        });
+    outcome
+}
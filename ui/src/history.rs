@@ -0,0 +1,169 @@
+use std::fmt;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use crate::config::app_dir;
+use crate::snapshots::CurrentVersions;
+
+const HISTORY_FILE: &str = "history.log";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    Install,
+    Delete,
+    SetCurrent,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Action::Install => "install",
+            Action::Delete => "delete",
+            Action::SetCurrent => "current",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "install" => Ok(Action::Install),
+            "delete" => Ok(Action::Delete),
+            "current" => Ok(Action::SetCurrent),
+            other => Err(format!("unknown action '{}'", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub timestamp: SystemTime,
+    pub candidate: String,
+    pub version: String,
+    pub action: Action,
+}
+
+impl fmt::Display for HistoryEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}",
+            self.timestamp
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            self.candidate,
+            self.version,
+            self.action
+        )
+    }
+}
+
+impl FromStr for HistoryEntry {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = input.split('\t').collect();
+        match parts.as_slice() {
+            [timestamp, candidate, version, action] => Ok(HistoryEntry {
+                timestamp: UNIX_EPOCH
+                    + Duration::from_secs(timestamp.parse().map_err(|_| "invalid timestamp")?),
+                candidate: candidate.to_string(),
+                version: version.to_string(),
+                action: action.parse()?,
+            }),
+            _ => Err(format!("malformed history entry '{}'", input)),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct History {
+    entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    pub fn load() -> History {
+        let entries = history_path()
+            .and_then(fs::read_to_string)
+            .map(|content| {
+                content
+                    .lines()
+                    .filter_map(|line| HistoryEntry::from_str(line).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        History { entries }
+    }
+
+    pub fn record(
+        &mut self,
+        candidate: &str,
+        version: &str,
+        action: Action,
+    ) -> std::io::Result<()> {
+        let entry = HistoryEntry {
+            timestamp: SystemTime::now(),
+            candidate: candidate.to_string(),
+            version: version.to_string(),
+            action,
+        };
+        let path = history_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", entry)?;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    // most recent entries first
+    pub fn for_candidate<'a>(
+        &'a self,
+        binary_name: &'a str,
+    ) -> impl Iterator<Item = &'a HistoryEntry> {
+        self.entries
+            .iter()
+            .rev()
+            .filter(move |entry| entry.candidate == binary_name)
+    }
+
+    // the version switches of all candidates, oldest first
+    pub fn switches(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.action == Action::SetCurrent)
+    }
+
+    // the current versions as recorded up to and including the time; candidates without a
+    // recorded switch, or whose current version was deleted since, aren't mentioned
+    pub fn current_versions_at(&self, time: SystemTime) -> CurrentVersions {
+        let mut versions = CurrentVersions::new();
+        for entry in self.entries.iter().filter(|entry| entry.timestamp <= time) {
+            match entry.action {
+                Action::SetCurrent => {
+                    versions.insert(entry.candidate.clone(), entry.version.clone());
+                }
+                Action::Delete if versions.get(&entry.candidate) == Some(&entry.version) => {
+                    versions.remove(&entry.candidate);
+                }
+                _ => {}
+            }
+        }
+        versions
+    }
+}
+
+fn history_path() -> std::io::Result<PathBuf> {
+    app_dir().map(|dir| dir.join(HISTORY_FILE))
+}
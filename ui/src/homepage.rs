@@ -0,0 +1,88 @@
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+
+use eframe::egui::*;
+
+use api::homepage;
+
+use crate::repaint::RepaintNotifier;
+use crate::tasks;
+
+const PADDING: f32 = 8.0;
+const WIDTH: f32 = 420.0;
+
+// the text of a candidate's homepage next to the list, to skim without leaving the app
+pub struct HomepagePanel {
+    name: String,
+    url: String,
+    text: Option<Result<String, String>>,
+    receiver: Option<Receiver<Result<String, String>>>,
+}
+
+impl HomepagePanel {
+    pub fn open(name: &str, url: &str, repaint: &RepaintNotifier) -> HomepagePanel {
+        let (sender, receiver) = mpsc::channel();
+        let fetched = url.to_string();
+        let repaint = repaint.clone();
+        tasks::spawn(move || {
+            let _ = sender.send(homepage::fetch_summary(&fetched).map_err(|e| e.to_string()));
+            repaint.notify();
+        });
+        HomepagePanel {
+            name: name.to_string(),
+            url: url.to_string(),
+            text: None,
+            receiver: Some(receiver),
+        }
+    }
+
+    pub fn is_for(&self, url: &str) -> bool {
+        self.url == url
+    }
+
+    // false once the panel is closed
+    pub fn render(&mut self, ctx: &CtxRef) -> bool {
+        if let Some(text) = self.receiver.as_ref().and_then(|r| r.try_recv().ok()) {
+            self.text = Some(text);
+            self.receiver = None;
+        }
+        let mut open = true;
+        SidePanel::right("homepage")
+            .resizable(true)
+            .default_width(WIDTH)
+            .show(ctx, |ui| {
+                ui.add_space(PADDING);
+                ui.horizontal(|ui| {
+                    ui.heading(&self.name);
+                    ui.with_layout(Layout::right_to_left(), |ui| {
+                        if ui.button("❌").on_hover_text("Close").clicked() {
+                            open = false;
+                        }
+                    });
+                });
+                ui.hyperlink_to("Open in the browser", &self.url);
+                ui.separator();
+                ScrollArea::vertical().show(ui, |ui| match &self.text {
+                    None => {
+                        ui.label("Loading the homepage…");
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(
+                            Color32::YELLOW,
+                            format!("Loading the homepage failed with:\n{}", e),
+                        );
+                    }
+                    Some(Ok(text)) => {
+                        for paragraph in text.split("\n\n") {
+                            match paragraph.strip_prefix("## ") {
+                                Some(heading) => ui.add(Label::new(heading).strong()),
+                                None => ui.add(Label::new(paragraph).wrap(true)),
+                            };
+                            ui.add_space(PADDING / 2.);
+                        }
+                    }
+                });
+            });
+        open
+    }
+}
@@ -0,0 +1,729 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use std::sync::mpsc::Sender;
+
+use eframe::egui::*;
+
+use api::install;
+use api::install::Progress;
+use api::remote::SdkmanApiError;
+use api::store::store;
+
+use crate::elevation;
+use crate::history::Action;
+use crate::journal::Journal;
+use crate::repaint::RepaintNotifier;
+use crate::tasks;
+use crate::util;
+
+const PADDING: f32 = 8.0;
+
+// set when the app quits; cancelling the downloads only stops the running steps
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub binary_name: String,
+    pub version: String,
+    pub action: Action,
+    // a local archive to install instead of downloading one
+    pub archive: Option<PathBuf>,
+}
+
+impl Step {
+    pub fn new(binary_name: &str, version: &str, action: Action) -> Step {
+        Step {
+            binary_name: binary_name.to_string(),
+            version: version.to_string(),
+            action,
+            archive: None,
+        }
+    }
+
+    pub fn from_archive(binary_name: &str, version: &str, archive: PathBuf) -> Step {
+        Step {
+            archive: Some(archive),
+            ..Step::new(binary_name, version, Action::Install)
+        }
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.action, self.binary_name, self.version)?;
+        if let Some(archive) = self
+            .archive
+            .as_ref()
+            .and_then(|archive| archive.file_name())
+        {
+            write!(f, " from {}", archive.to_string_lossy())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum JobState {
+    // the index of the step being run
+    Running(usize),
+    Succeeded,
+    // the index of the step that failed and why
+    Failed(usize, String),
+    RollingBack,
+    RolledBack,
+    RollbackFailed(String),
+}
+
+struct Job {
+    id: u64,
+    title: String,
+    steps: Vec<Step>,
+    state: JobState,
+    // what a failed job had applied before it failed
+    journal: Option<Journal>,
+    // the progress of the running step, if it reports any
+    progress: Option<Progress>,
+    // what the steps printed, e.g. when verifying an installation
+    output: Vec<String>,
+}
+
+impl Job {
+    fn is_running(&self) -> bool {
+        matches!(self.state, JobState::Running(_) | JobState::RollingBack)
+    }
+}
+
+enum LowDiskSpace {
+    Install,
+    CleanUp,
+    Cancel,
+}
+
+enum JobEvent {
+    Started(u64, usize),
+    Progress(u64, Progress),
+    Output(u64, String),
+    Succeeded(u64),
+    Failed(u64, usize, String, Journal),
+    RolledBack(u64, Option<String>),
+}
+
+pub fn describe(steps: &[Step]) -> String {
+    steps
+        .iter()
+        .map(|step| step.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+// what a finished job leaves behind for the rest of the app
+pub struct FinishedJob {
+    pub title: String,
+    pub completed: Vec<Step>,
+    pub error: Option<String>,
+    // the candidates whose directories it may have changed, even if it failed or rolled back
+    pub touched: Vec<String>,
+}
+
+pub struct Jobs {
+    jobs: Vec<Job>,
+    next_id: u64,
+    verify_installs: bool,
+    // the installation belongs to root, so the steps run through the system's authorization
+    elevated: bool,
+    // submitted while elevated, waiting for the user to agree to the password prompts
+    awaiting_elevation: Vec<(String, Vec<Step>)>,
+    // the free bytes while they are below the threshold of the settings
+    low_disk_space: Option<u64>,
+    // installs go ahead without asking until there's enough space again
+    low_disk_space_acknowledged: bool,
+    // installs submitted while the space was low, waiting for the user to go ahead anyway or
+    // for the space to come back
+    awaiting_space: Vec<(String, Vec<Step>)>,
+    sender: Sender<JobEvent>,
+    receiver: Receiver<JobEvent>,
+}
+
+impl Default for Jobs {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            jobs: Vec::new(),
+            next_id: 0,
+            verify_installs: false,
+            elevated: false,
+            awaiting_elevation: Vec::new(),
+            low_disk_space: None,
+            low_disk_space_acknowledged: false,
+            awaiting_space: Vec::new(),
+            sender,
+            receiver,
+        }
+    }
+}
+
+impl Jobs {
+    pub fn new(verify_installs: bool) -> Jobs {
+        Jobs {
+            verify_installs,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.jobs.iter().any(|job| job.is_running())
+    }
+
+    // true while a job that runs the step is queued or running
+    pub fn is_pending(&self, step: &Step) -> bool {
+        self.jobs
+            .iter()
+            .filter(|job| job.is_running())
+            .flat_map(|job| &job.steps)
+            .chain(self.awaiting_elevation.iter().flat_map(|(_, steps)| steps))
+            .chain(self.awaiting_space.iter().flat_map(|(_, steps)| steps))
+            .any(|pending| pending == step)
+    }
+
+    // installed versions run their version command before the job goes on
+    pub fn set_verify_installs(&mut self, verify_installs: bool) {
+        self.verify_installs = verify_installs;
+    }
+
+    pub fn set_elevated(&mut self, elevated: bool) {
+        self.elevated = elevated;
+    }
+
+    pub fn is_elevated(&self) -> bool {
+        self.elevated
+    }
+
+    pub fn set_low_disk_space(&mut self, free: Option<u64>, repaint: &RepaintNotifier) {
+        self.low_disk_space = free;
+        if free.is_none() {
+            self.low_disk_space_acknowledged = false;
+            for (title, steps) in std::mem::take(&mut self.awaiting_space) {
+                self.submit(title, steps, repaint);
+            }
+        }
+    }
+
+    pub fn low_disk_space(&self) -> Option<u64> {
+        self.low_disk_space
+    }
+
+    // the steps still waiting for the password prompt or the low disk space warning
+    pub fn take_awaiting(&mut self) -> Vec<Step> {
+        self.awaiting_elevation
+            .drain(..)
+            .chain(self.awaiting_space.drain(..))
+            .flat_map(|(_, steps)| steps)
+            .collect()
+    }
+
+    // runs the steps one after the other in the background, stopping at the first failure
+    pub fn submit(&mut self, title: String, steps: Vec<Step>, repaint: &RepaintNotifier) {
+        let installs = steps.iter().any(|step| step.action == Action::Install);
+        if installs && self.low_disk_space.is_some() && !self.low_disk_space_acknowledged {
+            self.awaiting_space.push((title, steps));
+            return;
+        }
+        if self.elevated {
+            self.awaiting_elevation.push((title, steps));
+            return;
+        }
+        let journal = Journal::create(steps);
+        self.run(title, journal, repaint);
+    }
+
+    // continues a job that was interrupted, starting over with the step it was in the middle of
+    pub fn resume(&mut self, journal: Journal, repaint: &RepaintNotifier) {
+        // nothing of a queued job has been run, so it's asked about like a new one
+        if journal.is_queued() {
+            let steps = journal.steps().to_vec();
+            journal.discard();
+            self.submit(describe(&steps), steps, repaint);
+            return;
+        }
+        let title = format!("Resume {}", describe(journal.steps()));
+        self.run(title, journal, repaint);
+    }
+
+    // takes back everything an interrupted job had applied
+    pub fn clean_up(&mut self, journal: Journal, repaint: &RepaintNotifier) {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.jobs.push(Job {
+            id,
+            title: format!("Clean up {}", describe(journal.steps())),
+            steps: journal.steps().to_vec(),
+            state: JobState::RollingBack,
+            journal: None,
+            progress: None,
+            output: Vec::new(),
+        });
+        self.spawn_rollback(id, journal, repaint);
+    }
+
+    fn run(&mut self, title: String, journal: Journal, repaint: &RepaintNotifier) {
+        let id = self.next_id;
+        self.next_id += 1;
+        let first = journal.next_step();
+        let steps = journal.steps().to_vec();
+        let verify_installs = self.verify_installs;
+        let elevated = self.elevated;
+        let sender = self.sender.clone();
+        let repaint = repaint.clone();
+        tasks::spawn(move || {
+            execute(id, journal, first, verify_installs, elevated, &|event| {
+                let _ = sender.send(event);
+                repaint.notify();
+            })
+        });
+        self.jobs.push(Job {
+            id,
+            title,
+            steps,
+            state: JobState::Running(first),
+            journal: None,
+            progress: None,
+            output: Vec::new(),
+        });
+    }
+
+    fn render_elevation_prompt(&mut self, ctx: &CtxRef, repaint: &RepaintNotifier) {
+        if self.awaiting_elevation.is_empty() {
+            return;
+        }
+        let steps = self.awaiting_elevation.iter().flat_map(|(_, steps)| steps);
+        let mut confirmed = None;
+        Window::new("Administrator rights needed")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0., 0.])
+            .show(ctx, |ui| {
+                ui.add_space(PADDING);
+                ui.label("This SDKMAN installation belongs to another user. To");
+                for step in steps {
+                    ui.monospace(step.to_string());
+                }
+                ui.label(
+                    "the system will ask for an administrator password, once for every change. \
+                     The changes can't be rolled back automatically.",
+                );
+                ui.add_space(PADDING);
+                ui.horizontal(|ui| {
+                    if ui.button("Continue").clicked() {
+                        confirmed = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        confirmed = Some(false);
+                    }
+                });
+            });
+        // the jobs submitted in the meantime are agreed to or cancelled together
+        match confirmed {
+            Some(true) => {
+                for (title, steps) in std::mem::take(&mut self.awaiting_elevation) {
+                    let journal = Journal::create(steps);
+                    self.run(title, journal, repaint);
+                }
+            }
+            Some(false) => self.awaiting_elevation.clear(),
+            None => {}
+        }
+    }
+
+    // returns true when the user wants to free up space before installing
+    fn render_low_disk_space_prompt(&mut self, ctx: &CtxRef, repaint: &RepaintNotifier) -> bool {
+        let free = match self.low_disk_space {
+            Some(free) if !self.awaiting_space.is_empty() => free,
+            _ => return false,
+        };
+        let steps = self.awaiting_space.iter().flat_map(|(_, steps)| steps);
+        let mut answer = None;
+        Window::new("Low disk space")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0., 0.])
+            .show(ctx, |ui| {
+                ui.add_space(PADDING);
+                ui.label(format!(
+                    "Only {} are left on the volume of the SDKMAN installation. Installing",
+                    util::format_size(free)
+                ));
+                for step in steps {
+                    ui.monospace(step.to_string());
+                }
+                ui.label("may fill it up and leave a half-extracted version behind.");
+                ui.add_space(PADDING);
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Install anyway")
+                        .on_hover_text("Don't ask again until there's enough space")
+                        .clicked()
+                    {
+                        answer = Some(LowDiskSpace::Install);
+                    }
+                    if ui
+                        .button("Free up space")
+                        .on_hover_text("Show the versions that haven't been used for a while")
+                        .clicked()
+                    {
+                        answer = Some(LowDiskSpace::CleanUp);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        answer = Some(LowDiskSpace::Cancel);
+                    }
+                });
+            });
+        match answer {
+            Some(LowDiskSpace::Install) => {
+                self.low_disk_space_acknowledged = true;
+                for (title, steps) in std::mem::take(&mut self.awaiting_space) {
+                    self.submit(title, steps, repaint);
+                }
+                false
+            }
+            Some(LowDiskSpace::CleanUp) => {
+                self.awaiting_space.clear();
+                true
+            }
+            Some(LowDiskSpace::Cancel) => {
+                self.awaiting_space.clear();
+                false
+            }
+            None => false,
+        }
+    }
+
+    // takes back what the failed job had applied, in the background as well
+    fn rollback(&mut self, id: u64, repaint: &RepaintNotifier) {
+        let job = match self.jobs.iter_mut().find(|job| job.id == id) {
+            Some(job) => job,
+            None => return,
+        };
+        let journal = match job.journal.take() {
+            Some(journal) => journal,
+            None => return,
+        };
+        job.state = JobState::RollingBack;
+        self.spawn_rollback(id, journal, repaint);
+    }
+
+    fn spawn_rollback(&self, id: u64, journal: Journal, repaint: &RepaintNotifier) {
+        let sender = self.sender.clone();
+        let repaint = repaint.clone();
+        tasks::spawn(move || {
+            let error = journal.rollback().err().map(|e| e.to_string());
+            let _ = sender.send(JobEvent::RolledBack(id, error));
+            repaint.notify();
+        });
+    }
+
+    // applies the progress reported by the job threads; returns the jobs that finished since the last poll
+    pub fn poll(&mut self) -> Vec<FinishedJob> {
+        let mut finished = Vec::new();
+        for event in self.receiver.try_iter() {
+            let (id, state, journal) = match event {
+                JobEvent::Progress(id, progress) => {
+                    if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+                        job.progress = Some(progress);
+                    }
+                    continue;
+                }
+                JobEvent::Output(id, output) => {
+                    if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+                        job.output.push(output);
+                    }
+                    continue;
+                }
+                JobEvent::Started(id, index) => (id, JobState::Running(index), None),
+                JobEvent::Succeeded(id) => (id, JobState::Succeeded, None),
+                JobEvent::Failed(id, index, e, journal) => {
+                    (id, JobState::Failed(index, e), Some(journal))
+                }
+                JobEvent::RolledBack(id, None) => (id, JobState::RolledBack, None),
+                JobEvent::RolledBack(id, Some(e)) => (id, JobState::RollbackFailed(e), None),
+            };
+            if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+                let mut touched: Vec<String> = job
+                    .steps
+                    .iter()
+                    .map(|step| step.binary_name.clone())
+                    .collect();
+                touched.sort();
+                touched.dedup();
+                match &state {
+                    JobState::Running(_) | JobState::RollingBack => {}
+                    JobState::Succeeded => finished.push(FinishedJob {
+                        title: job.title.clone(),
+                        completed: job.steps.clone(),
+                        error: None,
+                        touched,
+                    }),
+                    JobState::Failed(index, e) => finished.push(FinishedJob {
+                        title: job.title.clone(),
+                        completed: job.steps[..*index].to_vec(),
+                        error: Some(e.clone()),
+                        touched,
+                    }),
+                    JobState::RolledBack => finished.push(FinishedJob {
+                        title: format!("Rollback of {}", job.title),
+                        completed: Vec::new(),
+                        error: None,
+                        touched,
+                    }),
+                    JobState::RollbackFailed(e) => finished.push(FinishedJob {
+                        title: format!("Rollback of {}", job.title),
+                        completed: Vec::new(),
+                        error: Some(e.clone()),
+                        touched,
+                    }),
+                }
+                // nothing to roll back if the very first step failed without a trace
+                job.journal = match journal {
+                    Some(journal) if journal.has_applied() => Some(journal),
+                    Some(journal) => {
+                        journal.discard();
+                        None
+                    }
+                    None => None,
+                };
+                job.state = state;
+                job.progress = None;
+            }
+        }
+        finished
+    }
+
+    // docked jobs are shown in a pane of their own, see render_docked; returns true when the
+    // user wants to free up space first
+    pub fn render(&mut self, ctx: &CtxRef, repaint: &RepaintNotifier, docked: bool) -> bool {
+        self.render_elevation_prompt(ctx, repaint);
+        let clean_up = self.render_low_disk_space_prompt(ctx, repaint);
+        if docked || self.jobs.is_empty() {
+            return clean_up;
+        }
+        Window::new("Jobs")
+            .collapsible(true)
+            .resizable(false)
+            .anchor(Align2::RIGHT_BOTTOM, [-PADDING, -4. * PADDING])
+            .show(ctx, |ui| self.render_list(ui, repaint));
+        clean_up
+    }
+
+    pub fn render_docked(&mut self, ui: &mut Ui, repaint: &RepaintNotifier) {
+        if self.jobs.is_empty() {
+            ui.label("No jobs.");
+        } else {
+            self.render_list(ui, repaint);
+        }
+    }
+
+    fn render_list(&mut self, ui: &mut Ui, repaint: &RepaintNotifier) {
+        let mut clear = false;
+        let mut rollback = None;
+        // the downloads of all jobs together, if more than one is running
+        let downloads: Vec<(u64, u64)> = self
+            .jobs
+            .iter()
+            .filter_map(|job| match job.progress {
+                Some(Progress::Downloading(downloaded, Some(total))) => Some((downloaded, total)),
+                _ => None,
+            })
+            .collect();
+        if downloads.len() > 1 {
+            let downloaded: u64 = downloads.iter().map(|(downloaded, _)| downloaded).sum();
+            let total: u64 = downloads.iter().map(|(_, total)| total).sum();
+            ui.add(
+                ProgressBar::new(downloaded as f32 / total.max(1) as f32).text(format!(
+                    "{} downloads: {} of {}",
+                    downloads.len(),
+                    util::format_size(downloaded),
+                    util::format_size(total)
+                )),
+            );
+            ui.separator();
+        }
+        for job in &self.jobs {
+            ui.label(&job.title);
+            match &job.state {
+                JobState::Running(index) => {
+                    ui.add(
+                        ProgressBar::new(*index as f32 / job.steps.len() as f32).text(format!(
+                            "{}/{}: {}",
+                            index + 1,
+                            job.steps.len(),
+                            job.steps[*index]
+                        )),
+                    );
+                    match job.progress {
+                        Some(Progress::Waiting) => {
+                            ui.label("waiting for a download slot");
+                        }
+                        Some(Progress::Downloading(downloaded, Some(total))) => {
+                            ui.add(
+                                ProgressBar::new(downloaded as f32 / total.max(1) as f32).text(
+                                    format!(
+                                        "{} of {}",
+                                        util::format_size(downloaded),
+                                        util::format_size(total)
+                                    ),
+                                ),
+                            );
+                        }
+                        Some(Progress::Downloading(downloaded, None)) => {
+                            ui.label(format!("{} downloaded", util::format_size(downloaded)));
+                        }
+                        Some(Progress::Extracting) => {
+                            ui.label("extracting");
+                        }
+                        None => {}
+                    }
+                }
+                JobState::Succeeded => {
+                    ui.label("✔ done");
+                }
+                JobState::Failed(index, e) => {
+                    ui.add(Label::new(format!("✖ {} failed: {}", job.steps[*index], e)).wrap(true));
+                    if job.journal.is_some()
+                        && ui
+                            .button("Roll back")
+                            .on_hover_text("Undo the steps applied before the failure")
+                            .clicked()
+                    {
+                        rollback = Some(job.id);
+                    }
+                }
+                JobState::RollingBack => {
+                    ui.label("⟲ rolling back...");
+                }
+                JobState::RolledBack => {
+                    ui.label("⟲ rolled back");
+                }
+                JobState::RollbackFailed(e) => {
+                    ui.add(Label::new(format!("✖ rollback failed: {}", e)).wrap(true));
+                }
+            }
+            if !job.output.is_empty() {
+                CollapsingHeader::new("Output")
+                    .id_source(("job_output", job.id))
+                    .show(ui, |ui| {
+                        for output in &job.output {
+                            ui.monospace(output);
+                        }
+                    });
+            }
+            ui.add_space(PADDING);
+        }
+        ui.separator();
+        let any_finished = self.jobs.iter().any(|job| !job.is_running());
+        if ui
+            .add_enabled(any_finished, Button::new("Clear finished"))
+            .clicked()
+        {
+            clear = true;
+        }
+        if let Some(id) = rollback {
+            self.rollback(id, repaint);
+        }
+        if clear {
+            // clearing a failed job accepts what it left behind
+            for job in self.jobs.iter_mut().filter(|job| !job.is_running()) {
+                if let Some(journal) = job.journal.take() {
+                    journal.discard();
+                }
+            }
+            self.jobs.retain(|job| job.is_running());
+        }
+    }
+}
+
+// the running jobs stop before their next step and stay in the journal to be resumed
+pub fn shut_down() {
+    SHUTTING_DOWN.store(true, Ordering::SeqCst);
+}
+
+// runs the steps of a journal from the first one on, reporting what happens to emit
+fn execute(
+    id: u64,
+    mut journal: Journal,
+    first: usize,
+    verify_installs: bool,
+    elevated: bool,
+    emit: &dyn Fn(JobEvent),
+) {
+    if let Err(e) = journal.undo_interrupted() {
+        log!("Cleaning up the interrupted step failed: {}", e);
+        emit(JobEvent::Failed(id, first, e.to_string(), journal));
+        return;
+    }
+    for index in first..journal.steps().len() {
+        if SHUTTING_DOWN.load(Ordering::SeqCst) {
+            log!("Stopping before {}", journal.steps()[index]);
+            emit(JobEvent::Failed(
+                id,
+                index,
+                SdkmanApiError::Cancelled.to_string(),
+                journal,
+            ));
+            return;
+        }
+        emit(JobEvent::Started(id, index));
+        let step = journal.steps()[index].clone();
+        log!("Running {}", step);
+        let mut on_progress = |progress| emit(JobEvent::Progress(id, progress));
+        // sdk itself undoes what it left behind, so elevated steps aren't journaled
+        let result = if elevated {
+            elevation::run(&step)
+        } else {
+            journal
+                .apply(index, &mut on_progress)
+                .map_err(|e| e.to_string())
+        };
+        if let Err(e) = result {
+            log!("Running {} failed: {}", step, e);
+            emit(JobEvent::Failed(id, index, e, journal));
+            return;
+        }
+        if verify_installs && step.action == Action::Install {
+            let verified = install::verify_version(&step.binary_name, &step.version);
+            let output = match &verified {
+                Ok(output) => output.clone(),
+                Err(e) => e.to_string(),
+            };
+            emit(JobEvent::Output(id, output));
+            if let Err(e) = verified {
+                log!("Verifying {} failed: {}", step, e);
+                emit(JobEvent::Failed(id, index, e.to_string(), journal));
+                return;
+            }
+        }
+    }
+    journal.commit();
+    emit(JobEvent::Succeeded(id));
+}
+
+// runs the jobs left in the journal without a window, after the app quit while they were running;
+// a job failing again stays in the journal for the next start
+pub fn resume_headless(verify_installs: bool) {
+    // a root-owned installation needs a password for every step, they wait for the window
+    if !store().is_writable() {
+        log!("Leaving the jobs for the next start, the installation isn't writable");
+        return;
+    }
+    // nobody agreed to run the queued ones yet
+    for journal in Journal::recover()
+        .into_iter()
+        .filter(|journal| !journal.is_queued())
+    {
+        let first = journal.next_step();
+        log!("Resuming {}", describe(journal.steps()));
+        execute(0, journal, first, verify_installs, false, &|_| {});
+    }
+}
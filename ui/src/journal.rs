@@ -0,0 +1,672 @@
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use eframe::egui::*;
+
+use api::install::Progress;
+use api::local::version_path;
+use api::lock::EditLock;
+use api::remote::SdkmanApiError;
+use api::sdkman::Sdkman;
+
+use crate::config::app_dir;
+use crate::history::Action;
+use crate::jobs::Step;
+
+const JOURNAL_DIR: &str = "journal";
+const PADDING: f32 = 8.0;
+
+pub enum Recovery {
+    CleanUp,
+    Resume,
+    // stages the steps of a queued journal again instead of running them
+    Restage,
+    Discard,
+    Ignore,
+}
+
+// how to take back a step; written down before the step touches the disk
+#[derive(Debug, Clone)]
+enum Undo {
+    Uninstall,
+    // the version that was current before, if any
+    RestoreCurrent(Option<String>),
+    // whether the deleted version was the current one and where it gets stashed
+    Restore(bool, PathBuf),
+}
+
+impl fmt::Display for Undo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Undo::Uninstall => write!(f, "uninstall"),
+            Undo::RestoreCurrent(previous) => {
+                write!(f, "current\t{}", previous.as_deref().unwrap_or_default())
+            }
+            Undo::Restore(was_current, stash) => {
+                write!(f, "restore\t{}\t{}", was_current, stash.display())
+            }
+        }
+    }
+}
+
+impl FromStr for Undo {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = input.split('\t').collect();
+        match parts.as_slice() {
+            ["uninstall"] => Ok(Undo::Uninstall),
+            ["current", ""] => Ok(Undo::RestoreCurrent(None)),
+            ["current", previous] => Ok(Undo::RestoreCurrent(Some(previous.to_string()))),
+            ["restore", was_current, stash] => Ok(Undo::Restore(
+                was_current.parse().map_err(|_| "invalid current flag")?,
+                PathBuf::from(stash),
+            )),
+            _ => Err(format!("malformed undo '{}'", input)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct JournalEntry {
+    index: usize,
+    undo: Undo,
+}
+
+// the steps of a job and how far it got, mirrored to a file until the job is over
+#[derive(Debug, Default)]
+pub struct Journal {
+    path: Option<PathBuf>,
+    steps: Vec<Step>,
+    applied: Vec<JournalEntry>,
+    // a step that was begun but never finished, because the app went away in the middle of it
+    interrupted: Option<JournalEntry>,
+    // staged or waiting for a password when the app was closed, nothing has been run yet
+    queued: bool,
+    // held while this process has the journal, so no other one recovers it at the same time
+    lock: Option<EditLock>,
+}
+
+impl Journal {
+    pub fn create(steps: Vec<Step>) -> Journal {
+        let mut journal = Journal {
+            steps,
+            ..Default::default()
+        };
+        // without a journal file the job still runs, it just can't be recovered after a crash
+        match journal.write_plan() {
+            Ok(path) => {
+                journal.lock = EditLock::try_acquire(&path).ok();
+                journal.path = Some(path);
+            }
+            Err(e) => log!("Failed to write the job journal: {}", e),
+        }
+        journal
+    }
+
+    // keeps steps that haven't been started for the next start of the app
+    pub fn queue(steps: Vec<Step>) -> std::io::Result<()> {
+        let mut journal = Journal {
+            steps,
+            queued: true,
+            ..Default::default()
+        };
+        journal.path = Some(journal.write_plan()?);
+        journal.append("queued")
+    }
+
+    // the journals of jobs that never finished, most likely because the app was killed; the ones
+    // another process is running, e.g. one continuing them in the background, are left to it
+    pub fn recover() -> Vec<Journal> {
+        let entries = match journal_dir().and_then(fs::read_dir) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .collect();
+        paths.sort();
+        paths
+            .into_iter()
+            .filter_map(|path| {
+                let lock = match EditLock::try_acquire(&path) {
+                    Ok(lock) => Some(lock),
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => return None,
+                    Err(e) => {
+                        log!("Failed to lock the journal {}: {}", path.display(), e);
+                        None
+                    }
+                };
+                match Journal::read(&path) {
+                    Ok(journal) => Some(Journal { lock, ..journal }),
+                    Err(e) => {
+                        log!("Discarding unreadable journal {}: {}", path.display(), e);
+                        let _ = fs::remove_file(&path);
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    pub fn is_queued(&self) -> bool {
+        self.queued && !self.has_applied()
+    }
+
+    pub fn has_applied(&self) -> bool {
+        !self.applied.is_empty() || self.interrupted.is_some()
+    }
+
+    // the index of the first step that still has to be run
+    pub fn next_step(&self) -> usize {
+        self.applied.last().map_or(0, |entry| entry.index + 1)
+    }
+
+    // applies the step and remembers how to undo it
+    pub fn apply(
+        &mut self,
+        index: usize,
+        on_progress: &mut dyn FnMut(Progress),
+    ) -> Result<(), SdkmanApiError> {
+        let step = self.steps[index].clone();
+        let sdkman = Sdkman::new();
+        let undo = match step.action {
+            Action::Install => Undo::Uninstall,
+            Action::SetCurrent => Undo::RestoreCurrent(sdkman.current_version(&step.binary_name)?),
+            Action::Delete => Undo::Restore(
+                sdkman.current_version(&step.binary_name)?.as_deref()
+                    == Some(step.version.as_str()),
+                sdkman.stash_path(&step.binary_name, &step.version)?,
+            ),
+        };
+        self.append(&format!("begin\t{}\t{}", index, undo))?;
+        let entry = JournalEntry { index, undo };
+        let result = match step.action {
+            Action::Install => match &step.archive {
+                Some(archive) => {
+                    sdkman.install_archive(&step.binary_name, &step.version, archive, on_progress)
+                }
+                None => sdkman.install_with_progress(&step.binary_name, &step.version, on_progress),
+            },
+            Action::SetCurrent => sdkman.use_version(&step.binary_name, &step.version),
+            Action::Delete => Sdkman::new()
+                .stash(&step.binary_name, &step.version)
+                .map(|_| ()),
+        };
+        // a failed step may still have changed something, so it's treated like an interrupted one
+        if let Err(e) = result {
+            self.interrupted = Some(entry);
+            return Err(e);
+        }
+        // without the done line the next start would take the step for an interrupted one, so the job
+        // fails here and a rollback takes it back like one
+        if let Err(e) = self.append(&format!("done\t{}", index)) {
+            self.interrupted = Some(entry);
+            return Err(e.into());
+        }
+        self.applied.push(entry);
+        Ok(())
+    }
+
+    // takes back whatever an interrupted step may have left behind, so it can be run again
+    pub fn undo_interrupted(&mut self) -> Result<(), SdkmanApiError> {
+        if let Some(entry) = self.interrupted.take() {
+            self.undo(&entry)?;
+            self.append(&format!("undone\t{}", entry.index))?;
+        }
+        Ok(())
+    }
+
+    // deleted versions are only gone for good once the whole job went through
+    pub fn commit(self) {
+        let sdkman = Sdkman::new();
+        for entry in &self.applied {
+            if let Undo::Restore(_, stash) = &entry.undo {
+                if let Err(e) = sdkman.drop_stash(stash) {
+                    log!("Failed to remove {}: {}", stash.display(), e);
+                }
+            }
+        }
+        // a candidate whose last version went is removed as a whole
+        let mut emptied: Vec<&String> = self
+            .applied
+            .iter()
+            .filter(|entry| matches!(entry.undo, Undo::Restore(..)))
+            .map(|entry| &self.steps[entry.index].binary_name)
+            .collect();
+        emptied.sort();
+        emptied.dedup();
+        for binary_name in emptied {
+            match sdkman.remove_if_empty(binary_name) {
+                Ok(true) => log!("Removed {} along with its last version", binary_name),
+                Ok(false) => {}
+                Err(e) => log!("Failed to remove what's left of {}: {}", binary_name, e),
+            }
+        }
+        self.close();
+    }
+
+    // undoes the applied steps, most recent first; versions installed before are left alone
+    pub fn rollback(mut self) -> Result<(), SdkmanApiError> {
+        self.undo_interrupted()?;
+        while let Some(entry) = self.applied.pop() {
+            self.undo(&entry)?;
+            self.append(&format!("undone\t{}", entry.index))?;
+        }
+        self.close();
+        Ok(())
+    }
+
+    // every undo tolerates a step that was only partly applied
+    fn undo(&self, entry: &JournalEntry) -> Result<(), SdkmanApiError> {
+        let step = &self.steps[entry.index];
+        let binary_name = &step.binary_name;
+        let version = &step.version;
+        log!("Rolling back {}", step);
+        let sdkman = Sdkman::new();
+        match &entry.undo {
+            Undo::Uninstall => {
+                if version_path(binary_name, version)?.exists() {
+                    sdkman.uninstall(binary_name, version)?;
+                }
+            }
+            Undo::RestoreCurrent(Some(previous)) => sdkman.use_version(binary_name, previous)?,
+            Undo::RestoreCurrent(None) => sdkman.clear_current_version(binary_name)?,
+            Undo::Restore(was_current, stash) => {
+                if stash.exists() {
+                    sdkman.restore(binary_name, version, stash)?;
+                }
+                if *was_current {
+                    sdkman.use_version(binary_name, version)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // leaves the disk as it is and forgets about the job
+    pub fn discard(self) {
+        self.close();
+    }
+
+    fn close(self) {
+        if let Some(path) = self.path {
+            if let Err(e) = fs::remove_file(&path) {
+                log!("Failed to remove the journal {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    fn write_plan(&self) -> std::io::Result<PathBuf> {
+        let dir = journal_dir()?;
+        fs::create_dir_all(&dir)?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let path = dir.join(format!("job-{}.log", timestamp));
+        let mut file = File::create(&path)?;
+        for step in &self.steps {
+            write!(
+                file,
+                "step\t{}\t{}\t{}",
+                step.action, step.binary_name, step.version
+            )?;
+            match &step.archive {
+                Some(archive) => writeln!(file, "\t{}", archive.display())?,
+                None => writeln!(file)?,
+            }
+        }
+        file.sync_all()?;
+        Ok(path)
+    }
+
+    // every line hits the disk before the operation it announces
+    fn append(&self, line: &str) -> std::io::Result<()> {
+        match &self.path {
+            Some(path) => {
+                let mut file = OpenOptions::new().append(true).open(path)?;
+                writeln!(file, "{}", line)?;
+                file.sync_data()
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn read(path: &Path) -> Result<Journal, String> {
+        let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let mut journal = Journal {
+            path: Some(path.to_path_buf()),
+            ..Default::default()
+        };
+        for line in content.lines() {
+            let (kind, rest) = line.split_once('\t').unwrap_or((line, ""));
+            match kind {
+                "step" => {
+                    let parts: Vec<&str> = rest.split('\t').collect();
+                    match parts.as_slice() {
+                        [action, binary_name, version] => {
+                            journal
+                                .steps
+                                .push(Step::new(binary_name, version, action.parse()?));
+                        }
+                        [_, binary_name, version, archive] => {
+                            journal.steps.push(Step::from_archive(
+                                binary_name,
+                                version,
+                                PathBuf::from(archive),
+                            ));
+                        }
+                        _ => return Err(format!("malformed step '{}'", rest)),
+                    }
+                }
+                "queued" => journal.queued = true,
+                "begin" => {
+                    let (index, undo) = rest
+                        .split_once('\t')
+                        .ok_or_else(|| format!("malformed entry '{}'", rest))?;
+                    journal.interrupted = Some(JournalEntry {
+                        index: parse_index(index, journal.steps.len())?,
+                        undo: undo.parse()?,
+                    });
+                }
+                "done" => {
+                    let entry = journal
+                        .interrupted
+                        .take()
+                        .filter(|entry| entry.index.to_string() == rest)
+                        .ok_or_else(|| format!("unexpected end of step {}", rest))?;
+                    journal.applied.push(entry);
+                }
+                "undone" => {
+                    let index = parse_index(rest, journal.steps.len())?;
+                    if journal.interrupted.as_ref().map(|entry| entry.index) == Some(index) {
+                        journal.interrupted = None;
+                    } else {
+                        journal.applied.retain(|entry| entry.index != index);
+                    }
+                }
+                other => return Err(format!("unknown journal line '{}'", other)),
+            }
+        }
+        Ok(journal)
+    }
+}
+
+// asks what to do about a job that was interrupted the last time the app ran
+pub fn render_recovery_dialog(ctx: &CtxRef, journal: &Journal) -> Option<Recovery> {
+    if journal.is_queued() {
+        return render_queued_dialog(ctx, journal);
+    }
+    let mut recovery = None;
+    Window::new("Interrupted operation")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(Align2::CENTER_CENTER, [0., 0.])
+        .show(ctx, |ui| {
+            ui.add_space(PADDING);
+            ui.label("sdkman-ui was closed while applying these changes:");
+            for (index, step) in journal.steps().iter().enumerate() {
+                let marker = if index < journal.next_step() {
+                    "✔"
+                } else {
+                    " "
+                };
+                ui.monospace(format!("{} {}", marker, step));
+            }
+            ui.add_space(PADDING);
+            ui.label("Clean up takes back what was applied, resume runs the remaining changes.");
+            ui.add_space(PADDING);
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Clean up").clicked() {
+                    recovery = Some(Recovery::CleanUp);
+                }
+                if ui.button("Resume").clicked() {
+                    recovery = Some(Recovery::Resume);
+                }
+                if ui.button("Ask me later").clicked() {
+                    recovery = Some(Recovery::Ignore);
+                }
+            });
+            ui.add_space(PADDING);
+        });
+    recovery
+}
+
+fn render_queued_dialog(ctx: &CtxRef, journal: &Journal) -> Option<Recovery> {
+    let mut recovery = None;
+    Window::new("Queued changes")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(Align2::CENTER_CENTER, [0., 0.])
+        .show(ctx, |ui| {
+            ui.add_space(PADDING);
+            ui.label("These changes were waiting when sdkman-ui was closed:");
+            for step in journal.steps() {
+                ui.monospace(step.to_string());
+            }
+            ui.add_space(PADDING);
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Run them").clicked() {
+                    recovery = Some(Recovery::Resume);
+                }
+                if ui
+                    .button("Stage them")
+                    .on_hover_text("Review them in the pending changes first")
+                    .clicked()
+                {
+                    recovery = Some(Recovery::Restage);
+                }
+                if ui.button("Discard").clicked() {
+                    recovery = Some(Recovery::Discard);
+                }
+                if ui.button("Ask me later").clicked() {
+                    recovery = Some(Recovery::Ignore);
+                }
+            });
+            ui.add_space(PADDING);
+        });
+    recovery
+}
+
+fn parse_index(input: &str, steps: usize) -> Result<usize, String> {
+    input
+        .parse()
+        .ok()
+        .filter(|index| *index < steps)
+        .ok_or_else(|| format!("invalid step index '{}'", input))
+}
+
+fn journal_dir() -> std::io::Result<PathBuf> {
+    app_dir().map(|dir| dir.join(JOURNAL_DIR))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+    use std::sync::Arc;
+    use std::sync::Once;
+
+    use api::store::set_store;
+    use api::store::store;
+    use api::store::MemoryStore;
+
+    use super::*;
+
+    // the store is global, so the tests share one in which every test works on candidates of its own
+    fn memory_store() {
+        static STORE: Once = Once::new();
+        STORE.call_once(|| {
+            set_store(Arc::new(
+                MemoryStore::default()
+                    .with_version("gradle", "7.6", 90)
+                    .with_version("gradle", "8.0.2", 100)
+                    .with_version("maven", "3.8.7", 10)
+                    .with_version("maven", "3.9.0", 10),
+            ))
+        });
+    }
+
+    fn write_journal(name: &str, lines: &[&str]) -> PathBuf {
+        let dir = env::temp_dir().join(format!("sdkman-ui-journal-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("{}.log", name));
+        fs::write(&path, lines.join("\n") + "\n").unwrap();
+        path
+    }
+
+    #[test]
+    fn reads_how_far_a_job_got() {
+        let steps = [
+            "step\tinstall\tgradle\t8.0.2",
+            "step\tcurrent\tgradle\t8.0.2",
+            "step\tinstall\tkotlin\t1.8.10\t/tmp/kotlin-1.8.10.zip",
+        ];
+
+        let path = write_journal("queued", &[&steps[..], &["queued"]].concat());
+        let journal = Journal::read(&path).unwrap();
+        assert_eq!(journal.steps().len(), 3);
+        assert_eq!(
+            journal.steps()[2].archive,
+            Some(PathBuf::from("/tmp/kotlin-1.8.10.zip"))
+        );
+        assert!(journal.is_queued());
+        assert_eq!(journal.next_step(), 0);
+        fs::remove_file(&path).unwrap();
+
+        let path = write_journal(
+            "interrupted",
+            &[
+                &steps[..],
+                &["begin\t0\tuninstall", "done\t0", "begin\t1\tcurrent\t7.6"],
+            ]
+            .concat(),
+        );
+        let journal = Journal::read(&path).unwrap();
+        assert!(!journal.is_queued());
+        assert!(journal.has_applied());
+        assert_eq!(journal.next_step(), 1);
+        assert_eq!(
+            journal.interrupted.as_ref().map(|entry| entry.index),
+            Some(1)
+        );
+        fs::remove_file(&path).unwrap();
+
+        let path = write_journal(
+            "undone",
+            &[
+                &steps[..],
+                &[
+                    "begin\t0\tuninstall",
+                    "done\t0",
+                    "begin\t1\tcurrent\t",
+                    "undone\t1",
+                    "undone\t0",
+                ],
+            ]
+            .concat(),
+        );
+        let journal = Journal::read(&path).unwrap();
+        assert!(!journal.has_applied());
+        assert_eq!(journal.next_step(), 0);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn rejects_malformed_journals() {
+        let step = "step\tinstall\tgradle\t8.0.2";
+        for (name, line) in [
+            ("unstarted", "done\t0"),
+            ("out-of-range", "begin\t1\tuninstall"),
+            ("unknown-undo", "begin\t0\tpanic"),
+            ("unknown-line", "skipped\t0"),
+        ] {
+            let path = write_journal(name, &[step, line]);
+            assert!(Journal::read(&path).is_err(), "{}", line);
+            fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn rolls_back_a_half_applied_step() {
+        memory_store();
+        Sdkman::new().use_version("gradle", "7.6").unwrap();
+        let path = write_journal(
+            "rollback",
+            &["step\tcurrent\tgradle\t8.0.2", "step\tcurrent\tgradle\t7.6"],
+        );
+        let mut journal = Journal::read(&path).unwrap();
+        journal.apply(0, &mut |_| {}).unwrap();
+        assert_eq!(
+            store().current_version("gradle").unwrap().as_deref(),
+            Some("8.0.2")
+        );
+        // the app went away in the middle of the second step
+        journal.append("begin\t1\tcurrent\t8.0.2").unwrap();
+
+        let journal = Journal::read(&path).unwrap();
+        assert_eq!(journal.next_step(), 1);
+        assert_eq!(
+            journal.interrupted.as_ref().map(|entry| entry.index),
+            Some(1)
+        );
+        journal.rollback().unwrap();
+        assert_eq!(
+            store().current_version("gradle").unwrap().as_deref(),
+            Some("7.6")
+        );
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn resumes_with_the_interrupted_step() {
+        memory_store();
+        Sdkman::new().clear_current_version("maven").unwrap();
+        let path = write_journal(
+            "resume",
+            &[
+                "step\tcurrent\tmaven\t3.8.7",
+                "step\tcurrent\tmaven\t3.9.0",
+                "begin\t0\tcurrent\t",
+                "done\t0",
+                "begin\t1\tcurrent\t3.8.7",
+            ],
+        );
+        Sdkman::new().use_version("maven", "3.8.7").unwrap();
+        let mut journal = Journal::read(&path).unwrap();
+        journal.undo_interrupted().unwrap();
+        assert_eq!(
+            store().current_version("maven").unwrap().as_deref(),
+            Some("3.8.7")
+        );
+        journal.apply(journal.next_step(), &mut |_| {}).unwrap();
+        assert_eq!(
+            store().current_version("maven").unwrap().as_deref(),
+            Some("3.9.0")
+        );
+
+        let journal = Journal::read(&path).unwrap();
+        assert!(journal.interrupted.is_none());
+        assert_eq!(journal.next_step(), 2);
+        journal.commit();
+        assert!(!path.exists());
+    }
+}
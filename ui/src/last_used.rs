@@ -0,0 +1,44 @@
+use std::fs;
+use std::fs::File;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::config::app_dir;
+
+const LAST_USED_DIR: &str = "last-used";
+
+// a file per version whose modification time is the last time it ran; this stays on this
+// machine and is far more reliable than the access times of the binaries
+pub fn record_path(binary_name: &str, version: &str) -> std::io::Result<PathBuf> {
+    app_dir().map(|dir| dir.join(LAST_USED_DIR).join(binary_name).join(version))
+}
+
+pub fn recorded(binary_name: &str, version: &str) -> Option<SystemTime> {
+    let path = record_path(binary_name, version).ok()?;
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+pub fn record(binary_name: &str, version: &str) -> std::io::Result<()> {
+    let path = record_path(binary_name, version)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    File::create(&path)?.set_modified(SystemTime::now())
+}
+
+// drops the record of a deleted version, and the directory of the candidate with its last one
+pub fn forget(binary_name: &str, version: &str) -> std::io::Result<()> {
+    let path = record_path(binary_name, version)?;
+    match fs::remove_file(&path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    if let Some(parent) = path.parent() {
+        // fails as long as other versions have records
+        let _ = fs::remove_dir(parent);
+    }
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+
+use eframe::egui::*;
+
+use api::install;
+use api::local::LocalCandidate;
+
+use crate::jobs::Step;
+use crate::repaint::RepaintNotifier;
+use crate::tasks;
+
+const PADDING: f32 = 8.0;
+const ARCHIVE_EXTENSIONS: [&str; 3] = [".tar.gz", ".tgz", ".zip"];
+
+pub fn is_archive(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    ARCHIVE_EXTENSIONS
+        .iter()
+        .any(|extension| name.ends_with(extension))
+}
+
+// an archive dropped onto a candidate, waiting for the version to install it as
+pub struct LocalInstall {
+    binary_name: String,
+    archive: PathBuf,
+    version: String,
+    checksum: Option<Result<String, String>>,
+    checksum_receiver: Receiver<Result<String, String>>,
+}
+
+impl LocalInstall {
+    pub fn new(binary_name: &str, archive: PathBuf, repaint: &RepaintNotifier) -> LocalInstall {
+        // hashing a JDK takes a moment, so it happens in the background
+        let (sender, checksum_receiver) = mpsc::channel();
+        let path = archive.clone();
+        let repaint = repaint.clone();
+        tasks::spawn(move || {
+            let _ = sender.send(install::checksum(&path).map_err(|e| e.to_string()));
+            repaint.notify();
+        });
+        LocalInstall {
+            binary_name: binary_name.to_string(),
+            version: guess_version(&archive),
+            archive,
+            checksum: None,
+            checksum_receiver,
+        }
+    }
+
+    // the step to run once confirmed, an error once cancelled
+    pub fn render(
+        &mut self,
+        ctx: &CtxRef,
+        local_candidates: &[LocalCandidate],
+    ) -> Option<Result<Step, ()>> {
+        if let Ok(checksum) = self.checksum_receiver.try_recv() {
+            self.checksum = Some(checksum);
+        }
+        let version = self.version.trim();
+        let taken = local_candidates
+            .iter()
+            .find(|local| *local.binary_name() == self.binary_name)
+            .is_some_and(|local| local.versions().contains_key(version));
+        let valid = !version.is_empty() && !version.contains(['/', '\\']) && !taken;
+        let mut decision = None;
+        Window::new("Install archive")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0., 0.])
+            .show(ctx, |ui| {
+                ui.add_space(PADDING);
+                Grid::new("local_install")
+                    .num_columns(2)
+                    .spacing([2. * PADDING, PADDING])
+                    .show(ui, |ui| {
+                        ui.label("Candidate:");
+                        ui.label(&self.binary_name);
+                        ui.end_row();
+                        ui.label("Archive:");
+                        ui.monospace(self.archive.display().to_string());
+                        ui.end_row();
+                        ui.label("SHA-256:");
+                        match &self.checksum {
+                            None => ui.label("computing…"),
+                            Some(Ok(checksum)) => ui.monospace(checksum),
+                            Some(Err(e)) => ui.label(format!("unavailable: {}", e)),
+                        };
+                        ui.end_row();
+                        ui.label("Version:");
+                        ui.text_edit_singleline(&mut self.version);
+                        ui.end_row();
+                    });
+                if taken {
+                    ui.label("This version is installed already.");
+                }
+                ui.add_space(PADDING);
+                ui.label(
+                    "Compare the checksum with the one the vendor publishes before installing.",
+                );
+                ui.add_space(PADDING);
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(valid, Button::new("Install")).clicked() {
+                        decision = Some(Ok(Step::from_archive(
+                            &self.binary_name,
+                            self.version.trim(),
+                            self.archive.clone(),
+                        )));
+                    }
+                    if ui.button("Cancel").clicked() {
+                        decision = Some(Err(()));
+                    }
+                });
+            });
+        decision
+    }
+}
+
+// the file name from its first number on, e.g. 3.9.0-bin-local for apache-maven-3.9.0-bin.zip
+fn guess_version(archive: &Path) -> String {
+    let name = archive
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let stem = ARCHIVE_EXTENSIONS
+        .iter()
+        .find_map(|extension| name.strip_suffix(extension))
+        .unwrap_or(&name);
+    let start = stem
+        .char_indices()
+        .find(|(index, c)| {
+            c.is_ascii_digit()
+                && stem[..*index]
+                    .chars()
+                    .last()
+                    .is_none_or(|before| before == '-' || before == '_')
+        })
+        .map(|(index, _)| index);
+    match start {
+        Some(start) => format!("{}-local", &stem[start..]),
+        None => "local".to_string(),
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::VecDeque;
+use std::io;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use lazy_static::lazy_static;
+use tracing_subscriber::EnvFilter;
+
+const CAPACITY: usize = 500;
+
+lazy_static! {
+    static ref BUFFER: Mutex<VecDeque<String>> = Mutex::new(VecDeque::with_capacity(CAPACITY));
+}
+
+// prints the message to stderr and keeps the most recent ones around for crash reports
+macro_rules! log {
+    ($($arg:tt)*) => {
+        $crate::logs::record(format!($($arg)*))
+    };
+}
+
+// the requests of the api crate end up in the same log, RUST_LOG tells which ones
+pub fn init_tracing() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("api=info"));
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_ansi(false)
+        .without_time()
+        .with_writer(|| Writer)
+        .try_init();
+}
+
+// hands every formatted event to record
+struct Writer;
+
+impl io::Write for Writer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        record(String::from_utf8_lossy(buf).trim_end().to_string());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// stdout is left to the data the command line modes print, e.g. --export-catalog
+pub fn record(message: String) {
+    eprintln!("{}", message);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    // a panic while holding the lock must not prevent the crash report from reading the logs
+    let mut buffer = BUFFER.lock().unwrap_or_else(|e| e.into_inner());
+    if buffer.len() == CAPACITY {
+        buffer.pop_front();
+    }
+    buffer.push_back(format!("{} {}", timestamp, message));
+}
+
+pub fn snapshot() -> Vec<String> {
+    BUFFER
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .cloned()
+        .collect()
+}
@@ -1,84 +1,293 @@
 use std::env;
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::thread;
 
 use eframe::egui::CentralPanel;
 use eframe::egui::ScrollArea;
 use eframe::egui::Vec2;
 use eframe::epi::App;
+use eframe::epi::IconData;
 use eframe::run_native;
 use eframe::NativeOptions;
 
+use api::dirs::SdkmanDirs;
 use api::local::retrieve_local_candidates;
+use api::manifest::Kind;
+use api::mock::MockSource;
+use api::remote;
 use api::remote::fetch_remote_candidates;
+use api::sdkman::Sdkman;
 use candidates::SdkmanApp;
+use candidates::Tab;
+use candidates::RESUME_JOBS_FLAG;
+use config::Config;
+use export::CatalogFormat;
 
+#[macro_use]
+mod logs;
+
+mod about;
+mod analytics;
+mod app_state;
 mod candidates;
+mod columns;
+mod compare;
+mod config;
+mod config_watch;
+mod crash;
+mod dashboard;
+mod diagnostics;
+mod disk_watch;
+mod doctor;
+mod download_preview;
+mod elevation;
+mod empty;
+mod environment;
+mod export;
+mod filters;
+mod history;
+mod homepage;
+mod jobs;
+mod journal;
+mod last_used;
+mod local_install;
+mod onboarding;
+mod perf;
+mod profiles;
+mod projects;
+mod protected;
+mod refresh;
+mod remove_all;
+mod repaint;
+mod run;
+mod screenshot;
+mod search;
+mod settings;
+mod shims;
+mod snapshots;
+mod state;
+mod subscriptions;
+mod summary;
+mod switch_preview;
+mod tasks;
+mod time_machine;
+mod transaction;
+mod util;
 
 impl App for SdkmanApp {
     fn update(&mut self, ctx: &eframe::egui::CtxRef, frame: &mut eframe::epi::Frame<'_>) {
+        self.begin_frame(ctx);
+        self.receive_state();
+        self.receive_config(ctx);
+        self.receive_jobs();
+        self.receive_disk_space();
+        self.receive_restored_candidate();
+        self.handle_dropped_files(ctx);
         self.render_top_panel(ctx, frame);
+        self.render_homepage(ctx);
+        self.render_panes(ctx);
         CentralPanel::default().show(ctx, |ui| {
-            ScrollArea::vertical().show(ui, |ui| {
-                self.render_candidates(ctx, ui);
+            self.render_tabs(ui);
+            ScrollArea::vertical().show(ui, |ui| match self.tab() {
+                Tab::Candidates => self.render_candidates(ctx, ui),
+                Tab::Dashboard => self.render_dashboard(ctx, ui),
+                Tab::Projects => self.render_projects(ctx, ui),
+                Tab::Doctor => self.render_doctor(ctx, ui),
+                Tab::Subscriptions => self.render_subscriptions(ctx, ui),
             });
             self.render_footer(ctx);
         });
+        self.render_settings(ctx);
+        self.render_jobs(ctx);
+        self.render_environment(ctx);
+        self.render_local_install(ctx);
+        self.render_download_preview(ctx);
+        self.render_run(ctx);
+        self.render_delete_warning(ctx);
+        self.render_switch_preview(ctx);
+        self.render_remove_all(ctx);
+        self.render_weekly_summary(ctx);
+        self.render_snapshots(ctx);
+        self.render_time_machine(ctx);
+        self.render_export_catalog(ctx);
+        self.render_profile_editor(ctx);
+        self.render_about(ctx);
+        self.render_crash_report(ctx);
+        self.render_interrupted_jobs(ctx);
+        self.render_onboarding(ctx);
+        self.render_quit_prompt(ctx, frame);
+        self.end_frame(ctx, frame);
     }
 
     fn setup(
         &mut self,
         ctx: &eframe::egui::CtxRef,
-        _frame: &mut eframe::epi::Frame<'_>,
+        frame: &mut eframe::epi::Frame<'_>,
         _storage: Option<&dyn eframe::epi::Storage>,
     ) {
         self.configure_fonts(ctx);
+        self.start_background_tasks(frame);
+        self.apply_theme(ctx);
     }
 
     fn name(&self) -> &str {
         self.app_name()
     }
+
+    fn on_exit(&mut self) {
+        self.shutdown();
+    }
 }
 
+const WINDOW_SIZE: Vec2 = Vec2::new(1024., 960.);
+
 fn main() {
+    crash::install_panic_hook();
+    logs::init_tracing();
+    let args: Vec<String> = env::args().collect();
+    let screenshots = dir_arg(&args, "--screenshots", "doc/screenshots");
+    // --mock serves canned candidates, e.g. for screenshots or working without a network
+    let mock = screenshots.is_some() || args.iter().any(|arg| arg == "--mock");
     if cfg!(target_os = "windows") {
-        println!("sdkman is not for windows!")
+        log!("sdkman is not for windows!")
         // for this show a dialog
-    } else if env::var("SDKMAN_DIR").is_err() {
-        println!("sdkman is not installed!")
+    } else if SdkmanDirs::resolve().is_err() && !mock {
+        log!("sdkman is not installed!")
     } else {
-        let remote_candidates_handle = thread::spawn(|| match fetch_remote_candidates() {
-            Ok(candidates) => candidates,
-            Err(e) => {
-                println!("Failed to retrieve remote candidates: {}", e);
-                Vec::new()
+        // screenshots look the same no matter how the app has been set up
+        let config = if screenshots.is_some() {
+            Config {
+                onboarded: true,
+                ..Default::default()
             }
-        });
-        let local_candidates_handle = thread::spawn(|| match retrieve_local_candidates() {
-            Ok(candidates) => candidates,
-            Err(e) => {
-                println!("Failed to retrieve local candidates: {}", e);
+        } else {
+            Config::load()
+        };
+        config.configure_installation();
+        config.configure_scan();
+        config.configure_cache();
+        config.configure_downloads();
+        config.configure_api();
+        if mock {
+            log!("Serving canned candidates instead of the SDKMAN API");
+            remote::set_source(Arc::new(MockSource));
+        }
+        if let Some(dir) = dir_arg(&args, "--record-fixtures", "api/tests/fixtures") {
+            log!("Recording the API responses to {}", dir.display());
+            remote::record_fixtures(Some(dir));
+        }
+        // finishes the jobs a quitting window left in the background
+        if args.iter().any(|arg| arg == RESUME_JOBS_FLAG) {
+            jobs::resume_headless(config.verify_installs);
+            return;
+        }
+        // prints the version a shorthand stands for, e.g. `--resolve java 21-tem` in a script
+        if let Some(index) = args.iter().position(|arg| arg == "--resolve") {
+            let binary_name = args.get(index + 1).map_or("", String::as_str);
+            let alias = args.get(index + 2).map_or("default", String::as_str);
+            match Sdkman::new().resolve(binary_name, alias) {
+                Ok(version) => println!("{}", version),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        // prints the catalog with the installed versions, e.g. `--export-catalog markdown > sdks.md`
+        if let Some(index) = args.iter().position(|arg| arg == "--export-catalog") {
+            let name = args.get(index + 1).map_or("csv", String::as_str);
+            let format = match CatalogFormat::parse(name) {
+                Some(format) => format,
+                None => {
+                    eprintln!("Unknown format '{}', use csv or markdown", name);
+                    std::process::exit(1);
+                }
+            };
+            let local_candidates = retrieve_local_candidates().unwrap_or_else(|e| {
+                log!("Failed to retrieve local candidates: {}", e);
                 Vec::new()
+            });
+            match fetch_remote_candidates() {
+                Ok(remote_candidates) => {
+                    println!("{}", format.render(&remote_candidates, &local_candidates))
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        // prints the JSON Schema of an exported file, e.g. `--print-schema snapshot` for an editor
+        if let Some(index) = args.iter().position(|arg| arg == "--print-schema") {
+            let name = args.get(index + 1).map_or("", String::as_str);
+            match Kind::all().iter().find(|kind| kind.to_string() == name) {
+                Some(kind) => println!("{}", kind.json_schema()),
+                None => {
+                    eprintln!(
+                        "Unknown schema '{}', use settings, snapshot or sdkmanrc",
+                        name
+                    );
+                    std::process::exit(1);
+                }
             }
+            return;
+        }
+        let remote_candidates_handle = thread::spawn(|| {
+            // the reason is kept to explain the empty candidate list
+            fetch_remote_candidates().map_err(|e| {
+                log!("Failed to retrieve remote candidates: {}", e);
+                e.to_string()
+            })
         });
-
-        match (
-            remote_candidates_handle.join(),
-            local_candidates_handle.join(),
-        ) {
-            (Ok(remote_candidates), Ok(local_candidates)) => {
-                let app = SdkmanApp::new(&remote_candidates, &local_candidates);
+        match remote_candidates_handle.join() {
+            Ok(remote_candidates) => {
+                // the installed versions are scanned once the window is open, see scan_local_on_start
+                let mut app = SdkmanApp::new(remote_candidates, Vec::new(), config);
+                if let Some(dir) = screenshots {
+                    if let Err(e) = screenshot::render_views(app, WINDOW_SIZE, &dir) {
+                        log!("Failed to render the screenshots: {}", e);
+                    }
+                    return;
+                }
+                app.scan_local_on_start();
                 let win_option = NativeOptions {
-                    initial_window_size: Some(Vec2::new(1024., 960.)),
+                    initial_window_size: Some(WINDOW_SIZE),
+                    drag_and_drop_support: true,
+                    icon_data: window_icon(),
                     ..Default::default()
                 };
                 run_native(Box::new(app), win_option);
             }
-            (Err(_), _) => {
-                println!("Remote candidates retrieval thread failed.");
-            }
-            (_, Err(_)) => {
-                println!("Local candidates retrieval thread failed.");
+            Err(_) => {
+                log!("Remote candidates retrieval thread failed.");
             }
         }
     }
 }
+
+// the logo in the title bar and the dock
+fn window_icon() -> Option<IconData> {
+    let image = image::load_from_memory(include_bytes!("../assets/logo.png"))
+        .map_err(|e| log!("Failed to load the window icon: {}", e))
+        .ok()?
+        .to_rgba8();
+    let (width, height) = image.dimensions();
+    Some(IconData {
+        rgba: image.into_raw(),
+        width,
+        height,
+    })
+}
+
+// the directory following a flag like --record-fixtures [dir], if the flag is there at all
+fn dir_arg(args: &[String], flag: &str, default: &str) -> Option<PathBuf> {
+    let index = args.iter().position(|arg| arg == flag)?;
+    let dir = args
+        .get(index + 1)
+        .filter(|arg| !arg.starts_with("--"))
+        .map_or(default, |arg| arg.as_str());
+    Some(PathBuf::from(dir))
+}
@@ -0,0 +1,230 @@
+use std::path::Path;
+
+use eframe::egui::*;
+
+use api::category;
+use api::dirs::SdkmanDirs;
+use api::remote;
+
+use crate::config::Config;
+use crate::config::Theme;
+
+const PADDING: f32 = 8.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Step {
+    Installation,
+    Platform,
+    Theme,
+    Favorites,
+    BackgroundRefresh,
+}
+
+impl Step {
+    fn next(&self) -> Option<Step> {
+        match self {
+            Step::Installation => Some(Step::Platform),
+            Step::Platform => Some(Step::Theme),
+            Step::Theme => Some(Step::Favorites),
+            Step::Favorites => Some(Step::BackgroundRefresh),
+            Step::BackgroundRefresh => None,
+        }
+    }
+    fn previous(&self) -> Option<Step> {
+        match self {
+            Step::Installation => None,
+            Step::Platform => Some(Step::Installation),
+            Step::Theme => Some(Step::Platform),
+            Step::Favorites => Some(Step::Theme),
+            Step::BackgroundRefresh => Some(Step::Favorites),
+        }
+    }
+    fn title(&self) -> &'static str {
+        match self {
+            Step::Installation => "1/5 SDKMAN installation",
+            Step::Platform => "2/5 Platform",
+            Step::Theme => "3/5 Theme",
+            Step::Favorites => "4/5 Favorite candidates",
+            Step::BackgroundRefresh => "5/5 Background refresh",
+        }
+    }
+}
+
+pub struct Onboarding {
+    step: Step,
+    config: Config,
+}
+
+impl Onboarding {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            step: Step::Installation,
+            config: config.clone(),
+        }
+    }
+
+    // renders the wizard and returns the resulting configuration once the user is done
+    pub fn render(
+        &mut self,
+        ctx: &CtxRef,
+        candidates: &[(String, String)],
+        local_candidates_count: usize,
+    ) -> Option<Config> {
+        let Self { step, config } = self;
+        let mut finished = false;
+
+        Window::new("Welcome to sdkman-ui")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0., 0.])
+            .show(ctx, |ui| {
+                ui.add_space(PADDING);
+                ui.heading(step.title());
+                ui.add_space(PADDING);
+
+                match step {
+                    Step::Installation => {
+                        Onboarding::render_installation(ui, local_candidates_count)
+                    }
+                    Step::Platform => Onboarding::render_platform(ui),
+                    Step::Theme => Onboarding::render_theme(ctx, ui, config),
+                    Step::Favorites => Onboarding::render_favorites(ui, config, candidates),
+                    Step::BackgroundRefresh => Onboarding::render_background_refresh(ui, config),
+                }
+
+                ui.add_space(2. * PADDING);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(step.previous().is_some(), Button::new("Back"))
+                        .clicked()
+                    {
+                        *step = step.previous().unwrap_or(*step);
+                    }
+                    ui.with_layout(Layout::right_to_left(), |ui| match step.next() {
+                        Some(next) => {
+                            if ui.button("Next").clicked() {
+                                *step = next;
+                            }
+                        }
+                        None => {
+                            if ui.button("Finish").clicked() {
+                                finished = true;
+                            }
+                        }
+                    });
+                });
+                ui.add_space(PADDING);
+            });
+
+        if finished {
+            config.onboarded = true;
+            Some(config.clone())
+        } else {
+            None
+        }
+    }
+
+    fn render_installation(ui: &mut Ui, local_candidates_count: usize) {
+        let dirs = SdkmanDirs::resolve().ok();
+        let show = |path: fn(&SdkmanDirs) -> &Path| {
+            dirs.as_ref()
+                .map(|dirs| path(dirs).display().to_string())
+                .unwrap_or_else(|| "not found".to_string())
+        };
+        let sdkman_dir = show(|dirs| dirs.root.as_path());
+        let candidates_dir = show(|dirs| dirs.candidates.as_path());
+        let layout = match &dirs {
+            Some(dirs) if dirs.is_native() => "native",
+            Some(_) => "classic",
+            None => "unknown",
+        };
+        Grid::new("onboarding_installation")
+            .num_columns(2)
+            .spacing([4. * PADDING, PADDING])
+            .show(ui, |ui| {
+                ui.label("SDKMAN directory:");
+                ui.monospace(sdkman_dir);
+                ui.end_row();
+                ui.label("Candidates directory:");
+                ui.monospace(candidates_dir);
+                ui.end_row();
+                ui.label("Layout:");
+                ui.monospace(layout);
+                ui.end_row();
+                ui.label("Installed candidates:");
+                ui.monospace(local_candidates_count.to_string());
+                ui.end_row();
+            });
+    }
+
+    fn render_platform(ui: &mut Ui) {
+        let platform = remote::platform();
+        let platform = if platform.is_empty() {
+            "unknown".to_string()
+        } else {
+            platform
+        };
+        ui.horizontal(|ui| {
+            ui.label("Detected platform:");
+            ui.monospace(platform);
+        });
+        ui.add_space(PADDING);
+        ui.label("Versions are listed and installed for this platform.");
+    }
+
+    fn render_theme(ctx: &CtxRef, ui: &mut Ui, config: &mut Config) {
+        ui.horizontal(|ui| {
+            let dark = ui.radio_value(&mut config.theme, Theme::Dark, "Dark");
+            let light = ui.radio_value(&mut config.theme, Theme::Light, "Light");
+            if dark.changed() || light.changed() {
+                config.theme.apply(ctx);
+            }
+        });
+    }
+
+    fn render_favorites(ui: &mut Ui, config: &mut Config, candidates: &[(String, String)]) {
+        ui.label("Pinned candidates are always listed first.");
+        ui.add_space(PADDING);
+        // the suggested ones first, in the order of the metadata
+        let suggested = category::suggested();
+        let mut candidates: Vec<&(String, String)> = candidates.iter().collect();
+        candidates.sort_by_key(|(_, binary_name)| {
+            suggested
+                .iter()
+                .position(|name| name == binary_name)
+                .unwrap_or(usize::MAX)
+        });
+        ScrollArea::vertical().max_height(300.).show(ui, |ui| {
+            for (name, binary_name) in candidates {
+                let mut pinned = config.is_pinned(binary_name);
+                let label = if suggested.contains(binary_name) {
+                    format!("{} ★", name)
+                } else {
+                    name.clone()
+                };
+                if ui.checkbox(&mut pinned, label).changed() {
+                    if pinned {
+                        config.pinned_candidates.push(binary_name.clone());
+                    } else {
+                        config.pinned_candidates.retain(|p| p != binary_name);
+                    }
+                }
+            }
+        });
+    }
+
+    fn render_background_refresh(ui: &mut Ui, config: &mut Config) {
+        ui.checkbox(
+            &mut config.background_refresh,
+            "Refresh the list of candidates in the background",
+        );
+        ui.add_enabled_ui(config.background_refresh, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Every");
+                ui.add(DragValue::new(&mut config.refresh_interval_minutes).clamp_range(5..=1440));
+                ui.label("minutes");
+            });
+        });
+    }
+}
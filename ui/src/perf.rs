@@ -0,0 +1,81 @@
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use eframe::egui::*;
+
+use crate::tasks;
+
+const SAMPLES: usize = 120;
+
+#[derive(Default)]
+pub struct PerfOverlay {
+    visible: bool,
+    frame_start: Option<Instant>,
+    frame_times: VecDeque<f32>,
+    widgets: usize,
+}
+
+impl PerfOverlay {
+    pub fn begin_frame(&mut self, ctx: &CtxRef) {
+        let input = ctx.input();
+        if input.modifiers.ctrl && input.modifiers.shift && input.key_pressed(Key::D) {
+            self.visible = !self.visible;
+        }
+        self.frame_start = Some(Instant::now());
+        self.widgets = 0;
+    }
+
+    pub fn end_frame(&mut self) {
+        if let Some(start) = self.frame_start.take() {
+            if self.frame_times.len() == SAMPLES {
+                self.frame_times.pop_front();
+            }
+            self.frame_times
+                .push_back(start.elapsed().as_secs_f32() * 1000.);
+        }
+    }
+
+    pub fn count_widgets(&mut self, count: usize) {
+        self.widgets += count;
+    }
+
+    pub fn render(&self, ctx: &CtxRef, cpu_usage: Option<f32>) {
+        if !self.visible {
+            return;
+        }
+        let average = if self.frame_times.is_empty() {
+            0.
+        } else {
+            self.frame_times.iter().sum::<f32>() / self.frame_times.len() as f32
+        };
+        let max = self.frame_times.iter().cloned().fold(0., f32::max);
+        Window::new("Performance")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::RIGHT_BOTTOM, [-10., -10.])
+            .show(ctx, |ui| {
+                Grid::new("performance").num_columns(2).show(ui, |ui| {
+                    ui.label("Frame time (avg):");
+                    ui.monospace(format!("{:.2} ms", average));
+                    ui.end_row();
+                    ui.label("Frame time (max):");
+                    ui.monospace(format!("{:.2} ms", max));
+                    ui.end_row();
+                    ui.label("CPU time (last frame):");
+                    ui.monospace(
+                        cpu_usage
+                            .map(|seconds| format!("{:.2} ms", seconds * 1000.))
+                            .unwrap_or_else(|| "-".to_string()),
+                    );
+                    ui.end_row();
+                    ui.label("Rendered rows:");
+                    ui.monospace(self.widgets.to_string());
+                    ui.end_row();
+                    ui.label("Background tasks:");
+                    ui.monospace(tasks::pending().to_string());
+                    ui.end_row();
+                });
+                ui.add(Label::new("Ctrl+Shift+D to hide").small());
+            });
+    }
+}
@@ -0,0 +1,136 @@
+use eframe::egui::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::filters::SmartFilter;
+use crate::snapshots::Snapshots;
+
+const PADDING: f32 = 8.0;
+
+// what to switch to at once, e.g. "Android dev" or "Scala dev"; saved in the config
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub name: String,
+    pub pinned_candidates: Vec<String>,
+    // the snapshot of current versions to restore
+    pub snapshot: Option<String>,
+    // the smart filter to apply
+    pub filter: Option<String>,
+}
+
+pub enum ProfileOutcome {
+    Open,
+    Save(Profile),
+    Delete(String),
+    Cancel,
+}
+
+pub struct ProfileEditor {
+    draft: Profile,
+    // the snapshots there are to pick from
+    snapshots: Vec<String>,
+}
+
+impl ProfileEditor {
+    pub fn new(draft: Profile) -> ProfileEditor {
+        ProfileEditor {
+            draft,
+            snapshots: Snapshots::load().names().cloned().collect(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.draft.name
+    }
+
+    // edits a profile; saving one under an existing name replaces it
+    pub fn render(
+        &mut self,
+        ctx: &CtxRef,
+        saved: bool,
+        pinned_candidates: &[String],
+        filters: &[SmartFilter],
+    ) -> ProfileOutcome {
+        let Self { draft, snapshots } = self;
+        let mut outcome = ProfileOutcome::Open;
+        Window::new("Profile")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0., 0.])
+            .show(ctx, |ui| {
+                ui.add_space(PADDING);
+                Grid::new("profile")
+                    .spacing([2. * PADDING, PADDING])
+                    .show(ui, |ui| {
+                        ui.label("Name:");
+                        ui.text_edit_singleline(&mut draft.name);
+                        ui.end_row();
+
+                        ui.label("Pinned:");
+                        ui.vertical(|ui| {
+                            ui.add(Label::new(pin_list(&draft.pinned_candidates)).wrap(true));
+                            if ui.button("Take the current pins").clicked() {
+                                draft.pinned_candidates = pinned_candidates.to_vec();
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Snapshot:");
+                        ComboBox::from_id_source("profile_snapshot")
+                            .selected_text(draft.snapshot.as_deref().unwrap_or("None"))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut draft.snapshot, None, "None");
+                                for name in snapshots.iter() {
+                                    ui.selectable_value(
+                                        &mut draft.snapshot,
+                                        Some(name.clone()),
+                                        name,
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label("Filter:");
+                        ComboBox::from_id_source("profile_filter")
+                            .selected_text(draft.filter.as_deref().unwrap_or("All candidates"))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut draft.filter, None, "All candidates");
+                                for filter in filters {
+                                    ui.selectable_value(
+                                        &mut draft.filter,
+                                        Some(filter.name.clone()),
+                                        &filter.name,
+                                    );
+                                }
+                            });
+                        ui.end_row();
+                    });
+                ui.add_space(PADDING);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!draft.name.trim().is_empty(), Button::new("Save"))
+                        .clicked()
+                    {
+                        outcome = ProfileOutcome::Save(draft.clone());
+                    }
+                    if saved && ui.button("Delete").clicked() {
+                        outcome = ProfileOutcome::Delete(draft.name.clone());
+                    }
+                    if ui.button("Cancel").clicked() {
+                        outcome = ProfileOutcome::Cancel;
+                    }
+                });
+            });
+        outcome
+    }
+}
+
+fn pin_list(pinned_candidates: &[String]) -> String {
+    if pinned_candidates.is_empty() {
+        "None".to_string()
+    } else {
+        pinned_candidates.join(", ")
+    }
+}
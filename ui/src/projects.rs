@@ -0,0 +1,481 @@
+use std::collections::HashMap;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+use eframe::egui::*;
+
+use api::local;
+use api::local::LocalCandidate;
+use api::sdkman_config;
+use api::sdkman_config::SdkmanConfig;
+
+use crate::environment;
+use crate::environment::SDKMANRC;
+use crate::history::Action;
+use crate::jobs::Step;
+use crate::last_used;
+
+const PADDING: f32 = 8.0;
+const GRADLE_PROPERTIES: &str = "gradle.properties";
+const TOOL_VERSIONS: &str = ".tool-versions";
+
+pub enum ProjectsOutcome {
+    Add(PathBuf),
+    Remove(PathBuf),
+    Sync(Vec<Step>),
+    Terminal(PathBuf, Vec<(String, String)>),
+}
+
+// what a project's .sdkmanrc asks for, checked against the installed versions when it's read
+// and whenever they change rather than on every frame
+struct ProjectRequirements {
+    requirements: Result<Vec<(String, String)>, String>,
+    // per requirement
+    installed: Vec<bool>,
+    missing: Vec<Step>,
+    status: String,
+}
+
+impl ProjectRequirements {
+    fn read(project: &Path, local_candidates: &[LocalCandidate]) -> ProjectRequirements {
+        let mut requirements = ProjectRequirements {
+            requirements: read_requirements(project),
+            installed: Vec::new(),
+            missing: Vec::new(),
+            status: String::new(),
+        };
+        requirements.resolve(local_candidates);
+        requirements
+    }
+
+    fn resolve(&mut self, local_candidates: &[LocalCandidate]) {
+        let requirements = match &self.requirements {
+            Ok(requirements) => requirements,
+            Err(_) => {
+                self.status = format!("no readable {}", SDKMANRC);
+                return;
+            }
+        };
+        self.installed = requirements
+            .iter()
+            .map(|(binary_name, version)| {
+                environment::is_installed(local_candidates, binary_name, version)
+            })
+            .collect();
+        self.missing = requirements
+            .iter()
+            .zip(&self.installed)
+            .filter(|(_, installed)| !**installed)
+            .map(|((binary_name, version), _)| Step::new(binary_name, version, Action::Install))
+            .collect();
+        self.status = if self.missing.is_empty() {
+            format!("✔ {} versions installed", requirements.len())
+        } else {
+            format!(
+                "{} of {} versions missing",
+                self.missing.len(),
+                requirements.len()
+            )
+        };
+    }
+}
+
+// the registered project directories and what their .sdkmanrc files ask for
+#[derive(Default)]
+pub struct Projects {
+    requirements: HashMap<PathBuf, ProjectRequirements>,
+    new_project: String,
+    // whether SDKMAN switches to the versions of a .sdkmanrc by itself on cd
+    auto_env: bool,
+}
+
+impl Projects {
+    // the files are tiny, but they're only read when the tab opens or on request
+    pub fn load(&mut self, projects: &[PathBuf], local_candidates: &[LocalCandidate]) {
+        self.requirements = projects
+            .iter()
+            .map(|project| {
+                let requirements = ProjectRequirements::read(project, local_candidates);
+                (project.clone(), requirements)
+            })
+            .collect();
+        self.auto_env = SdkmanConfig::load()
+            .map(|config| config.flag(sdkman_config::AUTO_ENV))
+            .unwrap_or_default();
+    }
+
+    // the installed versions changed, the files stay as they were read
+    pub fn resolve(&mut self, local_candidates: &[LocalCandidate]) {
+        for requirements in self.requirements.values_mut() {
+            requirements.resolve(local_candidates);
+        }
+    }
+
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        projects: &[PathBuf],
+        local_candidates: &[LocalCandidate],
+    ) -> Option<ProjectsOutcome> {
+        let mut outcome = None;
+
+        ui.add_space(PADDING);
+        ui.horizontal(|ui| {
+            ui.heading("Projects");
+            if ui
+                .button("⟳")
+                .on_hover_text("Read the files again")
+                .clicked()
+            {
+                self.load(projects, local_candidates);
+            }
+        });
+        ui.add_space(PADDING);
+        ui.horizontal(|ui| {
+            ui.label("Project directory:");
+            ui.text_edit_singleline(&mut self.new_project);
+            let path = PathBuf::from(self.new_project.trim());
+            let addable = path.is_dir() && !projects.contains(&path);
+            if ui.add_enabled(addable, Button::new("Add")).clicked() {
+                self.requirements.insert(
+                    path.clone(),
+                    ProjectRequirements::read(&path, local_candidates),
+                );
+                self.new_project.clear();
+                outcome = Some(ProjectsOutcome::Add(path));
+            }
+        });
+        ui.add_space(PADDING);
+        if projects.is_empty() {
+            ui.label(format!(
+                "Register the directories of your projects to keep the versions their {} files ask for installed.",
+                SDKMANRC
+            ));
+        } else if self.auto_env {
+            ui.label(format!(
+                "sdkman_auto_env is on, shells switch to the versions of a {} when entering its directory.",
+                SDKMANRC
+            ));
+        } else {
+            ui.label(format!(
+                "sdkman_auto_env is off, run sdk env in a project to use the versions of its {}.",
+                SDKMANRC
+            ));
+        }
+
+        for project in projects {
+            let ProjectRequirements {
+                requirements,
+                installed,
+                missing,
+                status,
+            } = self
+                .requirements
+                .entry(project.clone())
+                .or_insert_with(|| ProjectRequirements::read(project, local_candidates));
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.add(Label::new(project_name(project)).strong());
+                if missing.is_empty() {
+                    ui.label(status.as_str());
+                } else {
+                    ui.colored_label(Color32::YELLOW, status.as_str());
+                }
+                if ui
+                    .add_enabled(!missing.is_empty(), Button::new("Sync environment"))
+                    .on_hover_text("Install the missing versions")
+                    .clicked()
+                {
+                    outcome = Some(ProjectsOutcome::Sync(missing.clone()));
+                }
+                if let Ok(requirements) = requirements {
+                    if ui
+                        .button("Terminal")
+                        .on_hover_text("Open a terminal with these versions on the PATH")
+                        .clicked()
+                    {
+                        outcome = Some(ProjectsOutcome::Terminal(
+                            project.clone(),
+                            requirements.clone(),
+                        ));
+                    }
+                }
+                if ui.button("Remove").clicked() {
+                    outcome = Some(ProjectsOutcome::Remove(project.clone()));
+                }
+            });
+            CollapsingHeader::new(project.display().to_string())
+                .id_source(("project", project))
+                .show(ui, |ui| match requirements {
+                    Ok(requirements) if requirements.is_empty() => {
+                        ui.label("The file doesn't ask for any versions.");
+                    }
+                    Ok(requirements) => {
+                        Grid::new(("project_requirements", project))
+                            .striped(true)
+                            .spacing([2. * PADDING, PADDING])
+                            .show(ui, |ui| {
+                                for ((binary_name, version), installed) in
+                                    requirements.iter().zip(installed.iter())
+                                {
+                                    ui.label(binary_name);
+                                    ui.monospace(version);
+                                    if *installed {
+                                        ui.label("✔ installed");
+                                    } else {
+                                        ui.colored_label(Color32::YELLOW, "missing");
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    }
+                    Err(e) => {
+                        ui.label(e.as_str());
+                    }
+                });
+        }
+
+        if let Some(ProjectsOutcome::Remove(project)) = &outcome {
+            self.requirements.remove(project);
+        }
+        outcome
+    }
+}
+
+// starts the terminal in the project directory with the installed versions it asks for
+// activated the way sdk env does: their bin directories first on the PATH and their homes set
+pub fn launch_terminal(
+    project: &Path,
+    requirements: &[(String, String)],
+    terminal_command: &str,
+    local_candidates: &[LocalCandidate],
+) -> std::io::Result<()> {
+    launch(
+        project,
+        requirements,
+        terminal_command,
+        local_candidates,
+        None,
+    )
+}
+
+// a terminal in the home directory, running a program like jshell instead of a shell if given
+pub fn launch_in_terminal(
+    program: Option<&Path>,
+    requirements: &[(String, String)],
+    terminal_command: &str,
+    local_candidates: &[LocalCandidate],
+) -> std::io::Result<()> {
+    let dir = env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(env::temp_dir);
+    launch(
+        &dir,
+        requirements,
+        terminal_command,
+        local_candidates,
+        program,
+    )
+}
+
+fn launch(
+    project: &Path,
+    requirements: &[(String, String)],
+    terminal_command: &str,
+    local_candidates: &[LocalCandidate],
+    program: Option<&Path>,
+) -> std::io::Result<()> {
+    let mut homes = Vec::new();
+    for (binary_name, version) in requirements {
+        if environment::is_installed(local_candidates, binary_name, version) {
+            homes.push((binary_name, local::home_path(binary_name, version)?));
+        }
+    }
+    let mut paths: Vec<PathBuf> = homes.iter().map(|(_, home)| home.join("bin")).collect();
+    if let Some(path) = env::var_os("PATH") {
+        paths.extend(env::split_paths(&path));
+    }
+    let path = env::join_paths(paths)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+    let mut vars: Vec<(String, OsString)> = vec![("PATH".to_string(), path)];
+    for (binary_name, home) in &homes {
+        vars.push((
+            format!("{}_HOME", binary_name.to_uppercase()),
+            home.clone().into_os_string(),
+        ));
+    }
+
+    match terminal_command.trim() {
+        "" if cfg!(target_os = "macos") => launch_terminal_app(project, &vars, program)?,
+        command => {
+            let command = if command.is_empty() {
+                default_terminal()
+            } else {
+                command
+            };
+            // quoted like in a shell, so paths with spaces stay in one piece
+            let mut parts = shlex::split(command).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Can't make sense of the terminal command '{}'", command),
+                )
+            })?;
+            if let Some(program) = program {
+                // the default commands end with what they open, which becomes the program
+                if parts.len() > 1
+                    && matches!(parts.last().map(String::as_str), Some(".") | Some("cmd"))
+                {
+                    parts.pop();
+                } else {
+                    let exec_args = exec_args(parts.first().map_or("", String::as_str));
+                    parts.extend(exec_args.iter().map(ToString::to_string));
+                }
+                parts.push(program.to_string_lossy().to_string());
+            }
+            let (terminal, args) = parts.split_first().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "The terminal command is empty")
+            })?;
+            Command::new(terminal)
+                .args(args)
+                .current_dir(project)
+                .envs(vars)
+                .spawn()?;
+        }
+    }
+    for (binary_name, version) in requirements {
+        if environment::is_installed(local_candidates, binary_name, version) {
+            last_used::record(binary_name, version)?;
+        }
+    }
+    Ok(())
+}
+
+// how a terminal emulator is told to run a program instead of a shell
+fn exec_args(terminal: &str) -> &'static [&'static str] {
+    let name = Path::new(terminal)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match name.as_str() {
+        "gnome-terminal" | "kgx" | "ptyxis" => &["--"],
+        "wezterm" => &["start", "--"],
+        "kitty" => &[],
+        _ => &["-e"],
+    }
+}
+
+// macOS starts Terminal.app through launchd, which doesn't pass the environment on, so the
+// window is told to set it up itself
+fn launch_terminal_app(
+    project: &Path,
+    vars: &[(String, OsString)],
+    program: Option<&Path>,
+) -> io::Result<()> {
+    let quote = |value: &str| {
+        shlex::try_quote(value)
+            .map(|quoted| quoted.to_string())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))
+    };
+    let mut script = format!("cd {}", quote(&project.to_string_lossy())?);
+    for (name, value) in vars {
+        script.push_str(&format!(
+            " && export {}={}",
+            name,
+            quote(&value.to_string_lossy())?
+        ));
+    }
+    if let Some(program) = program {
+        script.push_str(&format!(" && {}", quote(&program.to_string_lossy())?));
+    }
+    Command::new("osascript")
+        .arg("-e")
+        .arg(format!(
+            "tell application \"Terminal\" to do script {}",
+            applescript_string(&script)
+        ))
+        .arg("-e")
+        .arg("tell application \"Terminal\" to activate")
+        .spawn()?;
+    Ok(())
+}
+
+fn applescript_string(value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\");
+    format!("\"{}\"", str::replace(&escaped, '"', "\\\""))
+}
+
+fn default_terminal() -> &'static str {
+    if cfg!(windows) {
+        "cmd /c start cmd"
+    } else {
+        "x-terminal-emulator"
+    }
+}
+
+// the files of the projects that still refer to an installed version
+pub fn find_usages(projects: &[PathBuf], binary_name: &str, version: &str) -> Vec<PathBuf> {
+    projects
+        .iter()
+        .flat_map(|project| {
+            [SDKMANRC, GRADLE_PROPERTIES, TOOL_VERSIONS]
+                .into_iter()
+                .map(move |file_name| (file_name, project.join(file_name)))
+        })
+        .filter(|(file_name, path)| {
+            fs::read_to_string(path)
+                .is_ok_and(|content| refers_to(file_name, &content, binary_name, version))
+        })
+        .map(|(_, path)| path)
+        .collect()
+}
+
+fn refers_to(file_name: &str, content: &str, binary_name: &str, version: &str) -> bool {
+    match file_name {
+        SDKMANRC => environment::parse(content)
+            .iter()
+            .any(|(b, v)| b == binary_name && v == version),
+        // "tool version [fallback versions]" lines, as asdf writes them
+        TOOL_VERSIONS => content
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default())
+            .any(|line| {
+                let mut parts = line.split_whitespace();
+                parts.next() == Some(binary_name) && parts.any(|v| v == version)
+            }),
+        // gradle.properties points at installations, e.g. with org.gradle.java.home
+        _ => {
+            let dir = format!("candidates/{}/{}", binary_name, version);
+            content
+                .lines()
+                .filter(|line| !line.trim_start().starts_with('#'))
+                .map(|line| line.replace('\\', "/"))
+                .any(|line| {
+                    line.split(dir.as_str()).skip(1).any(|rest| {
+                        rest.chars()
+                            .next()
+                            .is_none_or(|c| c == '/' || c.is_whitespace())
+                    })
+                })
+        }
+    }
+}
+
+fn read_requirements(project: &Path) -> Result<Vec<(String, String)>, String> {
+    let path = project.join(SDKMANRC);
+    fs::read_to_string(&path)
+        .map(|content| environment::parse(&content))
+        .map_err(|e| format!("Reading {} failed with:\n{}", path.display(), e))
+}
+
+fn project_name(project: &Path) -> String {
+    project
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| project.display().to_string())
+}
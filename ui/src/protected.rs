@@ -0,0 +1,51 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::config::app_dir;
+use crate::util;
+
+const PROTECTED_FILE: &str = "protected.toml";
+
+// installed versions that must not be deleted, e.g. the JDK an old project still needs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Protected {
+    // the protected versions per candidate
+    versions: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Protected {
+    pub fn load() -> Protected {
+        util::load_toml(protected_path(), "the protected versions")
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        util::save_toml(&protected_path()?, self)
+    }
+
+    pub fn contains(&self, binary_name: &str, version: &str) -> bool {
+        self.versions
+            .get(binary_name)
+            .is_some_and(|versions| versions.contains(version))
+    }
+
+    // protects the version, or lifts the protection, and saves right away
+    pub fn toggle(&mut self, binary_name: &str, version: &str) -> std::io::Result<()> {
+        let versions = self.versions.entry(binary_name.to_string()).or_default();
+        if !versions.remove(version) {
+            versions.insert(version.to_string());
+        }
+        if versions.is_empty() {
+            self.versions.remove(binary_name);
+        }
+        self.save()
+    }
+}
+
+fn protected_path() -> std::io::Result<PathBuf> {
+    app_dir().map(|dir| dir.join(PROTECTED_FILE))
+}
@@ -0,0 +1,44 @@
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::time::Duration;
+
+use api::remote::RemoteCandidate;
+use api::sdkman::Sdkman;
+
+use crate::app_state::AppState;
+use crate::repaint::RepaintNotifier;
+use crate::subscriptions::Inbox;
+use crate::tasks;
+
+// refreshes the catalog and checks the subscriptions until the returned sender is dropped
+pub fn spawn_background_refresh(
+    interval: Duration,
+    state: AppState,
+    repaint: RepaintNotifier,
+) -> Sender<()> {
+    let (stop, stopped) = mpsc::channel::<()>();
+    tasks::spawn(move || {
+        while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+            // the cached list outlives the interval, so it would be read again every time
+            match Sdkman::new().refresh() {
+                Ok(candidates) => {
+                    let candidates: Vec<Arc<RemoteCandidate>> =
+                        candidates.into_iter().map(Arc::new).collect();
+                    let subscriptions = state.subscriptions();
+                    if !subscriptions.is_empty() {
+                        match Inbox::check(&candidates, &subscriptions) {
+                            Ok(inbox) => state.set_inbox(inbox),
+                            Err(e) => log!("Saving the inbox failed: {}", e),
+                        }
+                    }
+                    state.set_remote(candidates);
+                    repaint.notify();
+                }
+                Err(e) => log!("Background refresh of remote candidates failed: {}", e),
+            }
+        }
+    });
+    stop
+}
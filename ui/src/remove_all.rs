@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+
+use eframe::egui::*;
+
+use api::local::retrieve_candidate_usage;
+use api::local::LocalCandidate;
+
+use crate::history::Action;
+use crate::jobs::Step;
+use crate::protected::Protected;
+use crate::repaint::RepaintNotifier;
+use crate::tasks;
+use crate::util;
+
+const PADDING: f32 = 8.0;
+
+// every installed version of a candidate, waiting for the go to delete them, e.g. to decommission
+// a tool
+pub struct RemoveAll {
+    binary_name: String,
+    versions: Vec<String>,
+    current: Option<String>,
+    // protected versions are always kept
+    protected: Vec<String>,
+    keep_current: bool,
+    sizes: Option<HashMap<String, u64>>,
+    receiver: Receiver<HashMap<String, u64>>,
+}
+
+impl RemoveAll {
+    pub fn new(
+        local_candidate: &LocalCandidate,
+        protected: &Protected,
+        repaint: &RepaintNotifier,
+    ) -> RemoveAll {
+        // walking the versions takes a moment, so the sizes come in the background
+        let (sender, receiver) = mpsc::channel();
+        let binary_name = local_candidate.binary_name().clone();
+        let repaint = repaint.clone();
+        tasks::spawn(move || {
+            let sizes = match retrieve_candidate_usage(&binary_name) {
+                Ok(usage) => usage
+                    .versions()
+                    .iter()
+                    .map(|version| (version.version().clone(), version.disk_usage()))
+                    .collect(),
+                Err(e) => {
+                    log!("Failed to retrieve disk usage of {}: {}", binary_name, e);
+                    HashMap::new()
+                }
+            };
+            let _ = sender.send(sizes);
+            repaint.notify();
+        });
+        let mut versions: Vec<String> = local_candidate.versions().keys().cloned().collect();
+        versions.sort_by(|v1, v2| alphanumeric_sort::compare_str(v1, v2));
+        let current = local_candidate.current_version().cloned();
+        let protected = versions
+            .iter()
+            .filter(|version| protected.contains(local_candidate.binary_name(), version))
+            .cloned()
+            .collect();
+        RemoveAll {
+            binary_name: local_candidate.binary_name().clone(),
+            versions,
+            protected,
+            keep_current: current.is_some(),
+            current,
+            sizes: None,
+            receiver,
+        }
+    }
+
+    fn removed(&self) -> Vec<String> {
+        self.versions
+            .iter()
+            .filter(|version| !self.protected.contains(version))
+            .filter(|version| !self.keep_current || Some(*version) != self.current.as_ref())
+            .cloned()
+            .collect()
+    }
+
+    // the steps to run once confirmed, an error once cancelled
+    pub fn render(&mut self, ctx: &CtxRef) -> Option<Result<Vec<Step>, ()>> {
+        if let Ok(sizes) = self.receiver.try_recv() {
+            self.sizes = Some(sizes);
+        }
+        let removed = self.removed();
+        let total = self.sizes.as_ref().map(|sizes| {
+            removed
+                .iter()
+                .filter_map(|version| sizes.get(version))
+                .sum::<u64>()
+        });
+        let mut decision = None;
+        Window::new(format!("Remove all versions of {}", self.binary_name))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0., 0.])
+            .show(ctx, |ui| {
+                ui.add_space(PADDING);
+                ui.label(format!(
+                    "{} of {} installed versions will be deleted, freeing {}.",
+                    removed.len(),
+                    self.versions.len(),
+                    total.map_or_else(|| "…".to_string(), util::format_size)
+                ));
+                ui.add_space(PADDING);
+                Grid::new("remove_all")
+                    .striped(true)
+                    .num_columns(3)
+                    .spacing([4. * PADDING, PADDING / 2.])
+                    .show(ui, |ui| {
+                        for version in &self.versions {
+                            let kept = !removed.contains(version);
+                            ui.label(version);
+                            ui.label(
+                                self.sizes
+                                    .as_ref()
+                                    .and_then(|sizes| sizes.get(version))
+                                    .map_or_else(
+                                        || "…".to_string(),
+                                        |size| util::format_size(*size),
+                                    ),
+                            );
+                            let protected = self.protected.contains(version);
+                            ui.label(match (kept, Some(version) == self.current.as_ref()) {
+                                _ if protected => "protected",
+                                (true, _) => "kept",
+                                (false, true) => "current",
+                                (false, false) => "",
+                            });
+                            ui.end_row();
+                        }
+                    });
+                if self.current.is_some() {
+                    ui.add_space(PADDING);
+                    ui.checkbox(&mut self.keep_current, "Keep the current version");
+                }
+                ui.add_space(PADDING);
+                ui.horizontal(|ui| {
+                    let label = format!("Delete {} versions", removed.len());
+                    if ui
+                        .add_enabled(!removed.is_empty(), Button::new(label))
+                        .clicked()
+                    {
+                        decision = Some(Ok(removed
+                            .iter()
+                            .map(|version| Step::new(&self.binary_name, version, Action::Delete))
+                            .collect()));
+                    }
+                    if ui.button("Cancel").clicked() {
+                        decision = Some(Err(()));
+                    }
+                });
+            });
+        decision
+    }
+}
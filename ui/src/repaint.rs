@@ -0,0 +1,39 @@
+use std::sync::mpsc;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use eframe::epi::RepaintSignal;
+
+// at most ~30 repaints per second, no matter how chatty the background threads are
+const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(33);
+
+// lets background threads wake up the UI; the default notifier does nothing
+#[derive(Clone, Default)]
+pub struct RepaintNotifier {
+    sender: Option<Sender<()>>,
+}
+
+impl RepaintNotifier {
+    pub fn new(signal: Arc<dyn RepaintSignal>) -> Self {
+        let (sender, receiver) = mpsc::channel::<()>();
+        thread::spawn(move || {
+            while receiver.recv().is_ok() {
+                // coalesce everything arriving within the interval into a single repaint
+                thread::sleep(DEBOUNCE_INTERVAL);
+                receiver.try_iter().for_each(drop);
+                signal.request_repaint();
+            }
+        });
+        Self {
+            sender: Some(sender),
+        }
+    }
+
+    pub fn notify(&self) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(());
+        }
+    }
+}
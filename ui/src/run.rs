@@ -0,0 +1,196 @@
+use std::fs;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+
+use eframe::egui::*;
+
+use api::install;
+use api::local;
+
+use crate::repaint::RepaintNotifier;
+use crate::tasks;
+
+const PADDING: f32 = 8.0;
+
+pub enum RunOutcome {
+    Open,
+    // open a terminal with the version first on the PATH
+    Terminal,
+    Close,
+}
+
+// runs an executable of an installed version, e.g. gradle --version, and shows what it printed
+pub struct RunWindow {
+    pub binary_name: String,
+    pub version: String,
+    executables: Vec<String>,
+    executable: String,
+    args: String,
+    output: Option<Result<String, String>>,
+    receiver: Option<Receiver<Result<String, String>>>,
+}
+
+impl RunWindow {
+    pub fn new(binary_name: &str, version: &str) -> RunWindow {
+        let executables = executables(binary_name, version);
+        let (launcher, arg) = install::launcher(binary_name);
+        let (executable, args) = if executables.iter().any(|e| e == launcher) {
+            (launcher.to_string(), arg.to_string())
+        } else {
+            (
+                executables.first().cloned().unwrap_or_default(),
+                String::new(),
+            )
+        };
+        RunWindow {
+            binary_name: binary_name.to_string(),
+            version: version.to_string(),
+            executables,
+            executable,
+            args,
+            output: None,
+            receiver: None,
+        }
+    }
+
+    fn run(&mut self, repaint: &RepaintNotifier) {
+        let (sender, receiver) = mpsc::channel();
+        let binary_name = self.binary_name.clone();
+        let version = self.version.clone();
+        let executable = self.executable.clone();
+        // no quoting, the arguments of a quick check rarely need it
+        let args: Vec<String> = self.args.split_whitespace().map(String::from).collect();
+        let repaint = repaint.clone();
+        tasks::spawn(move || {
+            let output = install::run_version(&binary_name, &version, &executable, &args)
+                .map_err(|e| e.to_string())
+                .map(|output| match output.status {
+                    Some(status) if status.success() => output.printed,
+                    Some(status) => {
+                        format!("{}\nExited with {}", output.printed.trim_end(), status)
+                    }
+                    None => format!(
+                        "{}\nKilled, it didn't finish in time",
+                        output.printed.trim_end()
+                    ),
+                });
+            let _ = sender.send(output);
+            repaint.notify();
+        });
+        self.output = None;
+        self.receiver = Some(receiver);
+    }
+
+    pub fn render(&mut self, ctx: &CtxRef, repaint: &RepaintNotifier) -> RunOutcome {
+        if let Some(output) = self.receiver.as_ref().and_then(|r| r.try_recv().ok()) {
+            self.output = Some(output);
+            self.receiver = None;
+        }
+        let mut outcome = RunOutcome::Open;
+        let mut open = true;
+        let mut run = false;
+        Window::new(format!("Run {} {}", self.binary_name, self.version))
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                if self.executables.is_empty() {
+                    ui.label("This version has no executables in its bin directory.");
+                } else {
+                    ui.horizontal(|ui| {
+                        ComboBox::from_id_source("run_executable")
+                            .selected_text(&self.executable)
+                            .show_ui(ui, |ui| {
+                                for executable in &self.executables {
+                                    ui.selectable_value(
+                                        &mut self.executable,
+                                        executable.clone(),
+                                        executable,
+                                    );
+                                }
+                            });
+                        let args =
+                            ui.add(TextEdit::singleline(&mut self.args).hint_text("arguments"));
+                        if args.lost_focus() && ui.input().key_pressed(Key::Enter) {
+                            run = true;
+                        }
+                        if ui
+                            .add_enabled(self.receiver.is_none(), Button::new("▶ Run"))
+                            .clicked()
+                        {
+                            run = true;
+                        }
+                    });
+                }
+                ui.add_space(PADDING);
+                match &self.output {
+                    _ if self.receiver.is_some() => {
+                        ui.label(format!("Running {}…", self.executable));
+                    }
+                    None => {}
+                    Some(Err(e)) => {
+                        ui.colored_label(Color32::YELLOW, format!("Running failed with:\n{}", e));
+                    }
+                    Some(Ok(printed)) => {
+                        ScrollArea::vertical().max_height(300.).show(ui, |ui| {
+                            ui.monospace(printed.trim_end());
+                        });
+                        if ui.button("Copy the output").clicked() {
+                            ui.ctx().output().copied_text = printed.clone();
+                        }
+                    }
+                }
+                ui.add_space(PADDING);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Open a terminal")
+                        .on_hover_text("For interactive tools like jshell")
+                        .clicked()
+                    {
+                        outcome = RunOutcome::Terminal;
+                    }
+                    if ui.button("Close").clicked() {
+                        outcome = RunOutcome::Close;
+                    }
+                });
+            });
+        if run && self.receiver.is_none() && !self.executable.is_empty() {
+            self.run(repaint);
+        }
+        if !open {
+            outcome = RunOutcome::Close;
+        }
+        outcome
+    }
+}
+
+// the executables in the bin directory of a version, sorted by name
+pub fn executables(binary_name: &str, version: &str) -> Vec<String> {
+    let bin = match local::home_path(binary_name, version) {
+        Ok(home) => home.join("bin"),
+        Err(_) => return Vec::new(),
+    };
+    let mut executables: Vec<String> = fs::read_dir(bin)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.is_file() && is_executable(path))
+                .filter_map(|path| Some(path.file_name()?.to_string_lossy().to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    executables.sort();
+    executables
+}
+
+// batch files and the like sit next to the shell scripts, only the ones this platform runs count
+fn is_executable(path: &std::path::Path) -> bool {
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+    if cfg!(windows) {
+        matches!(extension.as_deref(), Some("exe" | "bat" | "cmd"))
+    } else {
+        extension.is_none()
+    }
+}
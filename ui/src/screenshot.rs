@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use eframe::egui::*;
+use eframe::epi;
+use eframe::epi::App;
+use image::Rgba;
+use image::RgbaImage;
+
+use crate::candidates::SdkmanApp;
+use crate::candidates::Tab;
+
+const FRAMES_PER_VIEW: usize = 10;
+
+// the views rendered by --screenshots: file name, tab and the candidate to open
+const VIEWS: [(&str, Tab, Option<&str>); 4] = [
+    ("candidates.png", Tab::Candidates, None),
+    ("candidate-java.png", Tab::Candidates, Some("java")),
+    ("dashboard.png", Tab::Dashboard, None),
+    ("projects.png", Tab::Projects, None),
+];
+
+// keeps the textures the app allocates, so images like the logo end up in the screenshots
+#[derive(Default)]
+struct Textures {
+    next_id: u64,
+    images: HashMap<u64, (usize, usize, Vec<Color32>)>,
+}
+
+impl epi::TextureAllocator for Textures {
+    fn alloc_srgba_premultiplied(
+        &mut self,
+        size: (usize, usize),
+        srgba_pixels: &[Color32],
+    ) -> TextureId {
+        self.next_id += 1;
+        self.images
+            .insert(self.next_id, (size.0, size.1, srgba_pixels.to_vec()));
+        TextureId::User(self.next_id)
+    }
+
+    fn free(&mut self, id: TextureId) {
+        if let TextureId::User(id) = id {
+            self.images.remove(&id);
+        }
+    }
+}
+
+struct NoRepaint;
+
+impl epi::RepaintSignal for NoRepaint {
+    fn request_repaint(&self) {}
+}
+
+// renders the main views off-screen into PNG files, for the documentation and for
+// comparing layout changes; no window and no GPU are involved
+pub fn render_views(mut app: SdkmanApp, size: Vec2, dir: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let mut ctx = CtxRef::default();
+    let mut textures = Textures::default();
+    let mut output = epi::backend::AppOutput::default();
+    let repaint_signal: Arc<dyn epi::RepaintSignal> = Arc::new(NoRepaint);
+    let raw_input = RawInput {
+        screen_rect: Some(Rect::from_min_size(Pos2::ZERO, size)),
+        pixels_per_point: Some(1.),
+        ..Default::default()
+    };
+    let mut setup_done = false;
+
+    for (file_name, tab, binary_name) in VIEWS {
+        app.show_view(tab, binary_name);
+        let mut shapes = Vec::new();
+        // a few frames let the layout settle and the animations finish
+        for _ in 0..FRAMES_PER_VIEW {
+            let mut frame = epi::backend::FrameBuilder {
+                info: epi::IntegrationInfo {
+                    name: "screenshot",
+                    web_info: None,
+                    prefer_dark_mode: None,
+                    cpu_usage: None,
+                    native_pixels_per_point: Some(1.),
+                },
+                tex_allocator: &mut textures,
+                output: &mut output,
+                repaint_signal: repaint_signal.clone(),
+            }
+            .build();
+            if !setup_done {
+                app.setup(&ctx, &mut frame, None);
+                setup_done = true;
+            }
+            ctx.begin_frame(raw_input.clone());
+            app.update(&ctx, &mut frame);
+            let (_, frame_shapes) = ctx.end_frame();
+            shapes = frame_shapes;
+        }
+        let meshes = ctx.tessellate(shapes);
+        let image = rasterize(&meshes, &ctx.texture(), &textures, size);
+        let path = dir.join(file_name);
+        image
+            .save(&path)
+            .map_err(|e| std::io::Error::other(format!("{}: {}", path.display(), e)))?;
+        log!("Rendered {}", path.display());
+    }
+    Ok(())
+}
+
+// a plain software rasterizer for egui's triangles: premultiplied colors blended in sRGB,
+// nearest texel sampling; close enough to what the GPU draws to spot layout changes
+fn rasterize(
+    meshes: &[ClippedMesh],
+    font_texture: &Texture,
+    textures: &Textures,
+    size: Vec2,
+) -> RgbaImage {
+    let (width, height) = (size.x as u32, size.y as u32);
+    let mut image = RgbaImage::from_pixel(width, height, Rgba([0, 0, 0, 255]));
+    for ClippedMesh(clip_rect, mesh) in meshes {
+        let texel = |uv: Pos2| -> Color32 {
+            match mesh.texture_id {
+                TextureId::Egui => {
+                    let x =
+                        ((uv.x * font_texture.width as f32) as usize).min(font_texture.width - 1);
+                    let y =
+                        ((uv.y * font_texture.height as f32) as usize).min(font_texture.height - 1);
+                    Color32::from_white_alpha(font_texture.pixels[y * font_texture.width + x])
+                }
+                TextureId::User(id) => match textures.images.get(&id) {
+                    Some((w, h, pixels)) if *w > 0 && *h > 0 => {
+                        let x = ((uv.x * *w as f32) as usize).min(w - 1);
+                        let y = ((uv.y * *h as f32) as usize).min(h - 1);
+                        pixels[y * w + x]
+                    }
+                    _ => Color32::TRANSPARENT,
+                },
+            }
+        };
+        let clip = clip_rect.intersect(Rect::from_min_size(Pos2::ZERO, size));
+        for triangle in mesh.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| &mesh.vertices[triangle[i] as usize]);
+            let area = edge(a.pos, b.pos, c.pos);
+            if area.abs() < f32::EPSILON {
+                continue;
+            }
+            let min_x = a.pos.x.min(b.pos.x).min(c.pos.x).max(clip.min.x).floor() as u32;
+            let max_x = a.pos.x.max(b.pos.x).max(c.pos.x).min(clip.max.x).ceil() as u32;
+            let min_y = a.pos.y.min(b.pos.y).min(c.pos.y).max(clip.min.y).floor() as u32;
+            let max_y = a.pos.y.max(b.pos.y).max(c.pos.y).min(clip.max.y).ceil() as u32;
+            for y in min_y..max_y.min(height) {
+                for x in min_x..max_x.min(width) {
+                    let p = pos2(x as f32 + 0.5, y as f32 + 0.5);
+                    let wa = edge(b.pos, c.pos, p) / area;
+                    let wb = edge(c.pos, a.pos, p) / area;
+                    let wc = edge(a.pos, b.pos, p) / area;
+                    if wa < 0. || wb < 0. || wc < 0. {
+                        continue;
+                    }
+                    let uv = pos2(
+                        wa * a.uv.x + wb * b.uv.x + wc * c.uv.x,
+                        wa * a.uv.y + wb * b.uv.y + wc * c.uv.y,
+                    );
+                    let tex = texel(uv);
+                    let src: [f32; 4] = [0, 1, 2, 3].map(|i| {
+                        let color = wa * a.color[i] as f32
+                            + wb * b.color[i] as f32
+                            + wc * c.color[i] as f32;
+                        color * tex[i] as f32 / 255.
+                    });
+                    let dst = image.get_pixel_mut(x, y);
+                    let keep = 1. - src[3] / 255.;
+                    for (channel, src) in dst.0.iter_mut().zip(src).take(3) {
+                        *channel = (src + *channel as f32 * keep).round().min(255.) as u8;
+                    }
+                }
+            }
+        }
+    }
+    image
+}
+
+fn edge(a: Pos2, b: Pos2, p: Pos2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
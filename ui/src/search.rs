@@ -0,0 +1,57 @@
+use std::cmp::Reverse;
+
+use crate::candidates::Candidate;
+
+// name matches always outrank description matches
+const EXACT_NAME: u32 = 100;
+const NAME_PREFIX: u32 = 80;
+const NAME_CONTAINS: u32 = 60;
+const DESCRIPTION_PHRASE: u32 = 40;
+const DESCRIPTION_WORDS: u32 = 20;
+const MAX_RESULTS: usize = 10;
+
+fn score(candidate: &Candidate, term: &str, words: &[&str]) -> u32 {
+    let name = candidate.name().to_lowercase();
+    let binary_name = candidate.binary_name().to_lowercase();
+    let description = candidate.description().to_lowercase();
+    if name == term || binary_name == term {
+        EXACT_NAME
+    } else if name.starts_with(term) || binary_name.starts_with(term) {
+        NAME_PREFIX
+    } else if name.contains(term) || binary_name.contains(term) {
+        NAME_CONTAINS
+    } else if description.contains(term) {
+        DESCRIPTION_PHRASE
+    } else if words.iter().all(|word| description.contains(word)) {
+        DESCRIPTION_WORDS
+    } else {
+        0
+    }
+}
+
+pub fn matches(candidate: &Candidate, term: &str) -> bool {
+    let term = term.trim().to_lowercase();
+    let words: Vec<&str> = term.split_whitespace().collect();
+    score(candidate, &term, &words) > 0
+}
+
+// the candidates matching the term by name or description, best matches first
+pub fn search<'a>(candidates: &'a [Candidate], term: &str) -> Vec<&'a Candidate> {
+    let term = term.trim().to_lowercase();
+    if term.is_empty() {
+        return Vec::new();
+    }
+    let words: Vec<&str> = term.split_whitespace().collect();
+    let mut matches: Vec<(u32, &Candidate)> = candidates
+        .iter()
+        .map(|candidate| (score(candidate, &term, &words), candidate))
+        .filter(|(score, _)| *score > 0)
+        .collect();
+    // stable, so candidates with the same score keep the catalog order
+    matches.sort_by_key(|(score, _)| Reverse(*score));
+    matches
+        .into_iter()
+        .take(MAX_RESULTS)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
@@ -0,0 +1,463 @@
+use std::path::Path;
+
+use eframe::egui::*;
+
+use api::cache;
+use api::cache::Namespace;
+use api::download::Mirror;
+use api::remote::Channel;
+use api::sdkman_config;
+use api::sdkman_config::SdkmanConfig;
+use api::security;
+use api::store::CurrentDetection;
+
+use crate::config;
+use crate::config::Config;
+use crate::config::DoubleClickAction;
+use crate::config::PaneLayout;
+use crate::config::Theme;
+
+const PADDING: f32 = 8.0;
+
+pub enum SettingsOutcome {
+    Open,
+    Saved(Box<Config>),
+    Cancelled,
+    PreviewAnalytics(String),
+    ExportDiagnostics,
+}
+
+pub struct Settings {
+    draft: Config,
+    // the file settings are exported to and imported from
+    sync_path: String,
+    // what the last export or import did
+    sync_result: Option<Result<String, String>>,
+    // the keys signatures are checked with, listed on demand
+    signing_keys: Option<Result<Vec<String>, String>>,
+    // etc/config of SDKMAN, written on its own since SDKMAN reads it, not the app
+    sdkman_config: Result<SdkmanConfig, String>,
+    sdkman_config_result: Option<Result<String, String>>,
+}
+
+impl Settings {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            draft: config.clone(),
+            sync_path: config::settings_file().display().to_string(),
+            sync_result: None,
+            signing_keys: None,
+            sdkman_config: SdkmanConfig::load().map_err(|e| e.to_string()),
+            sdkman_config_result: None,
+        }
+    }
+
+    pub fn render(&mut self, ctx: &CtxRef) -> SettingsOutcome {
+        let Self {
+            draft,
+            sync_path,
+            sync_result,
+            signing_keys,
+            sdkman_config,
+            sdkman_config_result,
+        } = self;
+        let mut outcome = SettingsOutcome::Open;
+
+        Window::new("Settings")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.add_space(PADDING);
+                ui.heading("Appearance");
+                ui.horizontal(|ui| {
+                    let dark = ui.radio_value(&mut draft.theme, Theme::Dark, "Dark");
+                    let light = ui.radio_value(&mut draft.theme, Theme::Light, "Light");
+                    if dark.changed() || light.changed() {
+                        draft.theme.apply(ctx);
+                    }
+                });
+
+                ui.checkbox(
+                    &mut draft.show_release_dates,
+                    "Show release dates in the version list, where known",
+                );
+                ui.checkbox(
+                    &mut draft.homepage_panel,
+                    "Offer to read homepages in a side panel",
+                );
+                ui.checkbox(
+                    &mut draft.weekly_summary,
+                    "Show a weekly summary of new versions, outdated JDKs and disk usage",
+                );
+
+                ui.label("Layout of the candidates:");
+                ui.horizontal(|ui| {
+                    let layout = &mut draft.pane_layout;
+                    ui.radio_value(layout, PaneLayout::Single, "Single pane");
+                    ui.radio_value(layout, PaneLayout::ThreePanes, "Three panes")
+                        .on_hover_text(
+                            "The list, the versions and the jobs with the log side by side",
+                        );
+                });
+
+                ui.label("Double-clicking a version:");
+                ui.horizontal(|ui| {
+                    let action = &mut draft.double_click_action;
+                    ui.radio_value(action, DoubleClickAction::Smart, "Install or use")
+                        .on_hover_text(
+                            "Installs a missing version, makes an installed one current",
+                        );
+                    ui.radio_value(action, DoubleClickAction::Install, "Install");
+                    ui.radio_value(action, DoubleClickAction::SetCurrent, "Set current");
+                    ui.radio_value(action, DoubleClickAction::CopyIdentifier, "Copy identifier");
+                });
+                ui.checkbox(
+                    &mut draft.preview_switches,
+                    "Show what switching the current version changes before switching",
+                );
+
+                ui.add_space(PADDING);
+                ui.heading("Refresh");
+                ui.checkbox(
+                    &mut draft.background_refresh,
+                    "Refresh the list of candidates in the background",
+                );
+                ui.add_enabled_ui(draft.background_refresh, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Every");
+                        ui.add(
+                            DragValue::new(&mut draft.refresh_interval_minutes)
+                                .clamp_range(5..=1440),
+                        );
+                        ui.label("minutes");
+                    });
+                });
+
+                ui.add_space(PADDING);
+                ui.heading("Cache");
+                ui.horizontal(|ui| {
+                    ui.label("Keep the list of candidates for");
+                    ui.add(DragValue::new(&mut draft.catalog_cache_hours).clamp_range(0..=168));
+                    ui.label("hours");
+                    if ui.button("Clear").clicked() {
+                        if let Err(e) = cache::invalidate(Namespace::Catalog) {
+                            log!("Failed to clear the candidate cache: {}", e);
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Keep the versions of a candidate for");
+                    ui.add(DragValue::new(&mut draft.versions_cache_minutes).clamp_range(0..=1440));
+                    ui.label("minutes");
+                    if ui.button("Clear").clicked() {
+                        if let Err(e) = cache::invalidate(Namespace::Versions) {
+                            log!("Failed to clear the version cache: {}", e);
+                        }
+                    }
+                });
+
+                ui.add_space(PADDING);
+                ui.heading("Channel");
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut draft.channel, Channel::Stable, "Stable");
+                    ui.radio_value(&mut draft.channel, Channel::Beta, "Beta")
+                        .on_hover_text("Candidates and versions that are still being tried out");
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Platform override:");
+                    ui.text_edit_singleline(&mut draft.platform_override)
+                        .on_hover_text(
+                            "e.g. darwinx64; leave empty for the platform SDKMAN detected",
+                        );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Categories URL:");
+                    ui.text_edit_singleline(&mut draft.categories_url)
+                        .on_hover_text(
+                            "A TOML file with the category of each candidate and the suggested \
+                             ones; leave empty for the defaults the app ships with",
+                        );
+                });
+
+                ui.add_space(PADDING);
+                ui.heading("Downloads");
+                ui.horizontal(|ui| {
+                    ui.label("Download at most");
+                    ui.add(DragValue::new(&mut draft.max_parallel_downloads).clamp_range(1..=8));
+                    ui.label("versions at the same time");
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Ask before installing when less than");
+                    ui.add(DragValue::new(&mut draft.low_disk_space_mb).clamp_range(0..=102400));
+                    ui.label("MB are free");
+                });
+                ui.checkbox(
+                    &mut draft.verify_installs,
+                    "Verify installed versions by running their version command",
+                );
+                ui.checkbox(
+                    &mut draft.verify_signatures,
+                    "Verify the signatures of Apache Ant and Maven downloads with gpg",
+                );
+                if cfg!(target_os = "macos") {
+                    ui.checkbox(
+                        &mut draft.clear_quarantine,
+                        "Clear the quarantine of installed versions, so Gatekeeper doesn't ask",
+                    );
+                }
+                CollapsingHeader::new("Signing keys")
+                    .id_source("signing_keys")
+                    .show(ui, |ui| {
+                        ui.label(
+                            "The keys the projects list are imported with the first verification.",
+                        );
+                        ui.horizontal(|ui| {
+                            if ui.button("List").clicked() {
+                                *signing_keys =
+                                    Some(security::list_keys().map_err(|e| e.to_string()));
+                            }
+                            if ui
+                                .button("Forget")
+                                .on_hover_text("They're imported again when needed")
+                                .clicked()
+                            {
+                                *signing_keys = Some(
+                                    security::forget_keys()
+                                        .map(|_| Vec::new())
+                                        .map_err(|e| e.to_string()),
+                                );
+                            }
+                        });
+                        match signing_keys {
+                            Some(Ok(keys)) if keys.is_empty() => {
+                                ui.label("No keys imported yet.");
+                            }
+                            Some(Ok(keys)) => {
+                                for key in keys.iter() {
+                                    ui.monospace(key);
+                                }
+                            }
+                            Some(Err(e)) => {
+                                ui.colored_label(
+                                    Color32::YELLOW,
+                                    format!("Reading the keyring failed with:\n{}", e),
+                                );
+                            }
+                            None => {}
+                        }
+                    });
+                CollapsingHeader::new("Mirrors")
+                    .id_source("download_mirrors")
+                    .show(ui, |ui| {
+                        ui.label("Failed downloads are retried with the prefix replaced.");
+                        ui.label("Use * as the candidate to apply a mirror to all of them.");
+                        let mut remove = None;
+                        Grid::new("download_mirrors_grid").show(ui, |ui| {
+                            ui.label("Candidate");
+                            ui.label("URL prefix");
+                            ui.label("Mirror prefix");
+                            ui.end_row();
+                            for (index, mirror) in draft.download_mirrors.iter_mut().enumerate() {
+                                ui.add(
+                                    TextEdit::singleline(&mut mirror.candidate).desired_width(80.),
+                                );
+                                ui.text_edit_singleline(&mut mirror.prefix);
+                                ui.text_edit_singleline(&mut mirror.replacement);
+                                if ui.button("✖").clicked() {
+                                    remove = Some(index);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                        if let Some(index) = remove {
+                            draft.download_mirrors.remove(index);
+                        }
+                        if ui.button("Add mirror").clicked() {
+                            draft.download_mirrors.push(Mirror {
+                                candidate: "*".to_string(),
+                                prefix: String::new(),
+                                replacement: String::new(),
+                            });
+                        }
+                    });
+
+                ui.add_space(PADDING);
+                ui.heading("Local scan");
+                ui.horizontal(|ui| {
+                    ui.label("Current versions:");
+                    ui.radio_value(
+                        &mut draft.current_detection,
+                        CurrentDetection::ReadLink,
+                        "Read the current link",
+                    )
+                    .on_hover_text("Safe on network filesystems like NFS and SSHFS");
+                    ui.radio_value(
+                        &mut draft.current_detection,
+                        CurrentDetection::Canonicalize,
+                        "Resolve every version",
+                    )
+                    .on_hover_text("Sees through links to links, but may hang on network mounts");
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Give up scanning after");
+                    ui.add(DragValue::new(&mut draft.scan_timeout_seconds).clamp_range(1..=600));
+                    ui.label("seconds");
+                });
+
+                ui.add_space(PADDING);
+                ui.heading("SDKMAN");
+                match sdkman_config {
+                    Ok(sdkman_config) => {
+                        let flags = [
+                            (
+                                sdkman_config::AUTO_ENV,
+                                "Switch to the versions of a .sdkmanrc when entering its directory",
+                            ),
+                            (
+                                sdkman_config::AUTO_ANSWER,
+                                "Answer the questions of sdk with yes",
+                            ),
+                        ];
+                        for (key, text) in flags {
+                            let mut on = sdkman_config.flag(key);
+                            if ui.checkbox(&mut on, text).on_hover_text(key).changed() {
+                                sdkman_config.set_flag(key, on);
+                            }
+                        }
+                        ui.horizontal(|ui| {
+                            if ui
+                                .button("Write to SDKMAN")
+                                .on_hover_text("New shells pick the flags up")
+                                .clicked()
+                            {
+                                *sdkman_config_result = Some(
+                                    sdkman_config
+                                        .save()
+                                        .map(|backup| match backup {
+                                            Some(backup) => format!(
+                                                "Written, the previous file is kept as {}",
+                                                backup.display()
+                                            ),
+                                            None => "Written".to_string(),
+                                        })
+                                        .map_err(|e| {
+                                            format!("Writing the SDKMAN config failed with:\n{}", e)
+                                        }),
+                                );
+                            }
+                        });
+                        match sdkman_config_result {
+                            Some(Ok(message)) => {
+                                ui.label(message.as_str());
+                            }
+                            Some(Err(message)) => {
+                                ui.colored_label(Color32::YELLOW, message.as_str());
+                            }
+                            None => {}
+                        }
+                    }
+                    Err(e) => {
+                        ui.colored_label(
+                            Color32::YELLOW,
+                            format!("Reading the SDKMAN config failed with:\n{}", e),
+                        );
+                    }
+                }
+
+                ui.add_space(PADDING);
+                ui.heading("Projects");
+                ui.horizontal(|ui| {
+                    ui.label("Terminal command:");
+                    ui.text_edit_singleline(&mut draft.terminal_command)
+                        .on_hover_text(
+                            "e.g. gnome-terminal or kitty; leave empty for the system terminal",
+                        );
+                });
+
+                ui.add_space(PADDING);
+                ui.heading("Usage statistics");
+                ui.checkbox(
+                    &mut draft.analytics_enabled,
+                    "Count which candidates are installed with sdkman-ui",
+                );
+                ui.label("The counts are stored locally and only sent after you reviewed them.");
+                ui.add_enabled_ui(draft.analytics_enabled, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Submission URL:");
+                        ui.text_edit_singleline(&mut draft.analytics_url);
+                    });
+                    if ui.button("Review and send…").clicked() {
+                        outcome = SettingsOutcome::PreviewAnalytics(draft.analytics_url.clone());
+                    }
+                });
+
+                ui.add_space(PADDING);
+                ui.heading("Sync");
+                ui.label(
+                    "Theme, pins, filters and mirrors in a single file, e.g. to keep them with \
+                     the dotfiles. Paths of this machine and credentials are left out.",
+                );
+                ui.horizontal(|ui| {
+                    ui.label("File:");
+                    ui.text_edit_singleline(sync_path);
+                });
+                ui.horizontal(|ui| {
+                    let path = Path::new(sync_path.trim());
+                    if ui.button("Export").clicked() {
+                        *sync_result = Some(
+                            draft
+                                .export_settings(path)
+                                .map(|_| format!("Exported to {}", path.display()))
+                                .map_err(|e| format!("Exporting the settings failed with:\n{}", e)),
+                        );
+                    }
+                    if ui
+                        .button("Import")
+                        .on_hover_text("Takes effect once saved")
+                        .clicked()
+                    {
+                        *sync_result = Some(match draft.import_settings(path) {
+                            Ok(imported) => {
+                                *draft = imported;
+                                draft.theme.apply(ctx);
+                                Ok(format!("Imported {}, save to keep it", path.display()))
+                            }
+                            Err(e) => Err(format!("Importing the settings failed with:\n{}", e)),
+                        });
+                    }
+                });
+                match sync_result {
+                    Some(Ok(message)) => {
+                        ui.label(message.as_str());
+                    }
+                    Some(Err(message)) => {
+                        ui.colored_label(Color32::YELLOW, message.as_str());
+                    }
+                    None => {}
+                }
+
+                ui.add_space(PADDING);
+                ui.heading("Support");
+                ui.horizontal(|ui| {
+                    if ui.button("Export diagnostics").clicked() {
+                        outcome = SettingsOutcome::ExportDiagnostics;
+                    }
+                    ui.label("A zip file to attach to bug reports, without the analytics URL.");
+                });
+
+                ui.add_space(PADDING);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Save").clicked() {
+                        outcome = SettingsOutcome::Saved(Box::new(draft.clone()));
+                    }
+                    if ui.button("Cancel").clicked() {
+                        outcome = SettingsOutcome::Cancelled;
+                    }
+                });
+                ui.add_space(PADDING);
+            });
+
+        outcome
+    }
+}
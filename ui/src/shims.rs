@@ -0,0 +1,147 @@
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use api::local;
+
+use crate::config::app_dir;
+use crate::last_used;
+
+const SHIMS_DIR: &str = "shims";
+// followed by the candidate and version the shim runs
+const HEADER: &str = "# sdkman-ui shim for ";
+
+// the shim of a version is named after its executable and major version, e.g. java17
+pub fn shim_name(binary_name: &str, version: &str) -> String {
+    let major: String = version.chars().take_while(|c| c.is_ascii_digit()).collect();
+    if major.is_empty() {
+        format!("{}-{}", binary_name, version)
+    } else {
+        format!("{}{}", binary_name, major)
+    }
+}
+
+pub fn shim_path(binary_name: &str, version: &str) -> std::io::Result<PathBuf> {
+    app_dir().map(|dir| dir.join(SHIMS_DIR).join(shim_name(binary_name, version)))
+}
+
+// the candidate and version the shim of the name runs; versions of the same major, e.g. of two
+// vendors, and candidates ending in digits share names
+pub fn owner(binary_name: &str, version: &str) -> std::io::Result<Option<(String, String)>> {
+    let script = match fs::read_to_string(shim_path(binary_name, version)?) {
+        Ok(script) => script,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    Ok(script
+        .lines()
+        .find_map(|line| line.strip_prefix(HEADER))
+        .and_then(|owner| owner.split_once(' '))
+        .map(|(binary_name, version)| (binary_name.to_string(), version.to_string())))
+}
+
+fn is_owner(binary_name: &str, version: &str) -> std::io::Result<bool> {
+    Ok(owner(binary_name, version)?
+        .is_some_and(|(owner, owned)| owner == binary_name && owned == version))
+}
+
+// a script running the version no matter which one is current, for scripts that need e.g. a
+// fixed JDK; it records each run, so the version doesn't show up as unused
+pub fn create(binary_name: &str, version: &str) -> std::io::Result<PathBuf> {
+    if let Some((owner, owned)) = owner(binary_name, version)?
+        .filter(|(owner, owned)| owner != binary_name || owned != version)
+    {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!(
+                "{} already runs {} {}, remove it there first",
+                shim_name(binary_name, version),
+                owner,
+                owned
+            ),
+        ));
+    }
+    let shim = shim_path(binary_name, version)?;
+    if shim.exists() && owner(binary_name, version)?.is_none() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("{} is there already and isn't a shim", shim.display()),
+        ));
+    }
+    let home = local::home_path(binary_name, version)?;
+    let executable = executable(&home.join("bin"), binary_name)?;
+    let record = last_used::record_path(binary_name, version)?;
+    let record_dir = record.parent().unwrap_or(&record);
+    let home_var = format!("{}_HOME", binary_name.to_uppercase());
+    let script = format!(
+        "#!/bin/sh\n\
+         {header}{binary_name} {version}\n\
+         mkdir -p \"{record_dir}\" && touch \"{record}\" 2>/dev/null\n\
+         export {home_var}=\"{home}\"\n\
+         exec \"{executable}\" \"$@\"\n",
+        header = HEADER,
+        binary_name = binary_name,
+        version = version,
+        record_dir = record_dir.display(),
+        record = record.display(),
+        home_var = home_var,
+        home = home.display(),
+        executable = executable.display(),
+    );
+    if let Some(parent) = shim.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&shim, script)?;
+    make_executable(&shim)?;
+    Ok(shim)
+}
+
+// only the version's own shim, not one of another version with the same name
+pub fn remove(binary_name: &str, version: &str) -> std::io::Result<()> {
+    if !is_owner(binary_name, version)? {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{} {} has no shim", binary_name, version),
+        ));
+    }
+    fs::remove_file(shim_path(binary_name, version)?)
+}
+
+// removes the shim of a deleted version, unless it runs another version of the same major by now
+pub fn remove_stale(binary_name: &str, version: &str) -> std::io::Result<bool> {
+    if !is_owner(binary_name, version)? {
+        return Ok(false);
+    }
+    fs::remove_file(shim_path(binary_name, version)?)?;
+    Ok(true)
+}
+
+// the executable named like the candidate, or the only one there is, e.g. spring for springboot
+pub fn executable(bin: &Path, binary_name: &str) -> std::io::Result<PathBuf> {
+    let named = bin.join(binary_name);
+    if named.is_file() {
+        return Ok(named);
+    }
+    let executables: Vec<PathBuf> = fs::read_dir(bin)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_none())
+        .collect();
+    match executables.as_slice() {
+        [only] => Ok(only.clone()),
+        _ => Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("no {} executable in {}", binary_name, bin.display()),
+        )),
+    }
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
@@ -0,0 +1,273 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Error;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+
+use eframe::egui::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use api::local::LocalCandidate;
+use api::manifest;
+use api::manifest::Kind;
+
+use crate::config;
+use crate::config::app_dir;
+use crate::history::Action;
+use crate::jobs::Step;
+use crate::util;
+
+const SNAPSHOTS_FILE: &str = "snapshots.toml";
+const EXPORT_FILE: &str = "sdkman-ui-snapshot.toml";
+const PADDING: f32 = 8.0;
+
+// the current version per candidate
+pub type CurrentVersions = BTreeMap<String, String>;
+
+// the current versions of all candidates under a name like "work" or "legacy-project"
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Snapshots {
+    snapshots: BTreeMap<String, CurrentVersions>,
+}
+
+impl Snapshots {
+    pub fn load() -> Snapshots {
+        util::load_toml(snapshots_path(), "the snapshots")
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        util::save_toml(&snapshots_path()?, self)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        self.snapshots.keys()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&CurrentVersions> {
+        self.snapshots.get(name)
+    }
+
+    // a snapshot of the same name is replaced
+    pub fn insert(&mut self, name: &str, versions: CurrentVersions) -> std::io::Result<()> {
+        self.snapshots.insert(name.to_string(), versions);
+        self.save()
+    }
+
+    pub fn remove(&mut self, name: &str) -> std::io::Result<()> {
+        self.snapshots.remove(name);
+        self.save()
+    }
+}
+
+// a single snapshot as exported for another machine
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SnapshotFile {
+    versions: CurrentVersions,
+}
+
+pub fn export(versions: &CurrentVersions, path: &Path) -> std::io::Result<()> {
+    let file = SnapshotFile {
+        versions: versions.clone(),
+    };
+    let content =
+        toml::to_string_pretty(&file).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+    fs::write(path, manifest::header(Kind::Snapshot) + &content)
+}
+
+pub fn import(path: &Path) -> std::io::Result<CurrentVersions> {
+    let content = fs::read_to_string(path)?;
+    let file: SnapshotFile = manifest::parse_toml(Kind::Snapshot, &content)
+        .map_err(config::manifest_error)?
+        .try_into()
+        .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+    Ok(file.versions)
+}
+
+pub fn current_versions(local_candidates: &[LocalCandidate]) -> CurrentVersions {
+    local_candidates
+        .iter()
+        .filter_map(|local| {
+            local
+                .current_version()
+                .map(|version| (local.binary_name().clone(), version.clone()))
+        })
+        .collect()
+}
+
+// the steps switching to the versions, all of them or none: an error names the versions that
+// aren't installed anymore; candidates the versions don't mention stay as they are
+pub fn restore_steps(
+    versions: &CurrentVersions,
+    local_candidates: &[LocalCandidate],
+) -> Result<Vec<Step>, Vec<String>> {
+    let mut steps = Vec::new();
+    let mut missing = Vec::new();
+    for (binary_name, version) in versions {
+        let local = local_candidates
+            .iter()
+            .find(|local| local.binary_name() == binary_name);
+        match local.and_then(|local| local.versions().get(version)) {
+            Some(true) => {}
+            Some(false) => steps.push(Step::new(binary_name, version, Action::SetCurrent)),
+            None => missing.push(format!("{} {}", binary_name, version)),
+        }
+    }
+    if missing.is_empty() {
+        Ok(steps)
+    } else {
+        Err(missing)
+    }
+}
+
+pub enum SnapshotsOutcome {
+    Open,
+    Restore(String, Vec<Step>),
+    Close,
+}
+
+// saving, restoring, deleting, exporting and importing snapshots
+#[derive(Default)]
+pub struct SnapshotsWindow {
+    snapshots: Snapshots,
+    name: String,
+    // the file snapshots are exported to and imported from
+    path: String,
+    error: Option<String>,
+}
+
+impl SnapshotsWindow {
+    pub fn open() -> SnapshotsWindow {
+        SnapshotsWindow {
+            snapshots: Snapshots::load(),
+            path: config::settings_file()
+                .with_file_name(EXPORT_FILE)
+                .display()
+                .to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn render(
+        &mut self,
+        ctx: &CtxRef,
+        local_candidates: &[LocalCandidate],
+    ) -> SnapshotsOutcome {
+        let mut outcome = SnapshotsOutcome::Open;
+        let mut open = true;
+        Window::new("Snapshots")
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label("The current versions of all candidates, restored with one click.");
+                ui.add_space(PADDING);
+                ui.horizontal(|ui| {
+                    ui.label("Name:");
+                    ui.text_edit_singleline(&mut self.name);
+                    let name = self.name.trim().to_string();
+                    if ui
+                        .add_enabled(!name.is_empty(), Button::new("Save the current versions"))
+                        .clicked()
+                    {
+                        let versions = current_versions(local_candidates);
+                        match self.snapshots.insert(&name, versions) {
+                            Ok(()) => self.name.clear(),
+                            Err(e) => {
+                                self.error =
+                                    Some(format!("Saving the snapshot failed with:\n{}", e))
+                            }
+                        }
+                    }
+                    if ui
+                        .add_enabled(!name.is_empty(), Button::new("Import"))
+                        .on_hover_text("Saves the snapshot in the file under the name")
+                        .clicked()
+                    {
+                        let imported = import(Path::new(self.path.trim()))
+                            .and_then(|versions| self.snapshots.insert(&name, versions));
+                        match imported {
+                            Ok(()) => self.name.clear(),
+                            Err(e) => {
+                                self.error =
+                                    Some(format!("Importing the snapshot failed with:\n{}", e))
+                            }
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("File:");
+                    ui.text_edit_singleline(&mut self.path);
+                });
+                ui.add_space(PADDING);
+
+                let mut removed = None;
+                Grid::new("snapshots")
+                    .striped(true)
+                    .num_columns(4)
+                    .spacing([4. * PADDING, PADDING])
+                    .show(ui, |ui| {
+                        for name in self.snapshots.names() {
+                            let versions = match self.snapshots.get(name) {
+                                Some(versions) => versions,
+                                None => continue,
+                            };
+                            let summary = versions
+                                .iter()
+                                .map(|(binary_name, version)| {
+                                    format!("{} {}", binary_name, version)
+                                })
+                                .collect::<Vec<String>>()
+                                .join("\n");
+                            ui.label(name).on_hover_text(summary);
+                            match restore_steps(versions, local_candidates) {
+                                Ok(steps) => {
+                                    if ui
+                                        .add_enabled(!steps.is_empty(), Button::new("Restore"))
+                                        .on_disabled_hover_text("These are the current versions")
+                                        .clicked()
+                                    {
+                                        outcome = SnapshotsOutcome::Restore(name.clone(), steps);
+                                    }
+                                }
+                                Err(missing) => {
+                                    ui.add_enabled(false, Button::new("Restore"))
+                                        .on_disabled_hover_text(format!(
+                                            "Not installed anymore:\n{}",
+                                            missing.join("\n")
+                                        ));
+                                }
+                            }
+                            if ui.button("Export").clicked() {
+                                let path = Path::new(self.path.trim());
+                                if let Err(e) = export(versions, path) {
+                                    self.error =
+                                        Some(format!("Exporting the snapshot failed with:\n{}", e));
+                                }
+                            }
+                            if ui.button("Delete").clicked() {
+                                removed = Some(name.clone());
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(name) = removed {
+                    if let Err(e) = self.snapshots.remove(&name) {
+                        self.error = Some(format!("Deleting the snapshot failed with:\n{}", e));
+                    }
+                }
+                if let Some(error) = &self.error {
+                    ui.colored_label(Color32::YELLOW, error);
+                }
+            });
+        if !open {
+            outcome = SnapshotsOutcome::Close;
+        }
+        outcome
+    }
+}
+
+fn snapshots_path() -> std::io::Result<PathBuf> {
+    app_dir().map(|dir| dir.join(SNAPSHOTS_FILE))
+}
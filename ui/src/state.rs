@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::candidates::DetailTab;
+use crate::candidates::VersionSort;
+use crate::config::app_dir;
+use crate::util;
+
+const STATE_FILE: &str = "state.toml";
+
+// what the user was looking at, restored on the next launch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    // the binary name of the candidate whose details were open
+    pub selected_candidate: Option<String>,
+    pub detail_tab: DetailTab,
+    pub version_sort: VersionSort,
+    pub vendor_filter: Option<String>,
+}
+
+impl Default for UiState {
+    fn default() -> Self {
+        Self {
+            selected_candidate: None,
+            detail_tab: DetailTab::Versions,
+            version_sort: VersionSort::Default,
+            vendor_filter: None,
+        }
+    }
+}
+
+impl UiState {
+    pub fn load() -> UiState {
+        util::load_toml(state_path(), "the UI state")
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        util::save_toml(&state_path()?, self)
+    }
+}
+
+fn state_path() -> std::io::Result<PathBuf> {
+    app_dir().map(|dir| dir.join(STATE_FILE))
+}
@@ -0,0 +1,315 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fs;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use eframe::egui::*;
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use serde::Serialize;
+
+use api::cache;
+use api::cache::Namespace;
+use api::release::ReleaseDate;
+use api::remote::fetch_broadcast;
+use api::remote::fetch_candidate_versions;
+use api::remote::versions_cache_key;
+use api::remote::RemoteCandidate;
+
+use crate::app_state::AppState;
+use crate::config::app_dir;
+use crate::repaint::RepaintNotifier;
+use crate::tasks;
+use crate::util;
+
+const INBOX_FILE: &str = "inbox.toml";
+// the inbox as an Atom feed, for subscribing with a feed reader
+const FEED_FILE: &str = "inbox.atom";
+// the oldest news go once there are more
+const INBOX_LIMIT: usize = 200;
+const PADDING: f32 = 8.0;
+
+lazy_static! {
+    // the refresher and the UI both change the inbox file
+    static ref INBOX_LOCK: Mutex<()> = Mutex::new(());
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InboxItem {
+    // empty for a broadcast
+    pub binary_name: String,
+    // the new version, or the text of the broadcast
+    pub text: String,
+    // seconds since the epoch
+    pub received: u64,
+    pub read: bool,
+}
+
+// the news about the subscribed candidates
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Inbox {
+    broadcast: String,
+    // the versions seen so far, anything else is a new release
+    known: BTreeMap<String, BTreeSet<String>>,
+    items: Vec<InboxItem>,
+}
+
+pub enum InboxAction {
+    Open(String),
+    Read(usize),
+    ReadAll,
+    CheckNow,
+}
+
+impl Inbox {
+    pub fn load() -> Inbox {
+        util::load_toml(inbox_path(), "the inbox")
+    }
+
+    // changes what's on disk, not a copy that may be outdated by now
+    pub fn update(change: impl FnOnce(&mut Inbox)) -> std::io::Result<Inbox> {
+        let _lock = INBOX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut inbox = Inbox::load();
+        change(&mut inbox);
+        inbox.save()?;
+        Ok(inbox)
+    }
+
+    pub fn unread(&self) -> usize {
+        self.items.iter().filter(|item| !item.read).count()
+    }
+
+    // fetches the versions of the subscribed candidates and the broadcast;
+    // the first check of a candidate only learns which versions there are
+    pub fn check(
+        candidates: &[Arc<RemoteCandidate>],
+        subscriptions: &[String],
+    ) -> std::io::Result<Inbox> {
+        let mut versions: Vec<(String, BTreeSet<String>)> = Vec::new();
+        for candidate in candidates
+            .iter()
+            .filter(|candidate| subscriptions.contains(candidate.binary_name()))
+        {
+            let binary_name = candidate.binary_name();
+            if let Err(e) =
+                cache::invalidate_entry(Namespace::Versions, &versions_cache_key(binary_name))
+            {
+                log!(
+                    "Failed to clear the cached versions of {}: {}",
+                    binary_name,
+                    e
+                );
+            }
+            let mut model = candidate.as_ref().clone();
+            match fetch_candidate_versions(&mut model) {
+                Ok(model) => versions.push((
+                    binary_name.clone(),
+                    model
+                        .versions()
+                        .iter()
+                        .map(|version| version.id().clone())
+                        .collect(),
+                )),
+                Err(e) => log!("Checking {} for new versions failed: {}", binary_name, e),
+            }
+        }
+        let broadcast = fetch_broadcast().unwrap_or_else(|e| {
+            log!("Fetching the broadcast failed: {}", e);
+            String::new()
+        });
+
+        let received = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        Inbox::update(|inbox| {
+            for (binary_name, ids) in versions {
+                if let Some(known) = inbox.known.get(&binary_name) {
+                    for id in ids.difference(known) {
+                        inbox.items.push(InboxItem {
+                            binary_name: binary_name.clone(),
+                            text: id.clone(),
+                            received,
+                            read: false,
+                        });
+                    }
+                }
+                inbox.known.entry(binary_name).or_default().extend(ids);
+            }
+            if !broadcast.is_empty() && broadcast != inbox.broadcast {
+                if !inbox.broadcast.is_empty() {
+                    inbox.items.push(InboxItem {
+                        binary_name: String::new(),
+                        text: broadcast.clone(),
+                        received,
+                        read: false,
+                    });
+                }
+                inbox.broadcast = broadcast;
+            }
+            let excess = inbox.items.len().saturating_sub(INBOX_LIMIT);
+            inbox.items.drain(..excess);
+        })
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        util::save_toml(&inbox_path()?, self)?;
+        fs::write(feed_path()?, self.atom())
+    }
+
+    // the news as Atom entries, the newest first
+    fn atom(&self) -> String {
+        let updated = self.items.last().map_or(0, |item| item.received);
+        let mut feed = format!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+             <feed xmlns=\"http://www.w3.org/2005/Atom\">\n\
+             <id>urn:sdkman-ui:inbox</id>\n\
+             <title>sdkman-ui subscriptions</title>\n\
+             <updated>{}</updated>\n",
+            atom_time(updated)
+        );
+        for item in self.items.iter().rev() {
+            let mut hasher = DefaultHasher::new();
+            (&item.binary_name, &item.text, item.received).hash(&mut hasher);
+            let title = if item.binary_name.is_empty() {
+                "SDKMAN broadcast".to_string()
+            } else {
+                format!("{} {} has been released", item.binary_name, item.text)
+            };
+            feed.push_str(&format!(
+                "<entry>\n<id>urn:sdkman-ui:inbox:{:016x}</id>\n<title>{}</title>\n\
+                 <updated>{}</updated>\n<content type=\"text\">{}</content>\n</entry>\n",
+                hasher.finish(),
+                escape_xml(&title),
+                atom_time(item.received),
+                escape_xml(&item.text)
+            ));
+        }
+        feed.push_str("</feed>\n");
+        feed
+    }
+
+    pub fn render(&self, ui: &mut Ui, subscriptions: &[String]) -> Option<InboxAction> {
+        let mut action = None;
+        ui.add_space(PADDING);
+        ui.horizontal(|ui| {
+            ui.heading("Subscriptions");
+            if ui
+                .button("⟳")
+                .on_hover_text("Check for new versions now")
+                .clicked()
+            {
+                action = Some(InboxAction::CheckNow);
+            }
+            if ui
+                .add_enabled(self.unread() > 0, Button::new("Mark all as read"))
+                .clicked()
+            {
+                action = Some(InboxAction::ReadAll);
+            }
+        });
+        ui.add_space(PADDING);
+        if subscriptions.is_empty() {
+            ui.label("Subscribe to a candidate with its 🔔 button to hear about its new versions here. They are checked by the background refresh.");
+            return action;
+        }
+        ui.label(format!("Subscribed to {}", subscriptions.join(", ")));
+        if let Ok(feed) = feed_path() {
+            ui.label(format!("Feed readers can subscribe to {}", feed.display()));
+        }
+        ui.add_space(PADDING);
+        if self.items.is_empty() {
+            ui.label("No news yet.");
+        }
+        // the newest first
+        for (index, item) in self.items.iter().enumerate().rev() {
+            ui.separator();
+            ui.horizontal(|ui| {
+                let marker = if item.read { " " } else { "●" };
+                ui.label(marker);
+                if item.binary_name.is_empty() {
+                    ui.add(Label::new(&item.text).monospace().wrap(true));
+                } else {
+                    if ui
+                        .selectable_label(false, &item.binary_name)
+                        .on_hover_text("Show the versions")
+                        .clicked()
+                    {
+                        action = Some(InboxAction::Open(item.binary_name.clone()));
+                    }
+                    ui.label(format!("{} has been released", item.text));
+                }
+                if !item.read && ui.small_button("Mark as read").clicked() {
+                    action = Some(InboxAction::Read(index));
+                }
+            });
+        }
+        action
+    }
+
+    pub fn mark_read(&mut self, index: Option<usize>) {
+        for (i, item) in self.items.iter_mut().enumerate() {
+            if index.is_none_or(|index| index == i) {
+                item.read = true;
+            }
+        }
+    }
+}
+
+// checks right away instead of waiting for the background refresh, e.g. after subscribing
+pub fn check_in_background(
+    candidates: Vec<Arc<RemoteCandidate>>,
+    subscriptions: Vec<String>,
+    state: &AppState,
+    repaint: &RepaintNotifier,
+) {
+    let state = state.clone();
+    let repaint = repaint.clone();
+    tasks::spawn(move || match Inbox::check(&candidates, &subscriptions) {
+        Ok(inbox) => {
+            state.set_inbox(inbox);
+            repaint.notify();
+        }
+        Err(e) => log!("Saving the inbox failed: {}", e),
+    });
+}
+
+fn inbox_path() -> std::io::Result<PathBuf> {
+    app_dir().map(|dir| dir.join(INBOX_FILE))
+}
+
+fn feed_path() -> std::io::Result<PathBuf> {
+    app_dir().map(|dir| dir.join(FEED_FILE))
+}
+
+// e.g. 2024-03-19T08:15:00Z
+fn atom_time(secs: u64) -> String {
+    let time = secs % 86400;
+    format!(
+        "{}T{:02}:{:02}:{:02}Z",
+        ReleaseDate::from_unix_millis(secs as i64 * 1000),
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+fn escape_xml(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
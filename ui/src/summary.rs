@@ -0,0 +1,387 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use eframe::egui::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use api::alias;
+use api::local::retrieve_candidate_usage;
+use api::local::LocalCandidate;
+use api::release::ReleaseDate;
+use api::remote::fetch_candidate_versions;
+use api::remote::RemoteCandidate;
+
+use crate::config::app_dir;
+use crate::repaint::RepaintNotifier;
+use crate::tasks;
+use crate::util;
+
+const SNAPSHOT_FILE: &str = "summary.toml";
+const SUMMARIES_DIR: &str = "summaries";
+const WEEK: Duration = Duration::from_secs(7 * 86400);
+const PADDING: f32 = 8.0;
+
+// what the toolchain looked like when the last summary was made
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct Snapshot {
+    // seconds since the epoch
+    taken: u64,
+    // the default versions of the installed candidates
+    defaults: BTreeMap<String, String>,
+    // bytes per installed candidate
+    disk_usage: BTreeMap<String, u64>,
+}
+
+pub struct NewVersion {
+    pub binary_name: String,
+    pub current: Option<String>,
+    pub version: String,
+}
+
+pub struct FlaggedJdk {
+    pub id: String,
+    pub update: String,
+}
+
+pub struct Growth {
+    pub binary_name: String,
+    pub before: u64,
+    pub after: u64,
+}
+
+// what changed in the toolchain since the last summary
+pub struct WeeklySummary {
+    // none for the first summary, which has nothing to compare with
+    pub since: Option<u64>,
+    pub new_versions: Vec<NewVersion>,
+    pub flagged_jdks: Vec<FlaggedJdk>,
+    pub growth: Vec<Growth>,
+    pub disk_usage: u64,
+}
+
+// once a week, whenever the app is started after the last summary is that old
+pub fn due() -> bool {
+    let taken = Snapshot::load().taken;
+    let now = now().as_secs();
+    now.saturating_sub(taken) >= WEEK.as_secs()
+}
+
+// compares the toolchain with the last snapshot and takes a new one
+pub fn build(
+    candidates: &[Arc<RemoteCandidate>],
+    local_candidates: &[LocalCandidate],
+) -> std::io::Result<WeeklySummary> {
+    let previous = Snapshot::load();
+    let mut snapshot = Snapshot {
+        taken: now().as_secs(),
+        ..Default::default()
+    };
+    let mut new_versions = Vec::new();
+    let mut flagged_jdks = Vec::new();
+    let mut growth = Vec::new();
+
+    for local_candidate in local_candidates {
+        let binary_name = local_candidate.binary_name();
+        let remote = candidates
+            .iter()
+            .find(|candidate| candidate.binary_name() == binary_name);
+        if let Some(remote) = remote {
+            let default = util::strip_parens(remote.default_version()).to_string();
+            let changed = previous
+                .defaults
+                .get(binary_name)
+                .is_some_and(|before| *before != default);
+            if changed && !local_candidate.versions().contains_key(&default) {
+                new_versions.push(NewVersion {
+                    binary_name: binary_name.clone(),
+                    current: local_candidate.current_version().cloned(),
+                    version: default.clone(),
+                });
+            }
+            snapshot.defaults.insert(binary_name.clone(), default);
+
+            if binary_name == "java" {
+                let mut model = remote.as_ref().clone();
+                match fetch_candidate_versions(&mut model) {
+                    Ok(model) => {
+                        let mut installed: Vec<&String> =
+                            local_candidate.versions().keys().collect();
+                        installed.sort();
+                        for id in installed {
+                            // an update that's installed already is the one to use, not news
+                            let update = alias::newer_update("java", id, model.versions())
+                                .filter(|update| !local_candidate.versions().contains_key(update));
+                            if let Some(update) = update {
+                                flagged_jdks.push(FlaggedJdk {
+                                    id: id.clone(),
+                                    update,
+                                });
+                            }
+                        }
+                    }
+                    Err(e) => log!("Checking the installed JDKs for updates failed: {}", e),
+                }
+            }
+        } else if let Some(before) = previous.defaults.get(binary_name) {
+            // not in the catalog this time, its default is compared next time
+            snapshot
+                .defaults
+                .insert(binary_name.clone(), before.clone());
+        }
+
+        match retrieve_candidate_usage(binary_name) {
+            Ok(usage) => {
+                let after = usage.disk_usage();
+                if let Some(before) = previous.disk_usage.get(binary_name) {
+                    if after > *before {
+                        growth.push(Growth {
+                            binary_name: binary_name.clone(),
+                            before: *before,
+                            after,
+                        });
+                    }
+                }
+                snapshot.disk_usage.insert(binary_name.clone(), after);
+            }
+            Err(e) => log!("Failed to retrieve disk usage of {}: {}", binary_name, e),
+        }
+    }
+    growth.sort_by_key(|growth| std::cmp::Reverse(growth.after - growth.before));
+
+    // without a catalog the defaults can't be compared, the next summary compares with the last one
+    if candidates.is_empty() {
+        log!("Keeping the last summary, the catalog isn't there to compare with");
+    } else {
+        snapshot.save()?;
+    }
+    Ok(WeeklySummary {
+        since: Some(previous.taken).filter(|taken| *taken > 0),
+        new_versions,
+        flagged_jdks,
+        growth,
+        disk_usage: snapshot.disk_usage.values().sum(),
+    })
+}
+
+impl Snapshot {
+    fn load() -> Snapshot {
+        util::load_toml(snapshot_path(), "the last summary")
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        util::save_toml(&snapshot_path()?, self)
+    }
+}
+
+impl WeeklySummary {
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        let _ = writeln!(markdown, "# Toolchain summary of {}\n", today());
+        if let Some(since) = self.since {
+            let _ = writeln!(markdown, "Changes since {}.\n", date(since));
+        }
+
+        let _ = writeln!(markdown, "## New versions\n");
+        if self.new_versions.is_empty() {
+            let _ = writeln!(markdown, "No new default versions of installed candidates.");
+        }
+        for new_version in &self.new_versions {
+            let _ = writeln!(
+                markdown,
+                "- {} {} (using {})",
+                new_version.binary_name,
+                new_version.version,
+                new_version.current.as_deref().unwrap_or("none")
+            );
+        }
+
+        let _ = writeln!(markdown, "\n## JDKs with missing updates\n");
+        if self.flagged_jdks.is_empty() {
+            let _ = writeln!(markdown, "All installed JDKs are up to date.");
+        }
+        for flagged in &self.flagged_jdks {
+            let _ = writeln!(markdown, "- {}: update to {}", flagged.id, flagged.update);
+        }
+
+        let _ = writeln!(markdown, "\n## Disk usage\n");
+        let _ = writeln!(
+            markdown,
+            "The installed candidates take {}.",
+            util::format_size(self.disk_usage)
+        );
+        for growth in &self.growth {
+            let _ = writeln!(
+                markdown,
+                "- {}: {} → {}",
+                growth.binary_name,
+                util::format_size(growth.before),
+                util::format_size(growth.after)
+            );
+        }
+        markdown
+    }
+
+    // writes the markdown next to the earlier ones and returns where
+    pub fn export(&self) -> std::io::Result<PathBuf> {
+        let dir = app_dir()?.join(SUMMARIES_DIR);
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("summary-{}.md", today()));
+        fs::write(&path, self.to_markdown())?;
+        Ok(path)
+    }
+}
+
+// the summary window shown on launch
+#[derive(Default)]
+pub struct SummaryWindow {
+    summary: Option<WeeklySummary>,
+    receiver: Option<Receiver<WeeklySummary>>,
+    exported: Option<Result<PathBuf, String>>,
+}
+
+impl SummaryWindow {
+    // walks the installed versions, so it's done in the background
+    pub fn load(
+        &mut self,
+        candidates: Vec<Arc<RemoteCandidate>>,
+        local_candidates: Vec<LocalCandidate>,
+        repaint: &RepaintNotifier,
+    ) {
+        let (sender, receiver) = mpsc::channel();
+        let repaint = repaint.clone();
+        tasks::spawn(move || match build(&candidates, &local_candidates) {
+            Ok(summary) => {
+                let _ = sender.send(summary);
+                repaint.notify();
+            }
+            Err(e) => log!("Saving the weekly summary failed: {}", e),
+        });
+        self.receiver = Some(receiver);
+    }
+
+    // renders the summary once it's there and returns the binary name of the candidate clicked on
+    pub fn render(&mut self, ctx: &CtxRef) -> Option<String> {
+        if let Some(summary) = self.receiver.as_ref().and_then(|r| r.try_recv().ok()) {
+            self.summary = Some(summary);
+            self.receiver = None;
+        }
+        let summary = self.summary.as_ref()?;
+        let mut clicked = None;
+        let mut open = true;
+        Window::new("Weekly summary")
+            .collapsible(false)
+            .open(&mut open)
+            .default_width(480.)
+            .show(ctx, |ui| {
+                if let Some(since) = summary.since {
+                    ui.label(format!("What changed since {}:", date(since)));
+                } else {
+                    ui.label("The first summary, later ones show what changed in a week.");
+                }
+                ui.add_space(PADDING);
+
+                ui.heading("New versions");
+                if summary.new_versions.is_empty() {
+                    ui.label("No new default versions of installed candidates.");
+                }
+                for new_version in &summary.new_versions {
+                    ui.horizontal(|ui| {
+                        if ui
+                            .selectable_label(false, &new_version.binary_name)
+                            .on_hover_text("Show the versions")
+                            .clicked()
+                        {
+                            clicked = Some(new_version.binary_name.clone());
+                        }
+                        ui.label(format!(
+                            "{}, using {}",
+                            new_version.version,
+                            new_version.current.as_deref().unwrap_or("none")
+                        ));
+                    });
+                }
+                ui.add_space(PADDING);
+
+                ui.heading("JDKs with missing updates");
+                if summary.flagged_jdks.is_empty() {
+                    ui.label("All installed JDKs are up to date.");
+                }
+                for flagged in &summary.flagged_jdks {
+                    ui.colored_label(
+                        Color32::YELLOW,
+                        format!("⚠ {}: update to {}", flagged.id, flagged.update),
+                    )
+                    .on_hover_text("Java updates mostly fix security issues");
+                }
+                ui.add_space(PADDING);
+
+                ui.heading("Disk usage");
+                ui.label(format!(
+                    "The installed candidates take {}.",
+                    util::format_size(summary.disk_usage)
+                ));
+                for growth in &summary.growth {
+                    ui.label(format!(
+                        "{}: {} → {}",
+                        growth.binary_name,
+                        util::format_size(growth.before),
+                        util::format_size(growth.after)
+                    ));
+                }
+                ui.add_space(PADDING);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Copy as Markdown").clicked() {
+                        ui.ctx().output().copied_text = summary.to_markdown();
+                    }
+                    if ui.button("Export as Markdown").clicked() {
+                        self.exported = Some(summary.export().map_err(|e| e.to_string()));
+                    }
+                });
+                match &self.exported {
+                    Some(Ok(path)) => {
+                        ui.label(format!("Saved to {}", path.display()));
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(
+                            Color32::YELLOW,
+                            format!("Exporting the summary failed with:\n{}", e),
+                        );
+                    }
+                    None => {}
+                }
+            });
+        if !open || clicked.is_some() {
+            self.summary = None;
+        }
+        clicked
+    }
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+fn date(secs: u64) -> ReleaseDate {
+    ReleaseDate::from_unix_millis(secs as i64 * 1000)
+}
+
+fn today() -> ReleaseDate {
+    date(now().as_secs())
+}
+
+fn snapshot_path() -> std::io::Result<PathBuf> {
+    app_dir().map(|dir| dir.join(SNAPSHOT_FILE))
+}
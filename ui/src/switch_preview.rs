@@ -0,0 +1,140 @@
+use eframe::egui::*;
+
+use api::local;
+
+use crate::jobs::Step;
+use crate::run;
+
+const PADDING: f32 = 8.0;
+
+pub enum SwitchPreviewOutcome {
+    Open,
+    Switch,
+    Cancel,
+}
+
+// what making another version current changes for the shells, before it does
+pub struct SwitchPreview {
+    pub step: Step,
+    from: String,
+    // the current/bin entry on the PATH and the home variable, resolved for both versions
+    bin: (String, String),
+    home_variable: String,
+    home: (String, String),
+    major: (Option<String>, Option<String>),
+    added: Vec<String>,
+    removed: Vec<String>,
+}
+
+impl SwitchPreview {
+    pub fn new(step: Step, from: &str) -> SwitchPreview {
+        let binary_name = step.binary_name.clone();
+        let home = |version: &str| {
+            local::home_path(&binary_name, version)
+                .map(|home| home.display().to_string())
+                .unwrap_or_default()
+        };
+        let bin = |version: &str| {
+            local::home_path(&binary_name, version)
+                .map(|home| home.join("bin").display().to_string())
+                .unwrap_or_default()
+        };
+        let before = run::executables(&binary_name, from);
+        let after = run::executables(&binary_name, &step.version);
+        SwitchPreview {
+            from: from.to_string(),
+            bin: (bin(from), bin(&step.version)),
+            home_variable: format!("{}_HOME", binary_name.to_uppercase()),
+            home: (home(from), home(&step.version)),
+            major: (major(from), major(&step.version)),
+            added: after
+                .iter()
+                .filter(|e| !before.contains(e))
+                .cloned()
+                .collect(),
+            removed: before
+                .iter()
+                .filter(|e| !after.contains(e))
+                .cloned()
+                .collect(),
+            step,
+        }
+    }
+
+    pub fn render(&self, ctx: &CtxRef) -> SwitchPreviewOutcome {
+        let mut outcome = SwitchPreviewOutcome::Open;
+        Window::new(format!("Switch {}", self.step.binary_name))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0., 0.])
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Making {} current instead of {} changes in every new shell:",
+                    self.step.version, self.from
+                ));
+                ui.add_space(PADDING);
+                Grid::new("switch_preview")
+                    .striped(true)
+                    .num_columns(3)
+                    .spacing([2. * PADDING, PADDING])
+                    .show(ui, |ui| {
+                        ui.label("PATH");
+                        ui.monospace(&self.bin.0);
+                        ui.monospace(format!("→ {}", self.bin.1));
+                        ui.end_row();
+                        ui.label(&self.home_variable);
+                        ui.monospace(&self.home.0);
+                        ui.monospace(format!("→ {}", self.home.1));
+                        ui.end_row();
+                        if let (Some(from), Some(to)) = &self.major {
+                            ui.label("Major version");
+                            ui.monospace(from);
+                            ui.monospace(format!("→ {}", to));
+                            ui.end_row();
+                        }
+                    });
+                if let (Some(from), Some(to)) = &self.major {
+                    if from != to {
+                        ui.add_space(PADDING);
+                        ui.colored_label(
+                            Color32::YELLOW,
+                            format!(
+                                "The major version changes, builds and scripts relying on {} may behave differently",
+                                from
+                            ),
+                        );
+                    }
+                }
+                if !self.added.is_empty() {
+                    ui.add_space(PADDING);
+                    ui.label("Commands it adds:");
+                    ui.monospace(self.added.join(" "));
+                }
+                if !self.removed.is_empty() {
+                    ui.add_space(PADDING);
+                    ui.label("Commands it takes away:");
+                    ui.monospace(self.removed.join(" "));
+                }
+                ui.add_space(PADDING);
+                ui.horizontal(|ui| {
+                    if ui.button("Switch").clicked() {
+                        outcome = SwitchPreviewOutcome::Switch;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        outcome = SwitchPreviewOutcome::Cancel;
+                    }
+                });
+            });
+        outcome
+    }
+}
+
+// the leading number of a version, e.g. 21 of 21.0.2-tem
+fn major(version: &str) -> Option<String> {
+    let major: String = version.chars().take_while(char::is_ascii_digit).collect();
+    if major.is_empty() {
+        None
+    } else {
+        Some(major)
+    }
+}
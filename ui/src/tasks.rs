@@ -0,0 +1,46 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use std::time::Instant;
+
+static PENDING: AtomicUsize = AtomicUsize::new(0);
+
+// decrements the counter even if the task panics
+struct PendingGuard;
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        PENDING.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// spawns a background thread that is counted as pending until it finishes
+pub fn spawn<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    PENDING.fetch_add(1, Ordering::SeqCst);
+    thread::spawn(move || {
+        let _guard = PendingGuard;
+        f()
+    })
+}
+
+pub fn pending() -> usize {
+    PENDING.load(Ordering::SeqCst)
+}
+
+// waits for the pending tasks to finish, but no longer than the timeout; false if some are left
+pub fn wait(timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    while pending() > 0 {
+        if Instant::now() >= deadline {
+            return false;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    true
+}
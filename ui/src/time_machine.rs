@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use eframe::egui::*;
+
+use api::local::LocalCandidate;
+
+use crate::history::History;
+use crate::jobs::Step;
+use crate::snapshots;
+use crate::util;
+
+const PADDING: f32 = 8.0;
+
+pub enum TimeMachineOutcome {
+    Open,
+    Revert(SystemTime, Vec<Step>),
+    Close,
+}
+
+// a switch of the current version of a candidate
+struct Switch {
+    timestamp: SystemTime,
+    candidate: String,
+    // the version the switch replaced, as far as the history knows
+    from: Option<String>,
+    version: String,
+    // the steps back to the state as of the switch, or the versions that aren't there anymore;
+    // none for the latest switch, which is the state as of now
+    revert: Option<Result<Vec<Step>, Vec<String>>>,
+}
+
+// when the current version of each candidate changed, worked out when the window opens and
+// whenever the installed versions change, not every frame
+pub struct TimeMachine {
+    switches: Vec<Switch>,
+}
+
+impl TimeMachine {
+    pub fn new(history: &History, local_candidates: &[LocalCandidate]) -> TimeMachine {
+        let mut previous: HashMap<&str, &str> = HashMap::new();
+        let entries: Vec<_> = history.switches().collect();
+        let switches = entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let from = previous.insert(&entry.candidate, &entry.version);
+                let revert = (index + 1 < entries.len()).then(|| {
+                    let versions = history.current_versions_at(entry.timestamp);
+                    snapshots::restore_steps(&versions, local_candidates)
+                });
+                Switch {
+                    timestamp: entry.timestamp,
+                    candidate: entry.candidate.clone(),
+                    from: from.map(str::to_string),
+                    version: entry.version.clone(),
+                    revert,
+                }
+            })
+            .collect();
+        TimeMachine { switches }
+    }
+
+    // most recent first
+    pub fn render(&self, ctx: &CtxRef) -> TimeMachineOutcome {
+        let mut outcome = TimeMachineOutcome::Open;
+        let mut open = true;
+        Window::new("Time machine")
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                if self.switches.is_empty() {
+                    ui.label("No version switches recorded yet.");
+                    return;
+                }
+                ui.label(
+                    "Reverting restores the current versions of all candidates as they were then.",
+                );
+                ui.add_space(PADDING);
+                ScrollArea::vertical().max_height(400.).show(ui, |ui| {
+                    Grid::new("time_machine")
+                        .striped(true)
+                        .num_columns(4)
+                        .spacing([4. * PADDING, PADDING])
+                        .show(ui, |ui| {
+                            for switch in self.switches.iter().rev() {
+                                if let Some(revert) = render_switch(ui, switch) {
+                                    outcome = revert;
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+        if !open {
+            outcome = TimeMachineOutcome::Close;
+        }
+        outcome
+    }
+}
+
+fn render_switch(ui: &mut Ui, switch: &Switch) -> Option<TimeMachineOutcome> {
+    ui.label(util::format_date(switch.timestamp))
+        .on_hover_text(util::format_elapsed(switch.timestamp));
+    ui.label(&switch.candidate);
+    match &switch.from {
+        Some(from) => ui.monospace(format!("{} → {}", from, switch.version)),
+        None => ui.monospace(format!("→ {}", switch.version)),
+    };
+    let label = format!(
+        "Revert to state as of {}",
+        util::format_date(switch.timestamp)
+    );
+    match &switch.revert {
+        Some(Ok(steps)) => {
+            let clicked = ui
+                .add_enabled(!steps.is_empty(), Button::new(label))
+                .on_disabled_hover_text("These are the current versions")
+                .clicked();
+            if clicked {
+                return Some(TimeMachineOutcome::Revert(switch.timestamp, steps.clone()));
+            }
+        }
+        Some(Err(missing)) => {
+            ui.add_enabled(false, Button::new(label))
+                .on_disabled_hover_text(format!("Not installed anymore:\n{}", missing.join("\n")));
+        }
+        None => {
+            ui.label("");
+        }
+    }
+    None
+}
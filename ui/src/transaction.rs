@@ -0,0 +1,120 @@
+use eframe::egui::*;
+
+use crate::jobs::Step;
+
+const PADDING: f32 = 8.0;
+
+// actions staged by the user, applied together and in order as a single job
+#[derive(Default)]
+pub struct Transaction {
+    steps: Vec<Step>,
+    reviewing: bool,
+}
+
+impl Transaction {
+    pub fn stage(&mut self, step: Step) {
+        if !self.steps.contains(&step) {
+            self.steps.push(step);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn take(&mut self) -> Vec<Step> {
+        std::mem::take(&mut self.steps)
+    }
+
+    pub fn review(&mut self) {
+        self.reviewing = true;
+    }
+
+    // renders the review window; returns the steps to apply once the user confirmed them
+    pub fn render_review(&mut self, ctx: &CtxRef) -> Option<Vec<Step>> {
+        if !self.reviewing {
+            return None;
+        }
+        let mut apply = false;
+        let mut discard = false;
+        let mut close = false;
+        let mut move_up = None;
+        let mut remove = None;
+        let count = self.steps.len();
+        Window::new("Pending changes")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0., 0.])
+            .show(ctx, |ui| {
+                ui.add_space(PADDING);
+                if self.steps.is_empty() {
+                    ui.label("Nothing has been staged yet.");
+                }
+                Grid::new("pending_changes")
+                    .striped(true)
+                    .spacing([2. * PADDING, PADDING])
+                    .show(ui, |ui| {
+                        for (index, step) in self.steps.iter().enumerate() {
+                            ui.label(format!("{}.", index + 1));
+                            ui.monospace(step.to_string());
+                            ui.horizontal(|ui| {
+                                if ui.add_enabled(index > 0, Button::new("⬆")).clicked() {
+                                    move_up = Some(index);
+                                }
+                                if ui
+                                    .add_enabled(index + 1 < count, Button::new("⬇"))
+                                    .clicked()
+                                {
+                                    move_up = Some(index + 1);
+                                }
+                                if ui.button("✖").on_hover_text("Unstage").clicked() {
+                                    remove = Some(index);
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                ui.add_space(PADDING);
+                ui.label(
+                    "The changes are applied in this order; the first failure stops the rest.",
+                );
+                ui.add_space(PADDING);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!self.steps.is_empty(), Button::new("Apply"))
+                        .clicked()
+                    {
+                        apply = true;
+                    }
+                    if ui.button("Discard").clicked() {
+                        discard = true;
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+            });
+        if let Some(index) = move_up {
+            self.steps.swap(index - 1, index);
+        }
+        if let Some(index) = remove {
+            self.steps.remove(index);
+        }
+        if discard {
+            self.steps.clear();
+        }
+        if apply || discard || close {
+            self.reviewing = false;
+        }
+        if apply {
+            Some(std::mem::take(&mut self.steps))
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::fs;
+use std::io::Error;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use api::fsutil;
+use api::release::ReleaseDate;
+
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024. && unit < UNITS.len() - 1 {
+        size /= 1024.;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[unit])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+pub fn format_elapsed(time: SystemTime) -> String {
+    let seconds = SystemTime::now()
+        .duration_since(time)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let (amount, unit) = match seconds {
+        s if s < 60 => return "just now".to_string(),
+        s if s < 3600 => (s / 60, "minute"),
+        s if s < 86400 => (s / 3600, "hour"),
+        s if s < 30 * 86400 => (s / 86400, "day"),
+        s if s < 365 * 86400 => (s / (30 * 86400), "month"),
+        s => (s / (365 * 86400), "year"),
+    };
+    format!(
+        "{} {}{} ago",
+        amount,
+        unit,
+        if amount == 1 { "" } else { "s" }
+    )
+}
+
+pub fn format_date(time: SystemTime) -> String {
+    let millis = time
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_millis() as i64)
+        .unwrap_or_default();
+    ReleaseDate::from_unix_millis(millis).to_string()
+}
+
+// the default version is extracted from the candidate description as "(x.y.z)"
+pub fn strip_parens(version: &str) -> &str {
+    version.trim_start_matches('(').trim_end_matches(')')
+}
+
+pub fn open_path(path: &Path) -> std::io::Result<()> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    Command::new(opener).arg(path).spawn().map(|_| ())
+}
+
+// a file the app keeps its own data in; a missing or broken one starts afresh
+pub fn load_toml<T: DeserializeOwned + Default>(path: std::io::Result<PathBuf>, what: &str) -> T {
+    match path.and_then(fs::read_to_string) {
+        Ok(content) => toml::from_str(&content).unwrap_or_else(|e| {
+            log!("Failed to parse {}, starting afresh: {}", what, e);
+            T::default()
+        }),
+        Err(_) => T::default(),
+    }
+}
+
+// replaced in one step, as some of the files are watched and read again while the app writes them
+pub fn save_toml<T: Serialize>(path: &Path, value: &T) -> std::io::Result<()> {
+    let content =
+        toml::to_string_pretty(value).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+    fsutil::write_atomic(path, content.as_bytes())
+}
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use eframe::egui::*;
+use eframe::epi;
+
+use api::remote::parse_available_versions;
+use api::remote::parse_candidates;
+use api::remote::RemoteCandidate;
+use api::remote::RemoteVersion;
+
+// the SDKMAN API doesn't send CORS headers, so the browser has to go through a proxy that does
+const PROXY_URL: &str = match option_env!("SDKMAN_UI_PROXY") {
+    Some(url) => url,
+    None => "http://localhost:8080/2",
+};
+const PLATFORMS: [&str; 5] = [
+    "linuxx64",
+    "linuxarm64",
+    "darwinx64",
+    "darwinarm64",
+    "windowsx64",
+];
+const PADDING: f32 = 8.0;
+
+// filled in by the callback of the request, empty while it's running
+type Fetched<T> = Arc<Mutex<Option<Result<T, String>>>>;
+
+// the candidate browser for the web: read-only, as a browser can't install anything
+#[derive(Default)]
+pub struct WebApp {
+    candidates: Fetched<Vec<RemoteCandidate>>,
+    versions: HashMap<(String, &'static str), Fetched<Vec<RemoteVersion>>>,
+    selected: Option<String>,
+    platform: usize,
+    search: String,
+}
+
+fn fetch<T: Send + 'static>(
+    url: String,
+    parse: fn(&str) -> T,
+    repaint: Arc<dyn epi::RepaintSignal>,
+) -> Fetched<T> {
+    let fetched: Fetched<T> = Arc::new(Mutex::new(None));
+    let target = fetched.clone();
+    ehttp::fetch(ehttp::Request::get(&url), move |result| {
+        let parsed = result.and_then(|response| {
+            if response.ok {
+                Ok(parse(&String::from_utf8_lossy(&response.bytes)))
+            } else {
+                Err(format!("{} {}", response.status, response.status_text))
+            }
+        });
+        if let Ok(mut target) = target.lock() {
+            *target = Some(parsed);
+        }
+        repaint.request_repaint();
+    });
+    fetched
+}
+
+impl epi::App for WebApp {
+    fn name(&self) -> &str {
+        "sdkman-ui"
+    }
+
+    fn setup(
+        &mut self,
+        _ctx: &CtxRef,
+        frame: &mut epi::Frame<'_>,
+        _storage: Option<&dyn epi::Storage>,
+    ) {
+        self.candidates = fetch(
+            format!("{}/candidates/list", PROXY_URL),
+            |text| parse_candidates(text.to_string()),
+            frame.repaint_signal(),
+        );
+    }
+
+    fn update(&mut self, ctx: &CtxRef, frame: &mut epi::Frame<'_>) {
+        SidePanel::left("candidates").show(ctx, |ui| self.render_list(ui));
+        CentralPanel::default().show(ctx, |ui| self.render_details(ui, frame));
+    }
+}
+
+impl WebApp {
+    fn render_list(&mut self, ui: &mut Ui) {
+        ui.add_space(PADDING);
+        ui.heading("Candidates");
+        ui.text_edit_singleline(&mut self.search);
+        ui.add_space(PADDING);
+        let candidates = self.candidates.clone();
+        let candidates = candidates.lock().unwrap_or_else(|e| e.into_inner());
+        match &*candidates {
+            None => {
+                ui.label("Loading…");
+            }
+            Some(Err(e)) => {
+                ui.label(format!("Loading the candidates failed with:\n{}", e));
+            }
+            Some(Ok(candidates)) => {
+                let search = self.search.trim().to_lowercase();
+                ScrollArea::vertical().show(ui, |ui| {
+                    for candidate in candidates
+                        .iter()
+                        .filter(|candidate| candidate.name().to_lowercase().contains(&search))
+                    {
+                        let selected =
+                            self.selected.as_deref() == Some(candidate.binary_name().as_str());
+                        if ui.selectable_label(selected, candidate.name()).clicked() {
+                            self.selected = Some(candidate.binary_name().clone());
+                        }
+                    }
+                });
+            }
+        }
+    }
+
+    fn render_details(&mut self, ui: &mut Ui, frame: &epi::Frame<'_>) {
+        let binary_name = match &self.selected {
+            Some(binary_name) => binary_name.clone(),
+            None => {
+                ui.label("Pick a candidate to see its versions.");
+                return;
+            }
+        };
+        let candidates = self.candidates.clone();
+        let candidates = candidates.lock().unwrap_or_else(|e| e.into_inner());
+        let candidate = match &*candidates {
+            Some(Ok(candidates)) => candidates
+                .iter()
+                .find(|candidate| *candidate.binary_name() == binary_name),
+            _ => None,
+        };
+        let candidate = match candidate {
+            Some(candidate) => candidate,
+            None => return,
+        };
+
+        ui.add_space(PADDING);
+        ui.heading(candidate.name());
+        ui.hyperlink(candidate.homepage());
+        ui.add_space(PADDING);
+        ui.add(Label::new(candidate.description()).wrap(true));
+        ui.add_space(PADDING);
+        ComboBox::from_label("Platform")
+            .selected_text(PLATFORMS[self.platform])
+            .show_ui(ui, |ui| {
+                for (index, platform) in PLATFORMS.iter().enumerate() {
+                    ui.selectable_value(&mut self.platform, index, *platform);
+                }
+            });
+        ui.label(format!(
+            "Install versions with `sdk install {} <version>` in a terminal.",
+            binary_name
+        ));
+        ui.separator();
+
+        let platform = PLATFORMS[self.platform];
+        let versions = self
+            .versions
+            .entry((binary_name.clone(), platform))
+            .or_insert_with(|| {
+                fetch(
+                    format!(
+                        "{}/candidates/{}/{}/versions/list?installed=",
+                        PROXY_URL, binary_name, platform
+                    ),
+                    parse_available_versions,
+                    frame.repaint_signal(),
+                )
+            })
+            .clone();
+        let versions = versions.lock().unwrap_or_else(|e| e.into_inner());
+        match &*versions {
+            None => {
+                ui.label("Loading…");
+            }
+            Some(Err(e)) => {
+                ui.label(format!("Loading the versions failed with:\n{}", e));
+            }
+            Some(Ok(versions)) if versions.is_empty() => {
+                ui.label(format!("No versions available for {}.", platform));
+            }
+            Some(Ok(versions)) => {
+                ScrollArea::vertical().show(ui, |ui| {
+                    for version in versions {
+                        ui.monospace(format!("{: <13} {}", version.vendor(), version.id()));
+                    }
+                });
+            }
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen::prelude::wasm_bindgen]
+pub fn start(canvas_id: &str) -> Result<(), wasm_bindgen::JsValue> {
+    eframe::start_web(canvas_id, Box::new(WebApp::default()))
+}